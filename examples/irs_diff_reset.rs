@@ -1,99 +1,87 @@
-/*
-use std::{collections::BTreeMap, str::FromStr};
+use chrono::NaiveDate;
 
-use chrono::{Months, NaiveDate};
-use dpm::{
-    conventions::{
-        business_day::{BusinessDay, BusinessDayConventions},
-        day_count::{DayCount, DayCountConventions},
-    },
-    core::sequence::Sequence,
-    interest::term_structure::{CurveParameters, TermStructure},
-    iso::iso3166::CountryTwoCode,
-    math::interpolation::{self, Interpolate},
-    resources::holidays,
+use dpm::conventions::day_count::DayCountConventions;
+use dpm::interest::fixings::Fixings;
+use dpm::interest::swap::{
+    CompoundingMethods, Direction, InterestRateSwap, Notionals, SwapLeg, SwapPricer,
 };
+use dpm::interest::yield_curve::YieldCurve;
+use dpm::resources::market_data::load_curve;
+use dpm::time::calendar::Calendar;
+use dpm::time::periods::IntervalPeriod;
+use dpm::time::schedule::ScheduleBuilder;
 
-const CLIENT_VALUE: f64 = -24_351_735.5;
-*/
+// The client valued the swap at -24_351_735.50 as at 2023-12-31, whereas the market
+// data available is as at 2022-12-31.
 
-fn main() {
-    /*
-        let inception_date = NaiveDate::from_ymd_opt(2013, 8, 26).unwrap();
-        let termination_date = NaiveDate::from_ymd_opt(2030, 9, 26).unwrap();
-        let valuation_date = NaiveDate::from_ymd_opt(2023, 12, 31).unwrap();
-        let reset_tenor = Months::new(3);
-        let payment_tenor = Months::new(6);
-        let fixed_rate = 0.09187;
-        let bdc = BusinessDayConventions::default();
-        let dcc = DayCountConventions::default();
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Contractual terms.
+    let inception_date = NaiveDate::from_ymd_opt(2013, 8, 26).unwrap();
+    let termination_date = NaiveDate::from_ymd_opt(2030, 9, 26).unwrap();
+    let valuation_date = NaiveDate::from_ymd_opt(2022, 12, 31).unwrap();
+    let fixed_rate = 0.09187;
+    let calendar = Calendar::load("ZA")?;
+    let dcc = DayCountConventions::default();
 
-        let country_code: String = CountryTwoCode::from_str("ZA").unwrap().into();
-        let public_holidays = holidays::load_holidays(&country_code).unwrap();
+    // Amortising notionals of the outstanding payment periods. The floating rate
+    // resets quarterly, compounding into semi-annual payments.
+    let outstanding = vec![
+        883_304_310.15,
+        843_440_994.32,
+        722_922_858.41,
+        683_372_753.57,
+        644_409_517.34,
+        601_259_575.21,
+        558_556_590.33,
+        511_465_862.20,
+        464_686_029.06,
+        413_514_384.77,
+        362_146_031.56,
+        306_006_959.76,
+        249_782_107.16,
+        188_430_346.81,
+        126_948_482.02,
+        59_984_734.41,
+    ];
 
-        let nominal = vec![
-            883_304_310.15,
-            883_304_310.15,
-            843_440_994.32,
-            843_440_994.32,
-            722_922_858.41,
-            722_922_858.41,
-            683_372_753.57,
-            683_372_753.57,
-            644_409_517.34,
-            644_409_517.34,
-            601_259_575.21,
-            601_259_575.21,
-            558_556_590.33,
-            558_556_590.33,
-            511_465_862.20,
-            511_465_862.20,
-            464_686_029.06,
-            464_686_029.06,
-            413_514_384.77,
-            413_514_384.77,
-            362_146_031.56,
-            362_146_031.56,
-            306_006_959.76,
-            306_006_959.76,
-            249_782_107.16,
-            249_782_107.16,
-            188_430_346.81,
-            188_430_346.81,
-            126_948_482.02,
-            126_948_482.02,
-            59_984_734.41,
-            59_984_734.41,
-        ];
+    let schedule =
+        ScheduleBuilder::new(inception_date, termination_date, IntervalPeriod::Months(6));
 
-        // Ops
-        let seq_reset = NaiveDate::seq(inception_date, termination_date, reset_tenor);
-        let seq_payment = NaiveDate::seq(inception_date, termination_date, payment_tenor);
+    // One notional is required per period. Settled periods do not affect the value,
+    // and carry the first outstanding notional.
+    let periods = schedule.build(&calendar)?.periods().len();
+    let mut notionals = vec![outstanding[0]; periods - outstanding.len()];
+    notionals.extend_from_slice(&outstanding);
+    let nominal = Notionals::Explicit(notionals);
+    let swap = InterestRateSwap::new(
+        SwapLeg::fixed(
+            Direction::Pay,
+            nominal.clone(),
+            schedule.clone(),
+            fixed_rate,
+        )
+        .calendar(calendar.clone()),
+        SwapLeg::floating(Direction::Receive, nominal, schedule)
+            .reset_frequency(IntervalPeriod::Months(3))
+            .compounding(CompoundingMethods::Straight)
+            .calendar(calendar.clone())
+            .index("JIBAR3M")
+            .fixing_calendar(calendar),
+    );
 
-        let seq_reset: Vec<NaiveDate> = bdc.business_day(&seq_reset, &public_holidays);
-        let seq_payment: Vec<NaiveDate> = bdc.business_day(&seq_payment, &public_holidays);
+    // Market data.
+    let curve = YieldCurve::from_days(valuation_date, &load_curve("zar_swap")?, dcc);
+    let fixings = Fixings::new().load("JIBAR3M", "jibar")?;
 
-        let discount_fractions = dcc.year_fraction(&valuation_date, &seq_payment[1..].to_vec());
+    let valuation = SwapPricer::new(valuation_date, curve)
+        .fixings(&fixings)
+        .price(&swap)?;
 
-        let interest_fractions = dcc.year_fraction(
-            &seq_reset[..seq_reset.len() - 1].to_vec(),
-            &seq_reset[1..].to_vec(),
-        );
+    for leg in valuation.legs() {
+        println!("{}", leg);
+    }
+    println!("NPV: {:.2}", valuation.npv());
+    println!("Par rate: {:.6}", valuation.par_rate());
 
-        let reset_fractions = dcc.year_fraction(&valuation_date, &seq_reset);
-
-        let path = "src/resources/curves";
-        let dir = format!("{}/zar_swap.txt", path);
-        let contents = std::fs::read_to_string(dir).unwrap();
-        let curve: BTreeMap<u32, f64> = serde_json::from_str(&contents).unwrap();
-        let curve: CurveParameters<f64> = curve.into();
-
-        let (x, y) = curve.unpack_with_map_x(|a| a / 365.0);
-
-        let discount_factors = interpolation::Exponential.interpolate(&x, &y, &discount_fractions);
-        let forward_factors = interpolation::Exponential.interpolate(&x, &y, &reset_fractions);
-
-        println!("{:?}", reset_fractions);
-
-    */
+    Ok(())
 }
@@ -4,25 +4,20 @@ use chrono::{Datelike, Days, NaiveDate, Weekday};
 // TODO implement unit tests.
 // TODO Add error handling. Specifically when dates apprach NaiveDate::MIN or NaiveDate::MAX.
 // TODO add documentation.
-// FIXME, both the Effective and Termination date should not be adjusted in terms of the business day convention.
 
 //  --- Errors
 
 //  --- Enums
+#[derive(Clone, Copy, Debug, Default)]
 pub enum BusinessDayConventions {
     Actual,
     Following,
     Preceding,
+    #[default]
     ModifiedFollowing,
     ModifiedPreceding,
 }
 
-impl Default for BusinessDayConventions {
-    fn default() -> Self {
-        Self::ModifiedFollowing
-    }
-}
-
 //  --- Structs
 struct FollowingBusinessDay;
 struct PrecedingBusinessDay;
@@ -39,6 +34,10 @@ pub trait BusinessDayOperations: Sized {
 
     fn is_weekend(&self) -> bool;
 
+    fn is_business_day(&self, public_holidays: &[Self]) -> bool {
+        !self.is_weekend() && !self.is_holiday(public_holidays)
+    }
+
     fn get_year(&self) -> i32;
 
     fn get_month(&self) -> u32;
@@ -52,8 +51,22 @@ pub trait BusinessDayOperations: Sized {
     }
 }
 
+/// Holiday collections against which dates are adjusted.
+pub trait BusinessCalendar<A> {
+    fn is_business_day(&self, value: &A) -> bool;
+}
+
 //  --- Trait implementations: Concrete
 
+impl<A> BusinessCalendar<A> for Vec<A>
+where
+    A: BusinessDayOperations,
+{
+    fn is_business_day(&self, value: &A) -> bool {
+        value.is_business_day(self)
+    }
+}
+
 impl BusinessDayOperations for NaiveDate {
     fn is_holiday(&self, public_holidays: &[Self]) -> bool {
         public_holidays.contains(self)
@@ -80,37 +93,40 @@ impl BusinessDayOperations for NaiveDate {
     }
 }
 
-impl<A> BusinessDay<A, Vec<A>> for FollowingBusinessDay
+impl<A, B> BusinessDay<A, B> for FollowingBusinessDay
 where
     A: BusinessDayOperations + Copy,
+    B: BusinessCalendar<A>,
 {
-    fn business_day(&self, value: &A, public_holidays: &Vec<A>) -> A {
+    fn business_day(&self, value: &A, public_holidays: &B) -> A {
         let mut following_date = *value;
-        while following_date.is_holiday(public_holidays) || following_date.is_weekend() {
+        while !public_holidays.is_business_day(&following_date) {
             following_date = following_date.add_day();
         }
         following_date
     }
 }
 
-impl<A> BusinessDay<A, Vec<A>> for PrecedingBusinessDay
+impl<A, B> BusinessDay<A, B> for PrecedingBusinessDay
 where
     A: BusinessDayOperations + Copy,
+    B: BusinessCalendar<A>,
 {
-    fn business_day(&self, value: &A, public_holidays: &Vec<A>) -> A {
+    fn business_day(&self, value: &A, public_holidays: &B) -> A {
         let mut preceding_date = *value;
-        while preceding_date.is_holiday(public_holidays) || preceding_date.is_weekend() {
+        while !public_holidays.is_business_day(&preceding_date) {
             preceding_date = preceding_date.sub_day();
         }
         preceding_date
     }
 }
 
-impl<A> BusinessDay<A, Vec<A>> for ModifiedFollowingBusinessDay
+impl<A, B> BusinessDay<A, B> for ModifiedFollowingBusinessDay
 where
     A: BusinessDayOperations + Copy,
+    B: BusinessCalendar<A>,
 {
-    fn business_day(&self, value: &A, public_holidays: &Vec<A>) -> A {
+    fn business_day(&self, value: &A, public_holidays: &B) -> A {
         let mut following_date = FollowingBusinessDay.business_day(value, public_holidays);
         if following_date.is_next_month(value) {
             following_date = PrecedingBusinessDay.business_day(value, public_holidays);
@@ -119,11 +135,12 @@ where
     }
 }
 
-impl<A> BusinessDay<A, Vec<A>> for ModifiedPrecedingBusinessDay
+impl<A, B> BusinessDay<A, B> for ModifiedPrecedingBusinessDay
 where
     A: BusinessDayOperations + Copy,
+    B: BusinessCalendar<A>,
 {
-    fn business_day(&self, value: &A, public_holidays: &Vec<A>) -> A {
+    fn business_day(&self, value: &A, public_holidays: &B) -> A {
         let mut preceding_date = PrecedingBusinessDay.business_day(value, public_holidays);
         if preceding_date.is_next_month(value) {
             preceding_date = FollowingBusinessDay.business_day(value, public_holidays);
@@ -132,11 +149,12 @@ where
     }
 }
 
-impl<A> BusinessDay<A, Vec<A>> for BusinessDayConventions
+impl<A, B> BusinessDay<A, B> for BusinessDayConventions
 where
     A: BusinessDayOperations + Copy,
+    B: BusinessCalendar<A>,
 {
-    fn business_day(&self, value: &A, public_holidays: &Vec<A>) -> A {
+    fn business_day(&self, value: &A, public_holidays: &B) -> A {
         match self {
             Self::Actual => *value,
             Self::Following => FollowingBusinessDay.business_day(value, public_holidays),
@@ -153,12 +171,12 @@ where
 
 //  --- Trait implementations: Blanket
 
-impl<A, B> BusinessDay<Vec<A>> for B
+impl<A, B, C> BusinessDay<Vec<A>, C> for B
 where
     A: BusinessDayOperations + Copy,
-    B: BusinessDay<A, Vec<A>>,
+    B: BusinessDay<A, C>,
 {
-    fn business_day(&self, value: &Vec<A>, public_holidays: &Vec<A>) -> Vec<A> {
+    fn business_day(&self, value: &Vec<A>, public_holidays: &C) -> Vec<A> {
         value
             .iter()
             .copied()
@@ -2,66 +2,151 @@
 
 use chrono::{Datelike, NaiveDate};
 
-// TODO add unit tests.
+use crate::conventions::business_day::BusinessCalendar;
+use crate::time::ops::EndOfMonth;
+use crate::time::periods::{self, IntervalPeriod};
+
 // TODO add error handling?
-// TODO implement each of the below day count conventions.
 /*
-Day count conventions from Refinitiv:
-30/360
-30/360 US
-30/360 GER
-30/360 ISDA
-30/365 ISDA
-30/365 GER
-30/365 BRA
-30/Actual GER
-30/Actual
-30/Actual ISDA
-30E/360 ISMA
-Actual/360
-Actual/364
-Actual/365
-Actual/Actual
-Actual/Actual ISDA
-Actual/Actual AFB
-WorkingDays/252
-Actual/365LP
-Actual/365P
-ActualLeapDay/365
-ActualLeapDay/360
-Actual/36525
-Actual/Actual CAD Convention
+Day count conventions from Refinitiv, and the variant implementing each:
+30/360                          Thirty360Bond
+30/360 US                       Thirty360US
+30/360 GER                      Thirty360German
+30/360 ISDA                     Thirty360Bond
+30/365 ISDA                     Thirty365ISDA
+30/365 GER                      Thirty365German
+30/365 BRA                      Thirty365Brazil
+30/Actual GER                   ThirtyActualGerman
+30/Actual                       ThirtyActual
+30/Actual ISDA                  ThirtyActualISDA
+30E/360 ISMA                    ThirtyE360
+Actual/360                      Actual360
+Actual/364                      Actual364
+Actual/365                      Actual365Fixed
+Actual/Actual                   ActualActualICMA
+Actual/Actual ISDA              Actual365Actual
+Actual/Actual AFB               ActualActualAFB
+WorkingDays/252                 WorkingDays252
+Actual/365L                     Actual365L
+Actual/365P                     Actual365P
+ActualLeapDay/365               NonLeap365
+ActualLeapDay/360               NonLeap360
+Actual/36525                    Actual36525
+Actual/Actual CAD Convention    ActualActualCAD
 */
 
 //  --- Errors
+type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+    PeriodError(periods::Error),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::PeriodError(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<periods::Error> for Error {
+    fn from(value: periods::Error) -> Self {
+        Self::PeriodError(value)
+    }
+}
 
 //  --- Enums
+#[derive(Clone, Copy, Debug, Default)]
 pub enum DayCountConventions {
+    /// 30/360 (Bond basis), also 30/360 ISDA.
     Thirty360Bond,
+    /// 30/360 US, with the end of February adjustments.
+    Thirty360US,
+    /// 30/360 German.
+    Thirty360German,
+    /// 30E/360 (Eurobond basis), also 30E/360 ISMA.
     ThirtyE360,
-    ThirtyE360ISDA,
+    /// 30E/360 (ISDA). Holds the termination date, which is exempt
+    /// from the end of February adjustment.
+    ThirtyE360ISDA(Option<NaiveDate>),
     ThirtyEPlus360,
+    /// 30/365 ISDA.
+    Thirty365ISDA,
+    /// 30/365 German.
+    Thirty365German,
+    /// 30/365 Brazil.
+    Thirty365Brazil,
+    /// 30/Actual German.
+    ThirtyActualGerman,
+    /// 30/Actual.
+    ThirtyActual,
+    /// 30/Actual ISDA.
+    ThirtyActualISDA,
     Actual360,
+    Actual364,
+    #[default]
     Actual365Fixed,
+    /// Actual/365L. Holds the length of a regular coupon period, as annual
+    /// coupons follow their own leap year rule.
+    Actual365L(IntervalPeriod),
+    /// Actual/365P.
+    Actual365P,
+    /// Actual/365.25.
+    Actual36525,
+    /// Actual/Actual (ISDA).
     Actual365Actual,
+    /// Actual/Actual (ICMA). Holds the length of a regular coupon period.
+    ActualActualICMA(IntervalPeriod),
+    /// Actual/Actual AFB (French).
+    ActualActualAFB,
+    /// Actual/Actual Canadian bond convention. Holds the length of a regular coupon period.
+    ActualActualCAD(IntervalPeriod),
+    /// NL/365, also ActualLeapDay/365.
     NonLeap365,
-}
-
-impl Default for DayCountConventions {
-    fn default() -> Self {
-        Self::Actual365Fixed
-    }
+    /// NL/360, also ActualLeapDay/360.
+    NonLeap360,
+    /// Business days between the dates, over 252.
+    WorkingDays252,
 }
 
 //  --- Structs
 struct Thirty360Bond;
+struct Thirty360US;
 struct ThirtyE360;
-//struct ThirtyE360ISDA;
-//struct ThirtyEPlus360;
+struct ThirtyE360ISDA(Option<NaiveDate>);
+struct ThirtyEPlus360;
+struct ThirtyFixed<A>(A, f64);
+struct ThirtyActual<A>(A);
 struct Actual360;
+struct Actual364;
 struct Actual365Fixed;
-//struct Actual365Actual;
-//struct NonLeap365;
+struct Actual365L(IntervalPeriod);
+struct Actual365P;
+struct Actual36525;
+struct Actual365Actual;
+struct ActualActualICMA(IntervalPeriod);
+struct ActualActualAFB;
+struct ActualActualCAD(IntervalPeriod);
+struct NonLeap365;
+struct NonLeap360;
+struct WorkingDays252;
+
+/// The regular coupon periods of a schedule, rolled by 'tenor' from
+/// the regular coupon date 'anchor'.
+///
+/// For a front stub the anchor is the first regular coupon date,
+/// for a back stub it is the last regular coupon date.
+#[derive(Clone, Copy, Debug)]
+pub struct ReferencePeriod {
+    anchor: NaiveDate,
+    tenor: IntervalPeriod,
+    frequency: f64,
+    end_of_month: bool,
+}
 
 //  --- Traits
 pub trait DayCount<A, B, C> {
@@ -70,31 +155,204 @@ pub trait DayCount<A, B, C> {
     fn year_fraction(&self, start: &A, end: &B) -> C;
 }
 
+/// Day count for conventions that depend on a holiday calendar. Conventions
+/// that do not, ignore 'public_holidays' and return the same values as [`DayCount`].
+pub trait DayCountWithHolidays<A, B, C, D> {
+    fn day_count_with_holidays(&self, start: &A, end: &B, public_holidays: &D) -> C;
+
+    fn year_fraction_with_holidays(&self, start: &A, end: &B, public_holidays: &D) -> C;
+}
+
+/// Day count for conventions whose year fraction depends on the coupon schedule.
+/// Conventions that do not, ignore 'reference' and return the same values as [`DayCount`].
+pub trait DayCountWithReference<A, B, C> {
+    fn day_count_with_reference(&self, start: &A, end: &A, reference: &B) -> C;
+
+    fn year_fraction_with_reference(&self, start: &A, end: &A, reference: &B) -> C;
+}
+
+//  --- Implementations
+
+impl DayCountConventions {
+    /// Year fraction between the dates, rejecting a zero coupon tenor. The
+    /// infallible `year_fraction` returns NaN for such a tenor.
+    pub fn try_year_fraction(&self, start: &NaiveDate, end: &NaiveDate) -> Result<f64> {
+        match self {
+            Self::Actual365L(x) => Actual365L(*x).try_year_fraction(start, end),
+            Self::ActualActualICMA(x) => ActualActualICMA(*x).try_year_fraction(start, end),
+            Self::ActualActualCAD(x) => ActualActualCAD(*x).try_year_fraction(start, end),
+            _ => Ok(self.year_fraction(start, end)),
+        }
+    }
+}
+
+impl Actual365L {
+    fn try_year_fraction(&self, start: &NaiveDate, end: &NaiveDate) -> Result<f64> {
+        let denominator = if self.0.periods_per_year()? == 1.0 {
+            let (lower, upper) = (start.min(end), start.max(end));
+            if leap_days_between(lower, upper) > 0.0 {
+                366.0
+            } else {
+                365.0
+            }
+        } else {
+            days_in_year(end.year())
+        };
+        Ok(self.day_count(start, end) / denominator)
+    }
+}
+
+impl ActualActualICMA {
+    fn try_year_fraction(&self, start: &NaiveDate, end: &NaiveDate) -> Result<f64> {
+        if start > end {
+            return Ok(-self.try_year_fraction(end, start)?);
+        }
+
+        let frequency = self.0.periods_per_year()?;
+        let (full_periods, period_start, period_end) = notional_periods(start, end, &self.0)?;
+
+        Ok(full_periods as f64 / frequency
+            + self.day_count(&period_start, end)
+                / (frequency * self.day_count(&period_start, &period_end)))
+    }
+}
+
+impl ActualActualCAD {
+    fn try_year_fraction(&self, start: &NaiveDate, end: &NaiveDate) -> Result<f64> {
+        if start > end {
+            return Ok(-self.try_year_fraction(end, start)?);
+        }
+
+        let frequency = self.0.periods_per_year()?;
+        let (full_periods, period_start, period_end) = notional_periods(start, end, &self.0)?;
+
+        let days = self.day_count(&period_start, end);
+        let remainder = if days <= 365.0 / frequency {
+            days / 365.0
+        } else {
+            1.0 / frequency - self.day_count(end, &period_end) / 365.0
+        };
+
+        Ok(full_periods as f64 / frequency + remainder)
+    }
+}
+
+impl ReferencePeriod {
+    /// Reference periods rolled by 'tenor' from 'anchor'. A zero tenor is rejected.
+    pub fn new(anchor: NaiveDate, tenor: IntervalPeriod) -> Result<Self> {
+        Ok(Self {
+            anchor,
+            tenor,
+            frequency: tenor.periods_per_year()?,
+            end_of_month: false,
+        })
+    }
+
+    /// Roll notional coupon dates to the end of the month.
+    pub fn end_of_month(self, end_of_month: bool) -> Self {
+        Self {
+            end_of_month,
+            ..self
+        }
+    }
+
+    /// Notional coupon date 'n' periods from the anchor.
+    fn coupon_date(&self, n: i32) -> NaiveDate {
+        let date = if n >= 0 {
+            self.anchor + self.tenor.times(n as u32)
+        } else {
+            self.anchor - self.tenor.times(n.unsigned_abs())
+        };
+
+        if self.end_of_month {
+            date.eom().unwrap_or(date)
+        } else {
+            date
+        }
+    }
+
+    /// Notional coupon periods covering ['start', 'end'].
+    fn periods(&self, start: &NaiveDate, end: &NaiveDate) -> Vec<(NaiveDate, NaiveDate)> {
+        let mut n = 0;
+        while self.coupon_date(n) > *start {
+            n -= 1;
+        }
+        while self.coupon_date(n + 1) <= *start {
+            n += 1;
+        }
+
+        let mut periods = Vec::new();
+        while self.coupon_date(n) < *end {
+            periods.push((self.coupon_date(n), self.coupon_date(n + 1)));
+            n += 1;
+        }
+        periods
+    }
+}
+
 //  --- Trait implementations: Concrete
 impl DayCount<NaiveDate, NaiveDate, f64> for DayCountConventions {
     fn day_count(&self, start: &NaiveDate, end: &NaiveDate) -> f64 {
         match self {
             Self::Thirty360Bond => Thirty360Bond.day_count(start, end),
+            Self::Thirty360US => Thirty360US.day_count(start, end),
+            Self::Thirty360German => ThirtyE360ISDA(None).day_count(start, end),
             Self::ThirtyE360 => ThirtyE360.day_count(start, end),
-            Self::ThirtyE360ISDA => unimplemented!(),
-            Self::ThirtyEPlus360 => unimplemented!(),
+            Self::ThirtyE360ISDA(x) => ThirtyE360ISDA(*x).day_count(start, end),
+            Self::ThirtyEPlus360 => ThirtyEPlus360.day_count(start, end),
+            Self::Thirty365ISDA => ThirtyFixed(Thirty360Bond, 365.0).day_count(start, end),
+            Self::Thirty365German => ThirtyFixed(ThirtyE360ISDA(None), 365.0).day_count(start, end),
+            Self::Thirty365Brazil => ThirtyFixed(ThirtyE360, 365.0).day_count(start, end),
+            Self::ThirtyActualGerman => ThirtyActual(ThirtyE360ISDA(None)).day_count(start, end),
+            Self::ThirtyActual => ThirtyActual(ThirtyE360).day_count(start, end),
+            Self::ThirtyActualISDA => ThirtyActual(Thirty360Bond).day_count(start, end),
             Self::Actual360 => Actual360.day_count(start, end),
+            Self::Actual364 => Actual364.day_count(start, end),
             Self::Actual365Fixed => Actual365Fixed.day_count(start, end),
-            Self::Actual365Actual => unimplemented!(),
-            Self::NonLeap365 => unimplemented!(),
+            Self::Actual365L(x) => Actual365L(*x).day_count(start, end),
+            Self::Actual365P => Actual365P.day_count(start, end),
+            Self::Actual36525 => Actual36525.day_count(start, end),
+            Self::Actual365Actual => Actual365Actual.day_count(start, end),
+            Self::ActualActualICMA(x) => ActualActualICMA(*x).day_count(start, end),
+            Self::ActualActualAFB => ActualActualAFB.day_count(start, end),
+            Self::ActualActualCAD(x) => ActualActualCAD(*x).day_count(start, end),
+            Self::NonLeap365 => NonLeap365.day_count(start, end),
+            Self::NonLeap360 => NonLeap360.day_count(start, end),
+            Self::WorkingDays252 => WorkingDays252.day_count(start, end),
         }
     }
 
     fn year_fraction(&self, start: &NaiveDate, end: &NaiveDate) -> f64 {
         match self {
             Self::Thirty360Bond => Thirty360Bond.year_fraction(start, end),
+            Self::Thirty360US => Thirty360US.year_fraction(start, end),
+            Self::Thirty360German => ThirtyE360ISDA(None).year_fraction(start, end),
             Self::ThirtyE360 => ThirtyE360.year_fraction(start, end),
-            Self::ThirtyE360ISDA => unimplemented!(),
-            Self::ThirtyEPlus360 => unimplemented!(),
+            Self::ThirtyE360ISDA(x) => ThirtyE360ISDA(*x).year_fraction(start, end),
+            Self::ThirtyEPlus360 => ThirtyEPlus360.year_fraction(start, end),
+            Self::Thirty365ISDA => ThirtyFixed(Thirty360Bond, 365.0).year_fraction(start, end),
+            Self::Thirty365German => {
+                ThirtyFixed(ThirtyE360ISDA(None), 365.0).year_fraction(start, end)
+            }
+            Self::Thirty365Brazil => ThirtyFixed(ThirtyE360, 365.0).year_fraction(start, end),
+            Self::ThirtyActualGerman => {
+                ThirtyActual(ThirtyE360ISDA(None)).year_fraction(start, end)
+            }
+            Self::ThirtyActual => ThirtyActual(ThirtyE360).year_fraction(start, end),
+            Self::ThirtyActualISDA => ThirtyActual(Thirty360Bond).year_fraction(start, end),
             Self::Actual360 => Actual360.year_fraction(start, end),
+            Self::Actual364 => Actual364.year_fraction(start, end),
             Self::Actual365Fixed => Actual365Fixed.year_fraction(start, end),
-            Self::Actual365Actual => unimplemented!(),
-            Self::NonLeap365 => unimplemented!(),
+            Self::Actual365L(x) => Actual365L(*x).year_fraction(start, end),
+            Self::Actual365P => Actual365P.year_fraction(start, end),
+            Self::Actual36525 => Actual36525.year_fraction(start, end),
+            Self::Actual365Actual => Actual365Actual.year_fraction(start, end),
+            Self::ActualActualICMA(x) => ActualActualICMA(*x).year_fraction(start, end),
+            Self::ActualActualAFB => ActualActualAFB.year_fraction(start, end),
+            Self::ActualActualCAD(x) => ActualActualCAD(*x).year_fraction(start, end),
+            Self::NonLeap365 => NonLeap365.year_fraction(start, end),
+            Self::NonLeap360 => NonLeap360.year_fraction(start, end),
+            Self::WorkingDays252 => WorkingDays252.year_fraction(start, end),
         }
     }
 }
@@ -120,6 +378,36 @@ impl DayCount<NaiveDate, NaiveDate, f64> for Thirty360Bond {
     }
 }
 
+impl DayCount<NaiveDate, NaiveDate, f64> for Thirty360US {
+    fn day_count(&self, start: &NaiveDate, end: &NaiveDate) -> f64 {
+        let (y1, m1, mut d1) = (
+            start.year() as f64,
+            start.month() as f64,
+            start.day() as f64,
+        );
+        let (y2, m2, mut d2) = (end.year() as f64, end.month() as f64, end.day() as f64);
+
+        if is_last_day_of_february(start) {
+            if is_last_day_of_february(end) {
+                d2 = 30.0;
+            }
+            d1 = 30.0;
+        }
+
+        if d2 == 31.0 && d1 >= 30.0 {
+            d2 = 30.0;
+        }
+
+        d1 = d1.min(30.0);
+
+        (y2 - y1) * 360.0 + (m2 - m1) * 30.0 + (d2 - d1)
+    }
+
+    fn year_fraction(&self, start: &NaiveDate, end: &NaiveDate) -> f64 {
+        self.day_count(start, end) / 360.0
+    }
+}
+
 impl DayCount<NaiveDate, NaiveDate, f64> for ThirtyE360 {
     fn day_count(&self, start: &NaiveDate, end: &NaiveDate) -> f64 {
         let (y1, m1, d1) = (
@@ -140,6 +428,84 @@ impl DayCount<NaiveDate, NaiveDate, f64> for ThirtyE360 {
     }
 }
 
+impl DayCount<NaiveDate, NaiveDate, f64> for ThirtyE360ISDA {
+    fn day_count(&self, start: &NaiveDate, end: &NaiveDate) -> f64 {
+        let (y1, m1) = (start.year() as f64, start.month() as f64);
+        let (y2, m2) = (end.year() as f64, end.month() as f64);
+
+        let d1 = if is_last_day_of_february(start) {
+            30.0
+        } else {
+            start.day().min(30) as f64
+        };
+
+        let is_termination = self.0.is_some_and(|x| x == *end);
+        let d2 = if is_last_day_of_february(end) && !is_termination {
+            30.0
+        } else {
+            end.day().min(30) as f64
+        };
+
+        (y2 - y1) * 360.0 + (m2 - m1) * 30.0 + (d2 - d1)
+    }
+
+    fn year_fraction(&self, start: &NaiveDate, end: &NaiveDate) -> f64 {
+        self.day_count(start, end) / 360.0
+    }
+}
+
+impl DayCount<NaiveDate, NaiveDate, f64> for ThirtyEPlus360 {
+    fn day_count(&self, start: &NaiveDate, end: &NaiveDate) -> f64 {
+        let (y1, m1, d1) = (
+            start.year() as f64,
+            start.month() as f64,
+            start.day().min(30) as f64,
+        );
+        let (y2, mut m2, mut d2) = (end.year() as f64, end.month() as f64, end.day() as f64);
+
+        // A 31st rolls over into the first day of the following month.
+        if d2 == 31.0 {
+            d2 = 1.0;
+            m2 += 1.0;
+        }
+
+        (y2 - y1) * 360.0 + (m2 - m1) * 30.0 + (d2 - d1)
+    }
+
+    fn year_fraction(&self, start: &NaiveDate, end: &NaiveDate) -> f64 {
+        self.day_count(start, end) / 360.0
+    }
+}
+
+/// 30 day month numerator over a fixed number of days in the year.
+impl<A> DayCount<NaiveDate, NaiveDate, f64> for ThirtyFixed<A>
+where
+    A: DayCount<NaiveDate, NaiveDate, f64>,
+{
+    fn day_count(&self, start: &NaiveDate, end: &NaiveDate) -> f64 {
+        self.0.day_count(start, end)
+    }
+
+    fn year_fraction(&self, start: &NaiveDate, end: &NaiveDate) -> f64 {
+        self.day_count(start, end) / self.1
+    }
+}
+
+/// 30 day month numerator over the actual number of days in
+/// the year following 'start'.
+impl<A> DayCount<NaiveDate, NaiveDate, f64> for ThirtyActual<A>
+where
+    A: DayCount<NaiveDate, NaiveDate, f64>,
+{
+    fn day_count(&self, start: &NaiveDate, end: &NaiveDate) -> f64 {
+        self.0.day_count(start, end)
+    }
+
+    fn year_fraction(&self, start: &NaiveDate, end: &NaiveDate) -> f64 {
+        self.day_count(start, end) / days_in_year_following(start)
+    }
+}
+
 impl DayCount<NaiveDate, NaiveDate, f64> for Actual360 {
     fn day_count(&self, start: &NaiveDate, end: &NaiveDate) -> f64 {
         (*end - *start).num_days() as f64
@@ -150,6 +516,16 @@ impl DayCount<NaiveDate, NaiveDate, f64> for Actual360 {
     }
 }
 
+impl DayCount<NaiveDate, NaiveDate, f64> for Actual364 {
+    fn day_count(&self, start: &NaiveDate, end: &NaiveDate) -> f64 {
+        (*end - *start).num_days() as f64
+    }
+
+    fn year_fraction(&self, start: &NaiveDate, end: &NaiveDate) -> f64 {
+        self.day_count(start, end) / 364.0
+    }
+}
+
 impl DayCount<NaiveDate, NaiveDate, f64> for Actual365Fixed {
     fn day_count(&self, start: &NaiveDate, end: &NaiveDate) -> f64 {
         (*end - *start).num_days() as f64
@@ -160,6 +536,287 @@ impl DayCount<NaiveDate, NaiveDate, f64> for Actual365Fixed {
     }
 }
 
+/// The denominator is 366 when 'end' falls in a leap year. With annual
+/// coupons it is 366 when a 29 February falls in the period instead.
+impl DayCount<NaiveDate, NaiveDate, f64> for Actual365L {
+    fn day_count(&self, start: &NaiveDate, end: &NaiveDate) -> f64 {
+        (*end - *start).num_days() as f64
+    }
+
+    fn year_fraction(&self, start: &NaiveDate, end: &NaiveDate) -> f64 {
+        self.try_year_fraction(start, end).unwrap_or(f64::NAN)
+    }
+}
+
+/// The denominator is 366 when a 29 February falls in the period.
+impl DayCount<NaiveDate, NaiveDate, f64> for Actual365P {
+    fn day_count(&self, start: &NaiveDate, end: &NaiveDate) -> f64 {
+        (*end - *start).num_days() as f64
+    }
+
+    fn year_fraction(&self, start: &NaiveDate, end: &NaiveDate) -> f64 {
+        let (lower, upper) = (start.min(end), start.max(end));
+        let denominator = if leap_days_between(lower, upper) > 0.0 {
+            366.0
+        } else {
+            365.0
+        };
+        self.day_count(start, end) / denominator
+    }
+}
+
+impl DayCount<NaiveDate, NaiveDate, f64> for Actual36525 {
+    fn day_count(&self, start: &NaiveDate, end: &NaiveDate) -> f64 {
+        (*end - *start).num_days() as f64
+    }
+
+    fn year_fraction(&self, start: &NaiveDate, end: &NaiveDate) -> f64 {
+        self.day_count(start, end) / 365.25
+    }
+}
+
+impl DayCount<NaiveDate, NaiveDate, f64> for Actual365Actual {
+    fn day_count(&self, start: &NaiveDate, end: &NaiveDate) -> f64 {
+        (*end - *start).num_days() as f64
+    }
+
+    fn year_fraction(&self, start: &NaiveDate, end: &NaiveDate) -> f64 {
+        if start > end {
+            return -self.year_fraction(end, start);
+        }
+
+        let (y1, y2) = (start.year(), end.year());
+        if y1 == y2 {
+            return self.day_count(start, end) / days_in_year(y1);
+        }
+
+        // Days falling in the first and last calendar year are apportioned
+        // to the length of their respective years, full years in between count as one.
+        let first_year_end = first_day_of_year(y1 + 1);
+        let last_year_start = first_day_of_year(y2);
+
+        self.day_count(start, &first_year_end) / days_in_year(y1)
+            + (y2 - y1 - 1) as f64
+            + self.day_count(&last_year_start, end) / days_in_year(y2)
+    }
+}
+
+/// Regular coupon periods are rolled forward from 'start'. Each full period
+/// accrues 1 / frequency, the remainder is apportioned to the length of
+/// the notional period it falls in.
+impl DayCount<NaiveDate, NaiveDate, f64> for ActualActualICMA {
+    fn day_count(&self, start: &NaiveDate, end: &NaiveDate) -> f64 {
+        (*end - *start).num_days() as f64
+    }
+
+    fn year_fraction(&self, start: &NaiveDate, end: &NaiveDate) -> f64 {
+        self.try_year_fraction(start, end).unwrap_or(f64::NAN)
+    }
+}
+
+/// Whole years are counted back from 'end'. The denominator of the
+/// remaining stub is 366 when a 29 February falls in it.
+impl DayCount<NaiveDate, NaiveDate, f64> for ActualActualAFB {
+    fn day_count(&self, start: &NaiveDate, end: &NaiveDate) -> f64 {
+        (*end - *start).num_days() as f64
+    }
+
+    fn year_fraction(&self, start: &NaiveDate, end: &NaiveDate) -> f64 {
+        if start > end {
+            return -self.year_fraction(end, start);
+        }
+
+        let mut years = 0;
+        let mut stub_end = *end;
+        while let Some(previous) = end.checked_sub_months(chrono::Months::new(12 * (years + 1))) {
+            if previous < *start {
+                break;
+            }
+            years += 1;
+            stub_end = previous;
+        }
+
+        let denominator = if leap_days_between(start, &stub_end) > 0.0 {
+            366.0
+        } else {
+            365.0
+        };
+
+        years as f64 + self.day_count(start, &stub_end) / denominator
+    }
+}
+
+/// Within a coupon period, accruals shorter than half the nominal period length
+/// use Actual/365. Longer accruals are measured back from the full coupon.
+impl DayCount<NaiveDate, NaiveDate, f64> for ActualActualCAD {
+    fn day_count(&self, start: &NaiveDate, end: &NaiveDate) -> f64 {
+        (*end - *start).num_days() as f64
+    }
+
+    fn year_fraction(&self, start: &NaiveDate, end: &NaiveDate) -> f64 {
+        self.try_year_fraction(start, end).unwrap_or(f64::NAN)
+    }
+}
+
+impl DayCount<NaiveDate, NaiveDate, f64> for NonLeap365 {
+    fn day_count(&self, start: &NaiveDate, end: &NaiveDate) -> f64 {
+        if start > end {
+            return -self.day_count(end, start);
+        }
+        (*end - *start).num_days() as f64 - leap_days_between(start, end)
+    }
+
+    fn year_fraction(&self, start: &NaiveDate, end: &NaiveDate) -> f64 {
+        self.day_count(start, end) / 365.0
+    }
+}
+
+impl DayCount<NaiveDate, NaiveDate, f64> for NonLeap360 {
+    fn day_count(&self, start: &NaiveDate, end: &NaiveDate) -> f64 {
+        NonLeap365.day_count(start, end)
+    }
+
+    fn year_fraction(&self, start: &NaiveDate, end: &NaiveDate) -> f64 {
+        self.day_count(start, end) / 360.0
+    }
+}
+
+/// Counts weekdays in ['start', 'end'), without regard to public holidays.
+impl DayCount<NaiveDate, NaiveDate, f64> for WorkingDays252 {
+    fn day_count(&self, start: &NaiveDate, end: &NaiveDate) -> f64 {
+        self.day_count_with_holidays(start, end, &Vec::new())
+    }
+
+    fn year_fraction(&self, start: &NaiveDate, end: &NaiveDate) -> f64 {
+        self.day_count(start, end) / 252.0
+    }
+}
+
+impl<D> DayCountWithHolidays<NaiveDate, NaiveDate, f64, D> for DayCountConventions
+where
+    D: BusinessCalendar<NaiveDate>,
+{
+    fn day_count_with_holidays(
+        &self,
+        start: &NaiveDate,
+        end: &NaiveDate,
+        public_holidays: &D,
+    ) -> f64 {
+        match self {
+            Self::WorkingDays252 => {
+                WorkingDays252.day_count_with_holidays(start, end, public_holidays)
+            }
+            _ => self.day_count(start, end),
+        }
+    }
+
+    fn year_fraction_with_holidays(
+        &self,
+        start: &NaiveDate,
+        end: &NaiveDate,
+        public_holidays: &D,
+    ) -> f64 {
+        match self {
+            Self::WorkingDays252 => {
+                WorkingDays252.year_fraction_with_holidays(start, end, public_holidays)
+            }
+            _ => self.year_fraction(start, end),
+        }
+    }
+}
+
+impl DayCountWithReference<NaiveDate, ReferencePeriod, f64> for DayCountConventions {
+    fn day_count_with_reference(
+        &self,
+        start: &NaiveDate,
+        end: &NaiveDate,
+        _reference: &ReferencePeriod,
+    ) -> f64 {
+        self.day_count(start, end)
+    }
+
+    fn year_fraction_with_reference(
+        &self,
+        start: &NaiveDate,
+        end: &NaiveDate,
+        reference: &ReferencePeriod,
+    ) -> f64 {
+        match self {
+            Self::ActualActualICMA(_) => ActualActualICMA(reference.tenor)
+                .year_fraction_with_reference(start, end, reference),
+            _ => self.year_fraction(start, end),
+        }
+    }
+}
+
+/// Each notional coupon period overlapping ['start', 'end'] contributes its
+/// accrued days over frequency times the days in that period. Short and long
+/// stubs therefore follow from the position of the anchor.
+impl DayCountWithReference<NaiveDate, ReferencePeriod, f64> for ActualActualICMA {
+    fn day_count_with_reference(
+        &self,
+        start: &NaiveDate,
+        end: &NaiveDate,
+        _reference: &ReferencePeriod,
+    ) -> f64 {
+        self.day_count(start, end)
+    }
+
+    fn year_fraction_with_reference(
+        &self,
+        start: &NaiveDate,
+        end: &NaiveDate,
+        reference: &ReferencePeriod,
+    ) -> f64 {
+        if start > end {
+            return -self.year_fraction_with_reference(end, start, reference);
+        }
+
+        let frequency = reference.frequency;
+        reference
+            .periods(start, end)
+            .iter()
+            .map(|(period_start, period_end)| {
+                let accrual_start = start.max(period_start);
+                let accrual_end = end.min(period_end);
+                self.day_count(accrual_start, accrual_end)
+                    / (frequency * self.day_count(period_start, period_end))
+            })
+            .sum()
+    }
+}
+
+/// Counts business days in ['start', 'end').
+impl<D> DayCountWithHolidays<NaiveDate, NaiveDate, f64, D> for WorkingDays252
+where
+    D: BusinessCalendar<NaiveDate>,
+{
+    fn day_count_with_holidays(
+        &self,
+        start: &NaiveDate,
+        end: &NaiveDate,
+        public_holidays: &D,
+    ) -> f64 {
+        if start > end {
+            return -self.day_count_with_holidays(end, start, public_holidays);
+        }
+        start
+            .iter_days()
+            .take_while(|d| d < end)
+            .filter(|d| public_holidays.is_business_day(d))
+            .count() as f64
+    }
+
+    fn year_fraction_with_holidays(
+        &self,
+        start: &NaiveDate,
+        end: &NaiveDate,
+        public_holidays: &D,
+    ) -> f64 {
+        self.day_count_with_holidays(start, end, public_holidays) / 252.0
+    }
+}
+
 //  --- Trait implementations: Blanket
 impl<A, B, C> DayCount<Vec<A>, Vec<A>, Vec<B>> for C
 where
@@ -208,4 +865,628 @@ where
     }
 }
 
+impl<A, B, C, D> DayCountWithHolidays<Vec<A>, Vec<A>, Vec<B>, D> for C
+where
+    C: DayCountWithHolidays<A, A, B, D>,
+{
+    fn day_count_with_holidays(&self, start: &Vec<A>, end: &Vec<A>, public_holidays: &D) -> Vec<B> {
+        start
+            .iter()
+            .zip(end.iter())
+            .map(|(a, b)| self.day_count_with_holidays(a, b, public_holidays))
+            .collect()
+    }
+
+    fn year_fraction_with_holidays(
+        &self,
+        start: &Vec<A>,
+        end: &Vec<A>,
+        public_holidays: &D,
+    ) -> Vec<B> {
+        start
+            .iter()
+            .zip(end.iter())
+            .map(|(a, b)| self.year_fraction_with_holidays(a, b, public_holidays))
+            .collect()
+    }
+}
+
+impl<A, B, C, D> DayCountWithHolidays<Vec<A>, A, Vec<B>, D> for C
+where
+    C: DayCountWithHolidays<A, A, B, D>,
+{
+    fn day_count_with_holidays(&self, start: &Vec<A>, end: &A, public_holidays: &D) -> Vec<B> {
+        start
+            .iter()
+            .map(|a| self.day_count_with_holidays(a, end, public_holidays))
+            .collect()
+    }
+
+    fn year_fraction_with_holidays(&self, start: &Vec<A>, end: &A, public_holidays: &D) -> Vec<B> {
+        start
+            .iter()
+            .map(|a| self.year_fraction_with_holidays(a, end, public_holidays))
+            .collect()
+    }
+}
+
+impl<A, B, C, D> DayCountWithHolidays<A, Vec<A>, Vec<B>, D> for C
+where
+    C: DayCountWithHolidays<A, A, B, D>,
+{
+    fn day_count_with_holidays(&self, start: &A, end: &Vec<A>, public_holidays: &D) -> Vec<B> {
+        end.iter()
+            .map(|a| self.day_count_with_holidays(start, a, public_holidays))
+            .collect()
+    }
+
+    fn year_fraction_with_holidays(&self, start: &A, end: &Vec<A>, public_holidays: &D) -> Vec<B> {
+        end.iter()
+            .map(|a| self.year_fraction_with_holidays(start, a, public_holidays))
+            .collect()
+    }
+}
+
+//  --- Standalone functions
+
+fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_year(year: i32) -> f64 {
+    if is_leap_year(year) {
+        366.0
+    } else {
+        365.0
+    }
+}
+
+fn first_day_of_year(year: i32) -> NaiveDate {
+    NaiveDate::from_yo_opt(year, 1).unwrap_or(NaiveDate::MAX)
+}
+
+/// Number of days in the twelve months following 'date'.
+fn days_in_year_following(date: &NaiveDate) -> f64 {
+    date.checked_add_months(chrono::Months::new(12))
+        .map(|d| (d - *date).num_days() as f64)
+        .unwrap_or(365.0)
+}
+
+/// Rolls regular periods of length 'tenor' forward from 'start', returning the
+/// number of full periods ending on or before 'end', and the notional period containing 'end'.
+fn notional_periods(
+    start: &NaiveDate,
+    end: &NaiveDate,
+    tenor: &IntervalPeriod,
+) -> Result<(u32, NaiveDate, NaiveDate)> {
+    if *start + *tenor <= *start {
+        return Err(periods::Error::InvalidTenor(*tenor).into());
+    }
+
+    let mut full_periods = 0;
+    let mut period_start = *start;
+    let mut period_end = *start + *tenor;
+    while period_end <= *end {
+        full_periods += 1;
+        period_start = period_end;
+        period_end = *start + tenor.times(full_periods + 1);
+    }
+    Ok((full_periods, period_start, period_end))
+}
+
+fn is_last_day_of_february(date: &NaiveDate) -> bool {
+    date.month() == 2 && date.succ_opt().is_some_and(|d| d.month() == 3)
+}
+
+/// Number of 29 February's falling in the period ('start', 'end'].
+fn leap_days_between(start: &NaiveDate, end: &NaiveDate) -> f64 {
+    (start.year()..=end.year())
+        .filter(|y| is_leap_year(*y))
+        .filter_map(|y| NaiveDate::from_ymd_opt(y, 2, 29))
+        .filter(|d| d > start && d <= end)
+        .count() as f64
+}
+
 //  --- Unit tests
+#[cfg(test)]
+mod test_day_count {
+
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::test_utils::date;
+
+    mod test_thirty_e_360_isda {
+
+        use super::*;
+
+        // Examples from the ISDA 30/360 day count comparison.
+        #[test]
+        fn test_day_count_not_termination() {
+            let convention = DayCountConventions::ThirtyE360ISDA(None);
+            let population = vec![
+                (date(2007, 1, 15), date(2007, 1, 30), 15.0),
+                (date(2007, 1, 15), date(2007, 2, 15), 30.0),
+                (date(2007, 1, 15), date(2007, 7, 15), 180.0),
+                (date(2007, 9, 30), date(2008, 3, 31), 180.0),
+                (date(2007, 9, 30), date(2007, 10, 31), 30.0),
+                (date(2007, 9, 30), date(2008, 9, 30), 360.0),
+                (date(2007, 1, 15), date(2007, 1, 31), 15.0),
+                (date(2007, 1, 31), date(2007, 2, 28), 30.0),
+                (date(2007, 2, 28), date(2007, 3, 31), 30.0),
+                (date(2006, 8, 31), date(2007, 2, 28), 180.0),
+                (date(2007, 2, 28), date(2007, 8, 31), 180.0),
+                (date(2007, 2, 14), date(2007, 2, 28), 16.0),
+                (date(2007, 2, 26), date(2008, 2, 29), 364.0),
+                (date(2008, 2, 29), date(2009, 2, 28), 360.0),
+                (date(2008, 2, 29), date(2008, 3, 30), 30.0),
+                (date(2008, 2, 29), date(2008, 3, 31), 30.0),
+                (date(2007, 2, 28), date(2007, 3, 5), 5.0),
+                (date(2007, 10, 31), date(2007, 11, 28), 28.0),
+                (date(2007, 8, 31), date(2008, 2, 29), 180.0),
+                (date(2008, 2, 29), date(2008, 8, 31), 180.0),
+                (date(2008, 8, 31), date(2009, 2, 28), 180.0),
+                (date(2009, 2, 28), date(2009, 8, 31), 180.0),
+            ];
+
+            for (start, end, expected) in population {
+                assert_approx_eq!(convention.day_count(&start, &end), expected);
+                assert_approx_eq!(convention.year_fraction(&start, &end), expected / 360.0);
+            }
+        }
+
+        #[test]
+        fn test_day_count_termination() {
+            let termination = date(2009, 2, 28);
+            let convention = DayCountConventions::ThirtyE360ISDA(Some(termination));
+            assert_approx_eq!(
+                convention.day_count(&date(2008, 2, 29), &termination),
+                358.0
+            );
+            assert_approx_eq!(
+                convention.day_count(&date(2008, 8, 31), &termination),
+                178.0
+            );
+            // The start date is adjusted irrespective of the termination date.
+            assert_approx_eq!(
+                convention.day_count(&date(2009, 2, 28), &date(2009, 8, 31)),
+                180.0
+            );
+        }
+    }
+
+    mod test_thirty_e_plus_360 {
+
+        use super::*;
+
+        #[test]
+        fn test_day_count() {
+            let convention = DayCountConventions::ThirtyEPlus360;
+            assert_approx_eq!(
+                convention.day_count(&date(2007, 1, 15), &date(2007, 1, 30)),
+                15.0
+            );
+            assert_approx_eq!(
+                convention.day_count(&date(2007, 1, 31), &date(2007, 3, 31)),
+                61.0
+            );
+            assert_approx_eq!(
+                convention.day_count(&date(2007, 2, 28), &date(2007, 3, 31)),
+                33.0
+            );
+            assert_approx_eq!(
+                convention.day_count(&date(2007, 12, 31), &date(2008, 12, 31)),
+                361.0
+            );
+        }
+    }
+
+    mod test_actual_actual_isda {
+
+        use super::*;
+
+        // Examples from the ISDA paper 'EMU and market conventions'.
+        #[test]
+        fn test_year_fraction() {
+            let convention = DayCountConventions::Actual365Actual;
+            let population = vec![
+                (date(2003, 11, 1), date(2004, 5, 1), 0.497724380567),
+                (date(1999, 2, 1), date(1999, 7, 1), 0.410958904110),
+                (date(1999, 7, 1), date(2000, 7, 1), 1.001377348600),
+                (date(2002, 8, 15), date(2003, 7, 15), 0.915068493151),
+                (date(2003, 7, 15), date(2004, 1, 15), 0.504004790778),
+                (date(1999, 7, 30), date(2000, 1, 30), 0.503892506924),
+                (date(2000, 1, 30), date(2000, 6, 30), 0.415300546448),
+            ];
+
+            for (start, end, expected) in population {
+                assert_approx_eq!(convention.year_fraction(&start, &end), expected, 1e-9);
+                assert_approx_eq!(convention.year_fraction(&end, &start), -expected, 1e-9);
+            }
+        }
+
+        #[test]
+        fn test_year_fraction_multiple_years() {
+            let convention = DayCountConventions::Actual365Actual;
+            let yf = convention.year_fraction(&date(2003, 7, 1), &date(2008, 7, 1));
+            assert_approx_eq!(yf, 184.0 / 365.0 + 4.0 + 182.0 / 366.0, 1e-12);
+        }
+    }
+
+    mod test_non_leap_365 {
+
+        use super::*;
+
+        #[test]
+        fn test_day_count() {
+            let convention = DayCountConventions::NonLeap365;
+            assert_approx_eq!(
+                convention.day_count(&date(2008, 2, 1), &date(2009, 2, 1)),
+                365.0
+            );
+            assert_approx_eq!(
+                convention.day_count(&date(2008, 2, 29), &date(2008, 3, 1)),
+                1.0
+            );
+            assert_approx_eq!(
+                convention.day_count(&date(2008, 2, 28), &date(2008, 3, 1)),
+                1.0
+            );
+            assert_approx_eq!(
+                convention.day_count(&date(2007, 1, 1), &date(2013, 1, 1)),
+                2190.0
+            );
+            assert_approx_eq!(
+                convention.year_fraction(&date(2008, 2, 1), &date(2009, 2, 1)),
+                1.0
+            );
+        }
+    }
+
+    mod test_thirty_360_variants {
+
+        use super::*;
+
+        #[test]
+        fn test_thirty_360_us() {
+            let convention = DayCountConventions::Thirty360US;
+            assert_approx_eq!(
+                convention.day_count(&date(2007, 2, 28), &date(2007, 3, 31)),
+                30.0
+            );
+            assert_approx_eq!(
+                convention.day_count(&date(2007, 2, 28), &date(2008, 2, 29)),
+                360.0
+            );
+            assert_approx_eq!(
+                convention.day_count(&date(2007, 1, 31), &date(2007, 2, 28)),
+                28.0
+            );
+            assert_approx_eq!(
+                convention.day_count(&date(2007, 1, 15), &date(2007, 3, 31)),
+                76.0
+            );
+        }
+
+        #[test]
+        fn test_thirty_360_german() {
+            let convention = DayCountConventions::Thirty360German;
+            assert_approx_eq!(
+                convention.day_count(&date(2007, 1, 31), &date(2007, 2, 28)),
+                30.0
+            );
+            assert_approx_eq!(
+                convention.day_count(&date(2008, 2, 29), &date(2009, 2, 28)),
+                360.0
+            );
+        }
+
+        #[test]
+        fn test_thirty_365() {
+            let (start, end) = (date(2007, 1, 31), date(2007, 3, 31));
+            let isda = DayCountConventions::Thirty365ISDA.year_fraction(&start, &end);
+            let german = DayCountConventions::Thirty365German.year_fraction(&start, &end);
+            let brazil = DayCountConventions::Thirty365Brazil.year_fraction(&start, &end);
+            assert_approx_eq!(isda, 60.0 / 365.0);
+            assert_approx_eq!(german, 60.0 / 365.0);
+            assert_approx_eq!(brazil, 60.0 / 365.0);
+
+            let (start, end) = (date(2007, 1, 31), date(2007, 2, 28));
+            let german = DayCountConventions::Thirty365German.year_fraction(&start, &end);
+            assert_approx_eq!(german, 30.0 / 365.0);
+        }
+
+        #[test]
+        fn test_thirty_actual() {
+            let population = vec![
+                DayCountConventions::ThirtyActual,
+                DayCountConventions::ThirtyActualGerman,
+                DayCountConventions::ThirtyActualISDA,
+            ];
+
+            for convention in population {
+                let yf = convention.year_fraction(&date(2023, 1, 15), &date(2023, 7, 15));
+                assert_approx_eq!(yf, 180.0 / 365.0);
+                let yf = convention.year_fraction(&date(2024, 1, 15), &date(2024, 7, 15));
+                assert_approx_eq!(yf, 180.0 / 366.0);
+            }
+        }
+    }
+
+    mod test_actual_variants {
+
+        use super::*;
+
+        #[test]
+        fn test_actual_364() {
+            let convention = DayCountConventions::Actual364;
+            assert_approx_eq!(
+                convention.year_fraction(&date(2023, 1, 1), &date(2023, 12, 31)),
+                1.0
+            );
+        }
+
+        #[test]
+        fn test_actual_365_l() {
+            let convention = DayCountConventions::Actual365L(IntervalPeriod::Months(6));
+            let yf = convention.year_fraction(&date(2024, 1, 1), &date(2024, 7, 1));
+            assert_approx_eq!(yf, 182.0 / 366.0);
+            let yf = convention.year_fraction(&date(2023, 1, 1), &date(2023, 7, 1));
+            assert_approx_eq!(yf, 181.0 / 365.0);
+            let yf = convention.year_fraction(&date(2023, 7, 1), &date(2024, 1, 1));
+            assert_approx_eq!(yf, 184.0 / 366.0);
+
+            // Annual coupons use 366 only when a 29 February falls in the period.
+            let convention = DayCountConventions::Actual365L(IntervalPeriod::Years(1));
+            let yf = convention.year_fraction(&date(2023, 7, 1), &date(2024, 1, 1));
+            assert_approx_eq!(yf, 184.0 / 365.0);
+            let yf = convention.year_fraction(&date(2023, 7, 1), &date(2024, 7, 1));
+            assert_approx_eq!(yf, 366.0 / 366.0);
+        }
+
+        #[test]
+        fn test_zero_tenor() {
+            for convention in [
+                DayCountConventions::Actual365L(IntervalPeriod::Years(0)),
+                DayCountConventions::ActualActualICMA(IntervalPeriod::Months(0)),
+                DayCountConventions::ActualActualCAD(IntervalPeriod::Days(0)),
+            ] {
+                assert!(matches!(
+                    convention.try_year_fraction(&date(2023, 1, 1), &date(2023, 7, 1)),
+                    Err(Error::PeriodError(periods::Error::InvalidTenor(_)))
+                ));
+                assert!(convention
+                    .year_fraction(&date(2023, 1, 1), &date(2023, 7, 1))
+                    .is_nan());
+            }
+        }
+
+        #[test]
+        fn test_actual_365_p() {
+            let convention = DayCountConventions::Actual365P;
+            let yf = convention.year_fraction(&date(2023, 12, 1), &date(2024, 3, 1));
+            assert_approx_eq!(yf, 91.0 / 366.0);
+            let yf = convention.year_fraction(&date(2024, 3, 1), &date(2024, 6, 1));
+            assert_approx_eq!(yf, 92.0 / 365.0);
+        }
+
+        #[test]
+        fn test_actual_36525() {
+            let convention = DayCountConventions::Actual36525;
+            let yf = convention.year_fraction(&date(2023, 1, 1), &date(2024, 1, 1));
+            assert_approx_eq!(yf, 365.0 / 365.25);
+        }
+
+        #[test]
+        fn test_non_leap_360() {
+            let convention = DayCountConventions::NonLeap360;
+            let yf = convention.year_fraction(&date(2008, 2, 1), &date(2009, 2, 1));
+            assert_approx_eq!(yf, 365.0 / 360.0);
+        }
+
+        #[test]
+        fn test_working_days_252() {
+            let convention = DayCountConventions::WorkingDays252;
+            assert_approx_eq!(
+                convention.day_count(&date(2024, 1, 1), &date(2024, 1, 8)),
+                5.0
+            );
+            assert_approx_eq!(
+                convention.day_count(&date(2024, 1, 6), &date(2024, 1, 8)),
+                0.0
+            );
+            let yf = convention.year_fraction(&date(2024, 1, 1), &date(2024, 1, 15));
+            assert_approx_eq!(yf, 10.0 / 252.0);
+        }
+    }
+
+    mod test_actual_actual_variants {
+
+        use super::*;
+
+        #[test]
+        fn test_actual_actual_icma() {
+            let convention = DayCountConventions::ActualActualICMA(IntervalPeriod::Months(6));
+            let yf = convention.year_fraction(&date(2003, 11, 1), &date(2004, 5, 1));
+            assert_approx_eq!(yf, 0.5);
+            let yf = convention.year_fraction(&date(2003, 11, 1), &date(2004, 2, 1));
+            assert_approx_eq!(yf, 92.0 / (2.0 * 182.0));
+            let yf = convention.year_fraction(&date(2003, 11, 1), &date(2004, 8, 1));
+            assert_approx_eq!(yf, 0.5 + 92.0 / (2.0 * 184.0));
+
+            let convention = DayCountConventions::ActualActualICMA(IntervalPeriod::Years(1));
+            let yf = convention.year_fraction(&date(1999, 7, 1), &date(2000, 7, 1));
+            assert_approx_eq!(yf, 1.0);
+        }
+
+        #[test]
+        fn test_actual_actual_afb() {
+            let convention = DayCountConventions::ActualActualAFB;
+            let yf = convention.year_fraction(&date(1994, 2, 10), &date(1997, 6, 30));
+            assert_approx_eq!(yf, 3.0 + 140.0 / 365.0);
+            let yf = convention.year_fraction(&date(2004, 2, 10), &date(2004, 6, 30));
+            assert_approx_eq!(yf, 141.0 / 366.0);
+            let yf = convention.year_fraction(&date(2004, 2, 29), &date(2008, 2, 29));
+            assert_approx_eq!(yf, 4.0);
+        }
+
+        #[test]
+        fn test_actual_actual_cad() {
+            let convention = DayCountConventions::ActualActualCAD(IntervalPeriod::Months(6));
+            let yf = convention.year_fraction(&date(2023, 1, 1), &date(2023, 4, 1));
+            assert_approx_eq!(yf, 90.0 / 365.0);
+            let yf = convention.year_fraction(&date(2023, 1, 1), &date(2023, 7, 1));
+            assert_approx_eq!(yf, 0.5);
+            let yf = convention.year_fraction(&date(2023, 7, 1), &date(2023, 12, 31));
+            assert_approx_eq!(yf, 0.5 - 1.0 / 365.0);
+        }
+    }
+
+    mod test_working_days_252 {
+
+        use super::*;
+        use crate::resources::holidays::load_holidays;
+
+        #[test]
+        fn test_day_count_with_holidays() {
+            let convention = DayCountConventions::WorkingDays252;
+            let public_holidays = vec![date(2024, 1, 1), date(2024, 1, 6)];
+            let (start, end) = (date(2024, 1, 1), date(2024, 1, 15));
+
+            assert_approx_eq!(
+                convention.day_count_with_holidays(&start, &end, &public_holidays),
+                9.0
+            );
+            let yf = convention.year_fraction_with_holidays(&start, &end, &public_holidays);
+            assert_approx_eq!(yf, 9.0 / 252.0);
+        }
+
+        #[test]
+        fn test_day_count_with_loaded_holidays() {
+            let public_holidays = load_holidays("ZA").unwrap();
+            let convention = DayCountConventions::WorkingDays252;
+
+            // Human Rights Day, Good Friday and Family Day.
+            let (start, end) = (date(2024, 3, 18), date(2024, 4, 8));
+            let days = convention.day_count_with_holidays(&start, &end, &public_holidays);
+            assert_approx_eq!(days, 12.0);
+            let days = convention.day_count_with_holidays(&end, &start, &public_holidays);
+            assert_approx_eq!(days, -12.0);
+        }
+
+        #[test]
+        fn test_other_conventions_ignore_holidays() {
+            let public_holidays = vec![date(2024, 1, 2)];
+            let convention = DayCountConventions::Actual360;
+            let (start, end) = (vec![date(2024, 1, 1)], vec![date(2024, 1, 31)]);
+            let yf: Vec<f64> =
+                convention.year_fraction_with_holidays(&start, &end, &public_holidays);
+            assert_approx_eq!(yf[0], 30.0 / 360.0);
+        }
+    }
+
+    mod test_actual_actual_icma_reference {
+
+        use super::*;
+
+        // Examples from the ISDA paper 'EMU and market conventions'.
+        #[test]
+        fn test_regular_period() {
+            let convention = DayCountConventions::ActualActualICMA(IntervalPeriod::Months(6));
+            let reference =
+                ReferencePeriod::new(date(2004, 5, 1), IntervalPeriod::Months(6)).unwrap();
+            let yf = convention.year_fraction_with_reference(
+                &date(2003, 11, 1),
+                &date(2004, 5, 1),
+                &reference,
+            );
+            assert_approx_eq!(yf, 0.5, 1e-12);
+        }
+
+        #[test]
+        fn test_short_first_period() {
+            let convention = DayCountConventions::ActualActualICMA(IntervalPeriod::Years(1));
+            let reference =
+                ReferencePeriod::new(date(1999, 7, 1), IntervalPeriod::Years(1)).unwrap();
+            let yf = convention.year_fraction_with_reference(
+                &date(1999, 2, 1),
+                &date(1999, 7, 1),
+                &reference,
+            );
+            assert_approx_eq!(yf, 0.410958904110, 1e-12);
+        }
+
+        #[test]
+        fn test_long_first_period() {
+            let convention = DayCountConventions::ActualActualICMA(IntervalPeriod::Months(6));
+            let reference =
+                ReferencePeriod::new(date(2003, 7, 15), IntervalPeriod::Months(6)).unwrap();
+            let yf = convention.year_fraction_with_reference(
+                &date(2002, 8, 15),
+                &date(2003, 7, 15),
+                &reference,
+            );
+            assert_approx_eq!(yf, 0.915760869565, 1e-12);
+        }
+
+        #[test]
+        fn test_short_final_period() {
+            let convention = DayCountConventions::ActualActualICMA(IntervalPeriod::Months(6));
+            let reference =
+                ReferencePeriod::new(date(2000, 1, 30), IntervalPeriod::Months(6)).unwrap();
+            let yf = convention.year_fraction_with_reference(
+                &date(2000, 1, 30),
+                &date(2000, 6, 30),
+                &reference,
+            );
+            assert_approx_eq!(yf, 0.417582417582, 1e-12);
+        }
+
+        #[test]
+        fn test_long_final_period() {
+            let convention = DayCountConventions::ActualActualICMA(IntervalPeriod::Months(3));
+            let reference = ReferencePeriod::new(date(1999, 11, 30), IntervalPeriod::Months(3))
+                .unwrap()
+                .end_of_month(true);
+            let yf = convention.year_fraction_with_reference(
+                &date(1999, 11, 30),
+                &date(2000, 4, 30),
+                &reference,
+            );
+            assert_approx_eq!(yf, 0.415760869565, 1e-12);
+        }
+
+        #[test]
+        fn test_accrued_within_long_first_period() {
+            let convention = DayCountConventions::ActualActualICMA(IntervalPeriod::Months(6));
+            let reference =
+                ReferencePeriod::new(date(2003, 7, 15), IntervalPeriod::Months(6)).unwrap();
+            let yf = convention.year_fraction_with_reference(
+                &date(2002, 8, 15),
+                &date(2002, 11, 15),
+                &reference,
+            );
+            assert_approx_eq!(yf, 92.0 / (2.0 * 184.0), 1e-12);
+        }
+
+        #[test]
+        fn test_zero_tenor() {
+            assert!(matches!(
+                ReferencePeriod::new(date(2003, 7, 15), IntervalPeriod::Months(0)),
+                Err(Error::PeriodError(periods::Error::InvalidTenor(_)))
+            ));
+        }
+
+        #[test]
+        fn test_other_conventions_ignore_reference() {
+            let convention = DayCountConventions::Actual365Fixed;
+            let reference =
+                ReferencePeriod::new(date(2003, 7, 15), IntervalPeriod::Months(6)).unwrap();
+            let yf = convention.year_fraction_with_reference(
+                &date(2002, 8, 15),
+                &date(2003, 7, 15),
+                &reference,
+            );
+            assert_approx_eq!(yf, 334.0 / 365.0, 1e-12);
+        }
+    }
+}
@@ -1,12 +1,18 @@
-use std::collections::HashMap;
+//! Public holiday requests keyed by `CountryTwoCode`.
+//!
+//! Builds the Nager.Date requests of `resources::holidays` from country codes,
+//! rather than strings.
+//!
+//! ```rust,ignore
+//! let calendar = HolidaysBuilder::new()
+//!     .country_codes(&[CountryTwoCode::ZA, CountryTwoCode::US])
+//!     .periods(&[2023, 2024])
+//!     .build()
+//!     .fetch()?;
+//! ```
 
 use super::CountryTwoCode;
-use chrono::NaiveDate;
-use reqwest::blocking::Client;
-
-//  --- Constants ---
-const BASE_URL: &str = "https://date.nager.at/api/v3";
-const RESOURCE_DIR: &str = "src/resources/holidays";
+use crate::resources::holidays::{PublicHolidayRequest, PublicHolidayRequestBuilder};
 
 #[derive(Default)]
 pub struct NoCountryCodes;
@@ -22,15 +28,9 @@ pub struct HolidaysBuilder<C, P> {
     country_codes: C,
     periods: P,
 }
-pub struct HolidaysRequest {
-    client: Client,
-    identifiers: Vec<String>,
-    urls: Vec<String>,
-}
-pub struct Holidays<A>(HashMap<String, Vec<A>>);
 
 impl HolidaysBuilder<NoCountryCodes, NoPeriods> {
-    fn new() -> Self {
+    pub fn new() -> Self {
         Self::default()
     }
 }
@@ -55,27 +55,18 @@ impl<C, P> HolidaysBuilder<C, P> {
 }
 
 impl HolidaysBuilder<CountryCodes, Periods> {
-    pub fn build(self) -> HolidaysRequest {
-        let client = Client::new();
+    pub fn build(self) -> PublicHolidayRequest {
+        let country_codes: Vec<String> = self
+            .country_codes
+            .0
+            .iter()
+            .map(|code| code.to_string())
+            .collect();
+        let country_codes: Vec<&str> = country_codes.iter().map(|code| code.as_str()).collect();
 
-        let mut identifiers = Vec::new();
-        let mut urls = Vec::new();
-
-        for p in self.periods.0 {
-            for c in self.country_codes.0.clone() {
-                identifiers.push(c.clone().to_string());
-                let url = format!("{}/PublicHolidays/{}/{}", BASE_URL, p, &c);
-                urls.push(url);
-            }
-        }
-
-        HolidaysRequest {
-            client,
-            identifiers,
-            urls,
-        }
+        PublicHolidayRequestBuilder::new()
+            .country_codes(&country_codes)
+            .periods(&self.periods.0)
+            .build()
     }
 }
-
-//  --- Cache ---
-impl Holidays<u32> {}
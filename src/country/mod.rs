@@ -1,4 +1,5 @@
 pub mod holidays;
 pub mod iso3166;
+pub mod rules;
 pub use iso3166::CountryThreeCode;
 pub use iso3166::CountryTwoCode;
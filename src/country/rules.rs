@@ -0,0 +1,745 @@
+//! Rule based holidays.
+//!
+//! Generates the public holidays of a financial centre for any year, without
+//! requiring the cached holidays in `resources::holidays`. Each holiday is described
+//! by a date rule (fixed date, relative to Easter, nth weekday of a month, or an
+//! equinox), and an observance rule, which moves a holiday falling on a weekend.
+//!
+//! Holidays are returned as observed, such that a holiday which falls on a weekend
+//! and is substituted only appears on its substitute date. Ad hoc closures (royal
+//! events, elections, days of mourning) are only known up to the time of writing.
+//! The Japanese equinox days are set by observation, and are only generated between
+//! 1980 and 2099, the range of the approximation used.
+//!
+//! ```rust,ignore
+//! use dpm::country::rules::HolidayCalendars;
+//! use dpm::country::CountryTwoCode;
+//!
+//! let za = HolidayCalendars::try_from(CountryTwoCode::ZA).unwrap();
+//! let calendar = za.calendar(2024, 2060);
+//! ```
+
+use std::collections::BTreeSet;
+
+use chrono::{Datelike, Days, NaiveDate, Weekday};
+
+use super::CountryTwoCode;
+use crate::time::calendar::Calendar;
+use crate::time::ops::EndOfMonth;
+
+//  --- Errors
+type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+    UnsupportedCountry(CountryTwoCode),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnsupportedCountry(code) => {
+                write!(f, "No holiday rules are available for {}", code)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+//  --- Enums
+
+/// Date on which a holiday falls, before any weekend substitution.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DateRule {
+    /// Fixed month and day.
+    Fixed(u32, u32),
+    /// Number of days after Easter Sunday, negative for days before.
+    Easter(i64),
+    /// Nth weekday of the month, where negative values count back from the month end.
+    /// Zero matches no date.
+    NthWeekday(u32, Weekday, i8),
+    /// Japanese vernal equinox day, between 1980 and 2099.
+    VernalEquinox,
+    /// Japanese autumnal equinox day, between 1980 and 2099.
+    AutumnalEquinox,
+    /// Single, non recurring date.
+    Once(NaiveDate),
+}
+
+/// Substitution of a holiday which falls on a weekend.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Observance {
+    /// Not substituted.
+    #[default]
+    None,
+    /// Sunday moves to the next day that is not already a holiday.
+    SundayToNext,
+    /// Saturday or Sunday moves to the next weekday that is not already a holiday.
+    WeekendToNext,
+    /// Saturday moves to Friday, Sunday moves to Monday.
+    NearestWeekday,
+}
+
+/// Financial centres with rule based holidays.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HolidayCalendars {
+    /// South Africa.
+    ZA,
+    /// United States bond market, as recommended by SIFMA.
+    USSifma,
+    /// New York Stock Exchange.
+    USNyse,
+    /// England and Wales bank holidays.
+    GB,
+    /// Euro area TARGET2 settlement system.
+    Target2,
+    /// Tokyo, including the bank holidays on 2 and 3 January and 31 December. The
+    /// equinox days are only included between 1980 and 2099.
+    JP,
+    /// Sydney.
+    AU,
+    /// Zurich.
+    CH,
+}
+
+//  --- Structs
+#[derive(Clone, Debug, PartialEq)]
+pub struct HolidayRule {
+    date: DateRule,
+    observance: Observance,
+    first_year: Option<i32>,
+    last_year: Option<i32>,
+    except: Vec<i32>,
+}
+
+//  --- Implementations
+
+impl HolidayRule {
+    pub fn new(date: DateRule) -> Self {
+        Self {
+            date,
+            observance: Observance::default(),
+            first_year: None,
+            last_year: None,
+            except: Vec::new(),
+        }
+    }
+
+    pub fn observance(self, observance: Observance) -> Self {
+        Self { observance, ..self }
+    }
+
+    /// First year in which the holiday is observed.
+    pub fn from_year(self, year: i32) -> Self {
+        Self {
+            first_year: Some(year),
+            ..self
+        }
+    }
+
+    /// Last year in which the holiday is observed.
+    pub fn until_year(self, year: i32) -> Self {
+        Self {
+            last_year: Some(year),
+            ..self
+        }
+    }
+
+    /// Years in which the holiday is not observed, typically as it was moved.
+    pub fn except(self, years: &[i32]) -> Self {
+        Self {
+            except: years.to_vec(),
+            ..self
+        }
+    }
+
+    /// Unadjusted date of the holiday in 'year', if it is observed in that year.
+    pub fn date_in(&self, year: i32) -> Option<NaiveDate> {
+        if self.first_year.is_some_and(|y| year < y)
+            || self.last_year.is_some_and(|y| year > y)
+            || self.except.contains(&year)
+        {
+            return None;
+        }
+
+        match self.date {
+            DateRule::Fixed(month, day) => NaiveDate::from_ymd_opt(year, month, day),
+            DateRule::Easter(offset) => {
+                let easter = easter_sunday(year)?;
+                if offset >= 0 {
+                    easter.checked_add_days(Days::new(offset as u64))
+                } else {
+                    easter.checked_sub_days(Days::new(offset.unsigned_abs()))
+                }
+            }
+            DateRule::NthWeekday(month, weekday, n) => nth_weekday(year, month, weekday, n),
+            DateRule::VernalEquinox => NaiveDate::from_ymd_opt(year, 3, equinox(year, 20.8431)?),
+            DateRule::AutumnalEquinox => NaiveDate::from_ymd_opt(year, 9, equinox(year, 23.2488)?),
+            DateRule::Once(date) => (date.year() == year).then_some(date),
+        }
+    }
+}
+
+impl HolidayCalendars {
+    /// Holiday rules, in the order in which weekend substitutes are assigned.
+    pub fn rules(&self) -> Vec<HolidayRule> {
+        match self {
+            Self::ZA => za_rules(),
+            Self::USSifma => us_sifma_rules(),
+            Self::USNyse => us_nyse_rules(),
+            Self::GB => gb_rules(),
+            Self::Target2 => target2_rules(),
+            Self::JP => jp_rules(),
+            Self::AU => au_rules(),
+            Self::CH => ch_rules(),
+        }
+    }
+
+    /// Observed holidays in 'year', sorted.
+    pub fn holidays(&self, year: i32) -> Vec<NaiveDate> {
+        let rules = self.rules();
+        let unadjusted: Vec<(&HolidayRule, NaiveDate)> = rules
+            .iter()
+            .filter_map(|rule| rule.date_in(year).map(|date| (rule, date)))
+            .collect();
+
+        // Substitutes may not fall on any other holiday, observed or not.
+        let mut taken: BTreeSet<NaiveDate> = unadjusted.iter().map(|(_, date)| *date).collect();
+        let mut observed = BTreeSet::new();
+
+        for (rule, date) in unadjusted {
+            let date = match (rule.observance, date.weekday()) {
+                (Observance::SundayToNext, Weekday::Sun) => next_free_day(&date, &taken, false),
+                (Observance::WeekendToNext, Weekday::Sat | Weekday::Sun) => {
+                    next_free_day(&date, &taken, true)
+                }
+                (Observance::NearestWeekday, Weekday::Sat) => date.pred_opt(),
+                (Observance::NearestWeekday, Weekday::Sun) => date.succ_opt(),
+                _ => Some(date),
+            };
+            if let Some(date) = date {
+                taken.insert(date);
+                observed.insert(date);
+            }
+        }
+
+        if self.bridges_holidays() {
+            observed.extend(bridged_days(&observed));
+        }
+
+        observed.into_iter().collect()
+    }
+
+    /// Observed holidays in ['first_year', 'last_year'], sorted.
+    pub fn holidays_between(&self, first_year: i32, last_year: i32) -> Vec<NaiveDate> {
+        (first_year..=last_year)
+            .flat_map(|year| self.holidays(year))
+            .collect()
+    }
+
+    /// Calendar with a Saturday and Sunday weekend, holding the holidays in
+    /// ['first_year', 'last_year'].
+    pub fn calendar(&self, first_year: i32, last_year: i32) -> Calendar {
+        Calendar::new(&self.holidays_between(first_year, last_year))
+    }
+
+    /// Whether a day between two holidays is itself a holiday, as with the
+    /// Japanese citizen's holiday.
+    fn bridges_holidays(&self) -> bool {
+        matches!(self, Self::JP)
+    }
+}
+
+//  --- Trait implementations: Concrete
+
+/// The United States resolves to the SIFMA bond market calendar, not the NYSE,
+/// which is only available as `HolidayCalendars::USNyse`. Euro area members
+/// resolve to the TARGET2 settlement calendar rather than national holidays.
+impl TryFrom<CountryTwoCode> for HolidayCalendars {
+    type Error = Error;
+
+    fn try_from(value: CountryTwoCode) -> Result<Self> {
+        match value {
+            CountryTwoCode::ZA => Ok(Self::ZA),
+            CountryTwoCode::US => Ok(Self::USSifma),
+            CountryTwoCode::GB => Ok(Self::GB),
+            CountryTwoCode::JP => Ok(Self::JP),
+            CountryTwoCode::AU => Ok(Self::AU),
+            CountryTwoCode::CH => Ok(Self::CH),
+            CountryTwoCode::AT
+            | CountryTwoCode::BE
+            | CountryTwoCode::DE
+            | CountryTwoCode::ES
+            | CountryTwoCode::FI
+            | CountryTwoCode::FR
+            | CountryTwoCode::GR
+            | CountryTwoCode::IE
+            | CountryTwoCode::IT
+            | CountryTwoCode::LT
+            | CountryTwoCode::LU
+            | CountryTwoCode::LV
+            | CountryTwoCode::MT
+            | CountryTwoCode::NL
+            | CountryTwoCode::PT
+            | CountryTwoCode::SI
+            | CountryTwoCode::SK => Ok(Self::Target2),
+            _ => Err(Error::UnsupportedCountry(value)),
+        }
+    }
+}
+
+//  --- Standalone functions
+
+/// Easter Sunday in the Gregorian calendar (anonymous Gregorian algorithm).
+pub fn easter_sunday(year: i32) -> Option<NaiveDate> {
+    let a = year % 19;
+    let b = year / 100;
+    let c = year % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32)
+}
+
+fn nth_weekday(year: i32, month: u32, weekday: Weekday, n: i8) -> Option<NaiveDate> {
+    if n == 0 {
+        return None;
+    }
+    if n > 0 {
+        return NaiveDate::from_weekday_of_month_opt(year, month, weekday, n as u8);
+    }
+    let last = NaiveDate::from_ymd_opt(year, month, 1)?.eom()?;
+    let days_back = (7 + last.weekday().num_days_from_monday() - weekday.num_days_from_monday())
+        % 7
+        + 7 * (n.unsigned_abs() as u32 - 1);
+    let date = last.checked_sub_days(Days::new(days_back as u64))?;
+    (date.month() == month).then_some(date)
+}
+
+/// Day of the equinox in March or September. None outside 1980 to 2099, where the
+/// approximation is not valid.
+fn equinox(year: i32, base: f64) -> Option<u32> {
+    if !(1980..=2099).contains(&year) {
+        return None;
+    }
+    let elapsed = year - 1980;
+    Some((base + 0.242194 * elapsed as f64 - (elapsed / 4) as f64).floor() as u32)
+}
+
+fn next_free_day(
+    date: &NaiveDate,
+    taken: &BTreeSet<NaiveDate>,
+    skip_weekend: bool,
+) -> Option<NaiveDate> {
+    let mut date = date.succ_opt()?;
+    while taken.contains(&date)
+        || (skip_weekend && matches!(date.weekday(), Weekday::Sat | Weekday::Sun))
+    {
+        date = date.succ_opt()?;
+    }
+    Some(date)
+}
+
+/// Days, other than Sundays, which fall between two holidays.
+fn bridged_days(holidays: &BTreeSet<NaiveDate>) -> Vec<NaiveDate> {
+    holidays
+        .iter()
+        .filter_map(|date| date.checked_add_days(Days::new(2)).map(|d| (date, d)))
+        .filter(|(_, after)| holidays.contains(after))
+        .filter_map(|(date, _)| date.succ_opt())
+        .filter(|d| !holidays.contains(d) && d.weekday() != Weekday::Sun)
+        .collect()
+}
+
+fn once(year: i32, month: u32, day: u32) -> HolidayRule {
+    // Rule sets are hard coded, such that an invalid date is a programming error.
+    HolidayRule::new(DateRule::Once(
+        NaiveDate::from_ymd_opt(year, month, day).expect("invalid holiday date"),
+    ))
+}
+
+fn fixed(month: u32, day: u32) -> HolidayRule {
+    HolidayRule::new(DateRule::Fixed(month, day))
+}
+
+fn easter(offset: i64) -> HolidayRule {
+    HolidayRule::new(DateRule::Easter(offset))
+}
+
+fn nth(month: u32, weekday: Weekday, n: i8) -> HolidayRule {
+    HolidayRule::new(DateRule::NthWeekday(month, weekday, n))
+}
+
+//  --- Rule sets
+
+fn za_rules() -> Vec<HolidayRule> {
+    let mut rules: Vec<HolidayRule> = [
+        (1, 1),
+        (3, 21),
+        (4, 27),
+        (5, 1),
+        (6, 16),
+        (8, 9),
+        (9, 24),
+        (12, 16),
+        (12, 25),
+        (12, 26),
+    ]
+    .into_iter()
+    .map(|(m, d)| fixed(m, d).observance(Observance::SundayToNext))
+    .collect();
+    rules.extend([easter(-2), easter(1)]);
+    // Elections and other declared holidays.
+    rules.extend([
+        once(2011, 5, 18),
+        once(2014, 5, 7),
+        once(2016, 8, 3),
+        once(2019, 5, 8),
+        once(2021, 11, 1),
+        once(2023, 12, 15),
+        once(2024, 5, 29),
+    ]);
+    rules
+}
+
+fn us_sifma_rules() -> Vec<HolidayRule> {
+    vec![
+        fixed(1, 1).observance(Observance::SundayToNext),
+        nth(1, Weekday::Mon, 3),
+        nth(2, Weekday::Mon, 3),
+        easter(-2),
+        nth(5, Weekday::Mon, -1),
+        fixed(6, 19)
+            .observance(Observance::NearestWeekday)
+            .from_year(2022),
+        fixed(7, 4).observance(Observance::NearestWeekday),
+        nth(9, Weekday::Mon, 1),
+        nth(10, Weekday::Mon, 2),
+        fixed(11, 11).observance(Observance::SundayToNext),
+        nth(11, Weekday::Thu, 4),
+        fixed(12, 25).observance(Observance::NearestWeekday),
+    ]
+}
+
+fn us_nyse_rules() -> Vec<HolidayRule> {
+    vec![
+        fixed(1, 1).observance(Observance::SundayToNext),
+        nth(1, Weekday::Mon, 3).from_year(1998),
+        nth(2, Weekday::Mon, 3),
+        easter(-2),
+        nth(5, Weekday::Mon, -1),
+        fixed(6, 19)
+            .observance(Observance::NearestWeekday)
+            .from_year(2022),
+        fixed(7, 4).observance(Observance::NearestWeekday),
+        nth(9, Weekday::Mon, 1),
+        nth(11, Weekday::Thu, 4),
+        fixed(12, 25).observance(Observance::NearestWeekday),
+        // Special closures.
+        once(2001, 9, 11),
+        once(2001, 9, 12),
+        once(2001, 9, 13),
+        once(2001, 9, 14),
+        once(2004, 6, 11),
+        once(2007, 1, 2),
+        once(2012, 10, 29),
+        once(2012, 10, 30),
+        once(2018, 12, 5),
+        once(2025, 1, 9),
+    ]
+}
+
+fn gb_rules() -> Vec<HolidayRule> {
+    vec![
+        fixed(1, 1).observance(Observance::WeekendToNext),
+        easter(-2),
+        easter(1),
+        nth(5, Weekday::Mon, 1).except(&[1995, 2020]),
+        nth(5, Weekday::Mon, -1).except(&[2002, 2012, 2022]),
+        nth(8, Weekday::Mon, -1),
+        fixed(12, 25).observance(Observance::WeekendToNext),
+        fixed(12, 26).observance(Observance::WeekendToNext),
+        // Moved bank holidays.
+        once(1995, 5, 8),
+        once(2002, 6, 4),
+        once(2012, 6, 4),
+        once(2020, 5, 8),
+        once(2022, 6, 2),
+        // Royal events and the millennium.
+        once(1999, 12, 31),
+        once(2002, 6, 3),
+        once(2011, 4, 29),
+        once(2012, 6, 5),
+        once(2022, 6, 3),
+        once(2022, 9, 19),
+        once(2023, 5, 8),
+    ]
+}
+
+fn target2_rules() -> Vec<HolidayRule> {
+    vec![
+        fixed(1, 1),
+        easter(-2).from_year(2000),
+        easter(1).from_year(2000),
+        fixed(5, 1).from_year(2000),
+        fixed(12, 25),
+        fixed(12, 26).from_year(2000),
+        once(1999, 12, 31),
+        once(2001, 12, 31),
+    ]
+}
+
+fn jp_rules() -> Vec<HolidayRule> {
+    use Observance::SundayToNext;
+
+    vec![
+        // Bank holidays, which fall within the new year closure.
+        fixed(1, 1),
+        fixed(1, 2),
+        fixed(1, 3),
+        fixed(12, 31),
+        // National holidays.
+        nth(1, Weekday::Mon, 2).from_year(2000),
+        fixed(2, 11).observance(SundayToNext),
+        fixed(2, 23).observance(SundayToNext).from_year(2020),
+        HolidayRule::new(DateRule::VernalEquinox).observance(SundayToNext),
+        fixed(4, 29).observance(SundayToNext),
+        fixed(5, 3).observance(SundayToNext),
+        fixed(5, 4).observance(SundayToNext).from_year(2007),
+        fixed(5, 5).observance(SundayToNext),
+        fixed(7, 20)
+            .observance(SundayToNext)
+            .from_year(1996)
+            .until_year(2002),
+        nth(7, Weekday::Mon, 3)
+            .from_year(2003)
+            .except(&[2020, 2021]),
+        fixed(8, 11)
+            .observance(SundayToNext)
+            .from_year(2016)
+            .except(&[2020, 2021]),
+        fixed(9, 15).observance(SundayToNext).until_year(2002),
+        nth(9, Weekday::Mon, 3).from_year(2003),
+        HolidayRule::new(DateRule::AutumnalEquinox).observance(SundayToNext),
+        nth(10, Weekday::Mon, 2)
+            .from_year(2000)
+            .except(&[2020, 2021]),
+        fixed(11, 3).observance(SundayToNext),
+        fixed(11, 23).observance(SundayToNext),
+        fixed(12, 23)
+            .observance(SundayToNext)
+            .from_year(1989)
+            .until_year(2018),
+        // Imperial succession and the Tokyo Olympics.
+        once(2019, 4, 30),
+        once(2019, 5, 1),
+        once(2019, 5, 2),
+        once(2019, 10, 22),
+        once(2020, 7, 23),
+        once(2020, 7, 24),
+        once(2020, 8, 10),
+        once(2021, 7, 22),
+        once(2021, 7, 23),
+        once(2021, 8, 9),
+    ]
+}
+
+fn au_rules() -> Vec<HolidayRule> {
+    vec![
+        fixed(1, 1).observance(Observance::WeekendToNext),
+        fixed(1, 26).observance(Observance::WeekendToNext),
+        easter(-2),
+        easter(1),
+        fixed(4, 25),
+        nth(6, Weekday::Mon, 2),
+        nth(8, Weekday::Mon, 1),
+        nth(10, Weekday::Mon, 1),
+        fixed(12, 25).observance(Observance::WeekendToNext),
+        fixed(12, 26).observance(Observance::WeekendToNext),
+        // National day of mourning.
+        once(2022, 9, 22),
+    ]
+}
+
+fn ch_rules() -> Vec<HolidayRule> {
+    vec![
+        fixed(1, 1),
+        fixed(1, 2),
+        easter(-2),
+        easter(1),
+        fixed(5, 1),
+        easter(39),
+        easter(50),
+        fixed(8, 1),
+        fixed(12, 25),
+        fixed(12, 26),
+    ]
+}
+
+//  --- Unit tests
+#[cfg(test)]
+mod test_rules {
+
+    use super::*;
+    use crate::resources::holidays::load_holidays;
+    use crate::test_utils::date;
+
+    #[test]
+    fn test_easter_sunday() {
+        assert_eq!(easter_sunday(2024), Some(date(2024, 3, 31)));
+        assert_eq!(easter_sunday(2025), Some(date(2025, 4, 20)));
+        assert_eq!(easter_sunday(2038), Some(date(2038, 4, 25)));
+        assert_eq!(easter_sunday(2285), Some(date(2285, 3, 22)));
+    }
+
+    #[test]
+    fn test_nth_weekday() {
+        assert_eq!(
+            nth_weekday(2024, 5, Weekday::Mon, -1),
+            Some(date(2024, 5, 27))
+        );
+        assert_eq!(
+            nth_weekday(2024, 11, Weekday::Thu, 4),
+            Some(date(2024, 11, 28))
+        );
+        assert_eq!(
+            nth_weekday(2024, 9, Weekday::Mon, -1),
+            Some(date(2024, 9, 30))
+        );
+        assert_eq!(
+            nth_weekday(2024, 9, Weekday::Mon, 5),
+            Some(date(2024, 9, 30))
+        );
+        assert_eq!(nth_weekday(2024, 2, Weekday::Fri, 5), None);
+        assert_eq!(nth_weekday(2024, 5, Weekday::Mon, 0), None);
+        assert_eq!(
+            HolidayRule::new(DateRule::NthWeekday(5, Weekday::Mon, 0)).date_in(2024),
+            None
+        );
+    }
+
+    #[test]
+    fn test_za_matches_cached_holidays() {
+        let cached = load_holidays("ZA").unwrap();
+        let generated = HolidayCalendars::ZA.holidays_between(2009, 2039);
+        let calendar = Calendar::new(&generated);
+
+        // The cached holidays omit declared holidays, such as elections.
+        assert!(cached.iter().all(|d| calendar.is_holiday(d)));
+        assert!(calendar.is_holiday(&date(2024, 5, 29)));
+        // Christmas on a Sunday moves past the Day of Goodwill.
+        assert!(calendar.is_holiday(&date(2022, 12, 27)));
+    }
+
+    #[test]
+    fn test_us() {
+        let sifma = HolidayCalendars::USSifma.holidays(2021);
+        assert!(sifma.contains(&date(2021, 12, 24)));
+        assert!(sifma.contains(&date(2021, 10, 11)));
+        assert!(!sifma.contains(&date(2021, 6, 18)));
+        // New year on a Saturday is not observed on the Friday.
+        assert!(!HolidayCalendars::USSifma
+            .holidays(2021)
+            .contains(&date(2021, 12, 31)));
+
+        let nyse = HolidayCalendars::USNyse.holidays(2022);
+        assert!(nyse.contains(&date(2022, 6, 20)));
+        assert!(!nyse.contains(&date(2022, 10, 10)));
+        assert_eq!(nyse.len(), 10);
+    }
+
+    #[test]
+    fn test_gb() {
+        let expected = vec![
+            date(2022, 1, 3),
+            date(2022, 4, 15),
+            date(2022, 4, 18),
+            date(2022, 5, 2),
+            date(2022, 6, 2),
+            date(2022, 6, 3),
+            date(2022, 8, 29),
+            date(2022, 9, 19),
+            date(2022, 12, 26),
+            date(2022, 12, 27),
+        ];
+        assert_eq!(HolidayCalendars::GB.holidays(2022), expected);
+
+        // Christmas on a Saturday and Boxing Day on a Sunday.
+        let holidays = HolidayCalendars::GB.holidays(2021);
+        assert!(holidays.contains(&date(2021, 12, 27)));
+        assert!(holidays.contains(&date(2021, 12, 28)));
+    }
+
+    #[test]
+    fn test_target2() {
+        let expected = vec![
+            date(2024, 1, 1),
+            date(2024, 3, 29),
+            date(2024, 4, 1),
+            date(2024, 5, 1),
+            date(2024, 12, 25),
+            date(2024, 12, 26),
+        ];
+        assert_eq!(HolidayCalendars::Target2.holidays(2024), expected);
+    }
+
+    #[test]
+    fn test_jp() {
+        let holidays = HolidayCalendars::JP.holidays(2026);
+        assert!(holidays.contains(&date(2026, 3, 20)));
+        assert!(holidays.contains(&date(2026, 9, 23)));
+        // Citizen's holiday between Respect for the Aged Day and the equinox.
+        assert!(holidays.contains(&date(2026, 9, 22)));
+        // Constitution Day on a Sunday is substituted after Children's Day.
+        assert!(holidays.contains(&date(2026, 5, 6)));
+
+        let holidays = HolidayCalendars::JP.holidays(2024);
+        assert!(holidays.contains(&date(2024, 2, 12)));
+        // The equinox on a Sunday is observed on the Monday.
+        assert!(holidays.contains(&date(2024, 9, 23)));
+
+        // Equinox days are not generated outside the range of the approximation.
+        let equinoxes = [
+            HolidayRule::new(DateRule::VernalEquinox),
+            HolidayRule::new(DateRule::AutumnalEquinox),
+        ];
+        for year in [1979, 2100] {
+            assert!(equinoxes.iter().all(|rule| rule.date_in(year).is_none()));
+        }
+        assert_eq!(equinoxes[0].date_in(2099), Some(date(2099, 3, 20)));
+    }
+
+    #[test]
+    fn test_au_and_ch() {
+        let holidays = HolidayCalendars::AU.holidays(2021);
+        assert!(holidays.contains(&date(2021, 12, 27)));
+        assert!(holidays.contains(&date(2021, 12, 28)));
+        assert!(!holidays.contains(&date(2021, 4, 26)));
+
+        let holidays = HolidayCalendars::CH.holidays(2024);
+        assert!(holidays.contains(&date(2024, 5, 9)));
+        assert!(holidays.contains(&date(2024, 5, 20)));
+        assert_eq!(holidays.len(), 10);
+    }
+
+    #[test]
+    fn test_country_code() {
+        let calendars = HolidayCalendars::try_from(CountryTwoCode::US).unwrap();
+        assert_eq!(calendars, HolidayCalendars::USSifma);
+        let calendars = HolidayCalendars::try_from(CountryTwoCode::DE).unwrap();
+        assert_eq!(calendars, HolidayCalendars::Target2);
+        assert!(HolidayCalendars::try_from(CountryTwoCode::AF).is_err());
+
+        let calendar = HolidayCalendars::ZA.calendar(2060, 2060);
+        assert!(calendar.is_holiday(&date(2060, 4, 27)));
+    }
+}
@@ -0,0 +1,617 @@
+//! Discount curve bootstrapping.
+//!
+//! Builds a discount curve from an ordered strip of deposit, FRA, futures and par
+//! swap quotes. Each instrument adds a pillar at its maturity, whose discount factor
+//! is solved such that the instrument reprices to par, given the pillars before it.
+//! Discount factors between pillars are interpolated.
+//!
+//! All instruments are priced off a single curve, such that:
+//! DEPOSIT / FRA / FUTURE: Df(start) = (1 + r.t).Df(end)
+//! SWAP: Df(start) = S.(t1.Df1 + t2.Df2 ... + tn.Dfn) + Dfn
+//!
+//! ```rust,ignore
+//! let curve: Term<f64> = CurveBootstrapper::new(reference_date)
+//!     .calendar(calendar)
+//!     .bootstrap(&instruments)?;
+//! ```
+
+use std::cell::RefCell;
+
+use chrono::NaiveDate;
+
+use crate::conventions::business_day::BusinessDayConventions;
+use crate::conventions::day_count::{self, DayCountConventions};
+use crate::interest::term_structure::{Term, TermStructure};
+use crate::math::interpolation::{self, InterpolationMethod, TryInterpolate};
+use crate::math::solver::{self, RootFinder};
+use crate::time::calendar::Calendar;
+use crate::time::periods::IntervalPeriod;
+use crate::time::schedule::{self, ScheduleBuilder};
+
+//  --- Constants
+
+/// Largest acceptable difference between the repriced instrument and par.
+const REPRICING_TOLERANCE: f64 = 1e-10;
+
+/// Residual at which the solver stops, attainable when repricing an instrument.
+const SOLVER_TOLERANCE: f64 = 1e-14;
+
+//  --- Errors
+type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+    NoInstruments,
+    InstrumentBeforeReferenceDate(NaiveDate),
+    UnorderedMaturities(NaiveDate, NaiveDate),
+    NoConvergence(NaiveDate, solver::Error),
+    InterpolationError(NaiveDate, interpolation::Error),
+    ScheduleError(schedule::Error),
+    DayCountError(day_count::Error),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoInstruments => write!(f, "At least one instrument is required."),
+            Self::InstrumentBeforeReferenceDate(date) => write!(
+                f,
+                "Instrument starting on {} precedes the reference date.",
+                date
+            ),
+            Self::UnorderedMaturities(previous, current) => write!(
+                f,
+                "Maturity {} should follow the previous maturity {}.",
+                current, previous
+            ),
+            Self::NoConvergence(date, err) => {
+                write!(
+                    f,
+                    "Failed to solve the discount factor on {}: {}",
+                    date, err
+                )
+            }
+            Self::InterpolationError(date, err) => {
+                write!(f, "Failed to interpolate the curve to {}: {}", date, err)
+            }
+            Self::ScheduleError(err) => write!(f, "{}", err),
+            Self::DayCountError(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<schedule::Error> for Error {
+    fn from(value: schedule::Error) -> Self {
+        Self::ScheduleError(value)
+    }
+}
+
+impl From<day_count::Error> for Error {
+    fn from(value: day_count::Error) -> Self {
+        Self::DayCountError(value)
+    }
+}
+
+//  --- Enums
+
+/// Quoted instruments, in order of maturity.
+#[derive(Clone, Debug)]
+pub enum Instruments {
+    Deposit(Deposit),
+    Fra(Fra),
+    Future(Future),
+    Swap(Swap),
+}
+
+/// Cash flows used to reprice an instrument, relative to par.
+enum Repricing {
+    /// Df(start) / ((1 + r.t).Df(end))
+    Simple {
+        start: NaiveDate,
+        end: NaiveDate,
+        interest: f64,
+    },
+    /// (S.(t1.Df1 ... + tn.Dfn) + Dfn) / Df(start)
+    Annuity {
+        start: NaiveDate,
+        payments: Vec<(NaiveDate, f64)>,
+        rate: f64,
+    },
+}
+
+//  --- Structs
+
+#[derive(Clone, Debug)]
+pub struct Deposit {
+    start: NaiveDate,
+    end: NaiveDate,
+    rate: f64,
+    day_count: DayCountConventions,
+}
+
+#[derive(Clone, Debug)]
+pub struct Fra {
+    start: NaiveDate,
+    end: NaiveDate,
+    rate: f64,
+    day_count: DayCountConventions,
+}
+
+#[derive(Clone, Debug)]
+pub struct Future {
+    start: NaiveDate,
+    end: NaiveDate,
+    price: f64,
+    convexity_adjustment: f64,
+    day_count: DayCountConventions,
+}
+
+#[derive(Clone, Debug)]
+pub struct Swap {
+    start: NaiveDate,
+    end: NaiveDate,
+    rate: f64,
+    tenor: IntervalPeriod,
+    day_count: DayCountConventions,
+    business_day_convention: BusinessDayConventions,
+}
+
+#[derive(Clone, Debug)]
+pub struct CurveBootstrapper {
+    reference_date: NaiveDate,
+    day_count: DayCountConventions,
+    interpolation_method: InterpolationMethod,
+    calendar: Calendar,
+}
+
+//  --- Implementations
+
+impl Deposit {
+    pub fn new(start: NaiveDate, end: NaiveDate, rate: f64) -> Self {
+        Self {
+            start,
+            end,
+            rate,
+            day_count: DayCountConventions::default(),
+        }
+    }
+
+    pub fn day_count(self, day_count: DayCountConventions) -> Self {
+        Self { day_count, ..self }
+    }
+}
+
+impl Fra {
+    pub fn new(start: NaiveDate, end: NaiveDate, rate: f64) -> Self {
+        Self {
+            start,
+            end,
+            rate,
+            day_count: DayCountConventions::default(),
+        }
+    }
+
+    pub fn day_count(self, day_count: DayCountConventions) -> Self {
+        Self { day_count, ..self }
+    }
+}
+
+impl Future {
+    /// Futures are quoted as 100 less the rate, in percent.
+    pub fn new(start: NaiveDate, end: NaiveDate, price: f64) -> Self {
+        Self {
+            start,
+            end,
+            price,
+            convexity_adjustment: 0.0,
+            day_count: DayCountConventions::default(),
+        }
+    }
+
+    /// Deducted from the futures rate to arrive at the forward rate.
+    pub fn convexity_adjustment(self, convexity_adjustment: f64) -> Self {
+        Self {
+            convexity_adjustment,
+            ..self
+        }
+    }
+
+    pub fn day_count(self, day_count: DayCountConventions) -> Self {
+        Self { day_count, ..self }
+    }
+
+    pub fn forward_rate(&self) -> f64 {
+        (100.0 - self.price) / 100.0 - self.convexity_adjustment
+    }
+}
+
+impl Swap {
+    /// Par swap, with a fixed leg paying 'rate' every 'tenor'.
+    pub fn new(start: NaiveDate, end: NaiveDate, rate: f64, tenor: IntervalPeriod) -> Self {
+        Self {
+            start,
+            end,
+            rate,
+            tenor,
+            day_count: DayCountConventions::default(),
+            business_day_convention: BusinessDayConventions::default(),
+        }
+    }
+
+    pub fn day_count(self, day_count: DayCountConventions) -> Self {
+        Self { day_count, ..self }
+    }
+
+    pub fn business_day_convention(self, business_day_convention: BusinessDayConventions) -> Self {
+        Self {
+            business_day_convention,
+            ..self
+        }
+    }
+}
+
+impl Instruments {
+    pub fn start(&self) -> NaiveDate {
+        match self {
+            Self::Deposit(a) => a.start,
+            Self::Fra(a) => a.start,
+            Self::Future(a) => a.start,
+            Self::Swap(a) => a.start,
+        }
+    }
+
+    pub fn end(&self) -> NaiveDate {
+        match self {
+            Self::Deposit(a) => a.end,
+            Self::Fra(a) => a.end,
+            Self::Future(a) => a.end,
+            Self::Swap(a) => a.end,
+        }
+    }
+
+    fn repricing(&self, calendar: &Calendar) -> Result<Repricing> {
+        let simple = |start, end, rate, day_count: &DayCountConventions| {
+            Ok::<_, Error>(Repricing::Simple {
+                start,
+                end,
+                interest: rate * day_count.try_year_fraction(&start, &end)?,
+            })
+        };
+
+        Ok(match self {
+            Self::Deposit(a) => simple(a.start, a.end, a.rate, &a.day_count)?,
+            Self::Fra(a) => simple(a.start, a.end, a.rate, &a.day_count)?,
+            Self::Future(a) => simple(a.start, a.end, a.forward_rate(), &a.day_count)?,
+            Self::Swap(a) => {
+                let schedule = ScheduleBuilder::new(a.start, a.end, a.tenor)
+                    .business_day_convention(a.business_day_convention)
+                    .build(calendar)?;
+                let year_fractions = schedule
+                    .periods()
+                    .iter()
+                    .map(|p| {
+                        a.day_count
+                            .try_year_fraction(&p.adjusted_start, &p.adjusted_end)
+                    })
+                    .collect::<std::result::Result<Vec<f64>, _>>()?;
+                let payments = schedule
+                    .payment_dates()
+                    .into_iter()
+                    .zip(year_fractions)
+                    .collect();
+                Repricing::Annuity {
+                    start: a.start,
+                    payments,
+                    rate: a.rate,
+                }
+            }
+        })
+    }
+}
+
+impl Repricing {
+    /// Pillar added by the instrument.
+    fn maturity(&self) -> NaiveDate {
+        match self {
+            Self::Simple { end, .. } => *end,
+            Self::Annuity {
+                start, payments, ..
+            } => payments.last().map_or(*start, |(date, _)| *date),
+        }
+    }
+
+    /// Value of the instrument relative to par, which is one when correctly priced.
+    fn ratio<A>(&self, discount: A) -> f64
+    where
+        A: Fn(&NaiveDate) -> f64,
+    {
+        match self {
+            Self::Simple {
+                start,
+                end,
+                interest,
+            } => discount(start) / ((1.0 + interest) * discount(end)),
+            Self::Annuity {
+                start,
+                payments,
+                rate,
+            } => {
+                let annuity: f64 = payments
+                    .iter()
+                    .map(|(date, fraction)| fraction * discount(date))
+                    .sum();
+                (rate * annuity + discount(&self.maturity())) / discount(start)
+            }
+        }
+    }
+}
+
+impl CurveBootstrapper {
+    pub fn new(reference_date: NaiveDate) -> Self {
+        Self {
+            reference_date,
+            day_count: DayCountConventions::default(),
+            interpolation_method: InterpolationMethod::default(),
+            calendar: Calendar::default(),
+        }
+    }
+
+    /// Day count used to convert pillar dates into times.
+    pub fn day_count(self, day_count: DayCountConventions) -> Self {
+        Self { day_count, ..self }
+    }
+
+    /// Interpolation of discount factors between pillars.
+    pub fn interpolation_method(self, interpolation_method: InterpolationMethod) -> Self {
+        Self {
+            interpolation_method,
+            ..self
+        }
+    }
+
+    /// Calendar used to generate swap schedules.
+    pub fn calendar(self, calendar: Calendar) -> Self {
+        Self { calendar, ..self }
+    }
+
+    /// Discount factors at each pillar, keyed by time from the reference date.
+    /// The curve is anchored at a discount factor of one on the reference date.
+    pub fn bootstrap(&self, instruments: &[Instruments]) -> Result<Term<f64>> {
+        if instruments.is_empty() {
+            return Err(Error::NoInstruments);
+        }
+
+        let mut x = vec![0.0];
+        let mut y = vec![1.0];
+        let mut previous = self.reference_date;
+        let solver = RootFinder::new().target(1.0).tolerance(SOLVER_TOLERANCE);
+
+        for instrument in instruments {
+            if instrument.start() < self.reference_date {
+                return Err(Error::InstrumentBeforeReferenceDate(instrument.start()));
+            }
+            let repricing = instrument.repricing(&self.calendar)?;
+            let maturity = repricing.maturity();
+            if maturity <= previous {
+                return Err(Error::UnorderedMaturities(previous, maturity));
+            }
+
+            x.push(self.time(&maturity)?);
+            y.push(y[y.len() - 1]);
+
+            // A failed interpolation stops the solver, and is reported in its place.
+            let failure = RefCell::new(None);
+            let ratio = |df: f64| {
+                let mut y = y.clone();
+                if let Some(last) = y.last_mut() {
+                    *last = df;
+                }
+                repricing.ratio(|date| {
+                    self.time(date)
+                        .and_then(|t| {
+                            self.interpolation_method
+                                .try_interpolate(&x, &y, &t)
+                                .map_err(|err| Error::InterpolationError(maturity, err))
+                        })
+                        .unwrap_or_else(|err| {
+                            failure.replace(Some(err));
+                            f64::NAN
+                        })
+                })
+            };
+
+            let df = match solver.newton(ratio, y[y.len() - 1]) {
+                Ok(solution) => solution.root(),
+                // Steps may stall short of the solver tolerance, but within the repricing tolerance.
+                Err(solver::Error::Stalled(solution))
+                    if solution.residual().abs() <= REPRICING_TOLERANCE =>
+                {
+                    solution.root()
+                }
+                Err(err) => {
+                    return Err(failure
+                        .take()
+                        .unwrap_or(Error::NoConvergence(maturity, err)))
+                }
+            };
+
+            if let Some(last) = y.last_mut() {
+                *last = df;
+            }
+            previous = maturity;
+        }
+
+        Ok(Term::new(&x, &y))
+    }
+
+    fn time(&self, date: &NaiveDate) -> Result<f64> {
+        Ok(self
+            .day_count
+            .try_year_fraction(&self.reference_date, date)?)
+    }
+}
+
+//  --- Unit tests
+#[cfg(test)]
+mod test_bootstrap {
+
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::conventions::day_count::DayCount;
+    use crate::test_utils::date;
+
+    fn instruments() -> Vec<Instruments> {
+        let reference = date(2024, 1, 15);
+        vec![
+            Instruments::Deposit(Deposit::new(reference, date(2024, 4, 15), 0.0825)),
+            Instruments::Fra(Fra::new(date(2024, 4, 15), date(2024, 7, 15), 0.0831)),
+            Instruments::Future(
+                Future::new(date(2024, 7, 15), date(2024, 10, 15), 91.62)
+                    .convexity_adjustment(0.0001),
+            ),
+            Instruments::Swap(Swap::new(
+                reference,
+                date(2026, 1, 15),
+                0.0842,
+                IntervalPeriod::Months(3),
+            )),
+            Instruments::Swap(Swap::new(
+                reference,
+                date(2029, 1, 15),
+                0.0875,
+                IntervalPeriod::Months(3),
+            )),
+            Instruments::Swap(Swap::new(
+                reference,
+                date(2034, 1, 15),
+                0.0921,
+                IntervalPeriod::Months(3),
+            )),
+        ]
+    }
+
+    #[test]
+    fn test_reprices_instruments() {
+        let reference = date(2024, 1, 15);
+        let bootstrapper = CurveBootstrapper::new(reference);
+        let instruments = instruments();
+        let (x, y) = bootstrapper.bootstrap(&instruments).unwrap().unpack();
+
+        assert_eq!(x.len(), instruments.len() + 1);
+        assert!(y.windows(2).all(|a| a[1] < a[0]));
+
+        let discount = |date: &NaiveDate| {
+            InterpolationMethod::default()
+                .try_interpolate(&x, &y, &bootstrapper.time(date).unwrap())
+                .unwrap()
+        };
+        for instrument in instruments {
+            let repricing = instrument.repricing(&Calendar::default()).unwrap();
+            assert_approx_eq!(repricing.ratio(discount), 1.0, 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_deposit_discount_factor() {
+        let reference = date(2024, 1, 15);
+        let end = date(2025, 1, 15);
+        let instruments = vec![Instruments::Deposit(Deposit::new(reference, end, 0.08))];
+        let curve = CurveBootstrapper::new(reference)
+            .bootstrap(&instruments)
+            .unwrap();
+
+        let t = DayCountConventions::default().year_fraction(&reference, &end);
+        assert_approx_eq!(curve.y()[1], 1.0 / (1.0 + 0.08 * t), 1e-12);
+    }
+
+    #[test]
+    fn test_flat_curve() {
+        // Quotes implied by a continuously compounded rate of 7%, reprice to the same curve.
+        let reference = date(2024, 1, 15);
+        let dcc = DayCountConventions::default();
+        let df = |d: &NaiveDate| (-0.07 * dcc.year_fraction(&reference, d)).exp();
+
+        let end = date(2024, 7, 15);
+        let deposit_rate = (df(&reference) / df(&end) - 1.0) / dcc.year_fraction(&reference, &end);
+
+        let swap_end = date(2027, 1, 15);
+        let schedule = ScheduleBuilder::new(reference, swap_end, IntervalPeriod::Months(6))
+            .build(&Calendar::default())
+            .unwrap();
+        let annuity: f64 = schedule
+            .payment_dates()
+            .iter()
+            .zip(schedule.year_fractions(&dcc))
+            .map(|(d, t)| t * df(d))
+            .sum();
+        let last = schedule.payment_dates()[schedule.len() - 1];
+        let swap_rate = (1.0 - df(&last)) / annuity;
+
+        let instruments = vec![
+            Instruments::Deposit(Deposit::new(reference, end, deposit_rate)),
+            Instruments::Swap(Swap::new(
+                reference,
+                swap_end,
+                swap_rate,
+                IntervalPeriod::Months(6),
+            )),
+        ];
+        let curve = CurveBootstrapper::new(reference)
+            .bootstrap(&instruments)
+            .unwrap();
+
+        assert_approx_eq!(curve.y()[1], df(&end), 1e-12);
+        assert_approx_eq!(curve.y()[2], df(&last), 1e-12);
+    }
+
+    #[test]
+    fn test_invalid_instruments() {
+        let reference = date(2024, 1, 15);
+        let bootstrapper = CurveBootstrapper::new(reference);
+        assert!(matches!(
+            bootstrapper.bootstrap(&[]),
+            Err(Error::NoInstruments)
+        ));
+
+        let mut instruments = instruments();
+        instruments.swap(0, 1);
+        assert!(matches!(
+            bootstrapper.bootstrap(&instruments),
+            Err(Error::UnorderedMaturities(..))
+        ));
+
+        let instruments = vec![Instruments::Deposit(Deposit::new(
+            date(2024, 1, 1),
+            date(2024, 4, 1),
+            0.08,
+        ))];
+        assert!(matches!(
+            bootstrapper.bootstrap(&instruments),
+            Err(Error::InstrumentBeforeReferenceDate(..))
+        ));
+
+        let zero_tenor = DayCountConventions::Actual365L(IntervalPeriod::Months(0));
+        let instruments = vec![Instruments::Deposit(
+            Deposit::new(reference, date(2024, 4, 15), 0.08).day_count(zero_tenor),
+        )];
+        assert!(matches!(
+            bootstrapper.bootstrap(&instruments),
+            Err(Error::DayCountError(_))
+        ));
+        assert!(matches!(
+            bootstrapper
+                .clone()
+                .day_count(zero_tenor)
+                .bootstrap(&self::instruments()),
+            Err(Error::DayCountError(_))
+        ));
+
+        let bootstrapper = bootstrapper.interpolation_method(InterpolationMethod::PiecewiseLinear);
+        assert!(matches!(
+            bootstrapper.bootstrap(&self::instruments()),
+            Err(Error::InterpolationError(..))
+        ));
+    }
+}
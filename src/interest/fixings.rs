@@ -0,0 +1,206 @@
+//! Historical fixings.
+//!
+//! A store of the rates published for each index, keyed by the index name and fixing
+//! date. The rate of a period is looked up on its fixing date, a number of business
+//! days before the period starts in the calendar of the index. Rates that fixed before
+//! the valuation date must be published, while a rate fixing on the valuation date is
+//! either the published rate or projected, per [`ValuationDateFixings`].
+//!
+//! ```rust,ignore
+//! let fixings = Fixings::new().load("JIBAR3M", "jibar")?;
+//! let (fixing_date, rate) = fixings.fixing_for("JIBAR3M", &start, 0, &calendar)?;
+//! ```
+
+use std::collections::BTreeMap;
+
+use chrono::NaiveDate;
+
+use crate::resources::market_data::{self, load_spot};
+use crate::time::calendar::Calendar;
+
+//  --- Errors
+type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+    UnknownIndex(String),
+    MissingFixing(String, NaiveDate),
+    InvalidFixingDate(NaiveDate),
+    MarketDataError(market_data::Error),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownIndex(index) => write!(f, "No fixings are held for index '{}'.", index),
+            Self::MissingFixing(index, date) => {
+                write!(f, "No '{}' fixing is published for {}.", index, date)
+            }
+            Self::InvalidFixingDate(date) => {
+                write!(f, "No fixing date precedes the period starting {}.", date)
+            }
+            Self::MarketDataError(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<market_data::Error> for Error {
+    fn from(value: market_data::Error) -> Self {
+        Self::MarketDataError(value)
+    }
+}
+
+//  --- Enums
+
+/// Rate of a period fixing on the valuation date.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ValuationDateFixings {
+    /// The published rate if available, else the projected rate.
+    #[default]
+    PublishedIfAvailable,
+    /// The published rate, which is required.
+    Published,
+    /// The projected rate, ignoring any published rate.
+    Projected,
+}
+
+//  --- Structs
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Fixings(BTreeMap<String, BTreeMap<NaiveDate, f64>>);
+
+//  --- Implementations
+
+impl Fixings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the rates of 'index', keyed by fixing date, replacing those already held
+    /// on the same dates.
+    pub fn series(mut self, index: &str, rates: &BTreeMap<NaiveDate, f64>) -> Self {
+        self.0
+            .entry(index.to_string())
+            .or_default()
+            .extend(rates.iter().map(|(d, r)| (*d, *r)));
+        self
+    }
+
+    /// Adds the rates of 'index' from the spot market data named 'name'.
+    pub fn load(self, index: &str, name: &str) -> Result<Self> {
+        let rates: BTreeMap<NaiveDate, f64> = load_spot(name)?;
+        Ok(self.series(index, &rates))
+    }
+
+    pub fn insert(&mut self, index: &str, date: NaiveDate, rate: f64) -> &mut Self {
+        self.0
+            .entry(index.to_string())
+            .or_default()
+            .insert(date, rate);
+        self
+    }
+
+    pub fn indices(&self) -> Vec<&str> {
+        self.0.keys().map(|k| k.as_str()).collect()
+    }
+
+    /// Rates of 'index', keyed by fixing date.
+    pub fn rates(&self, index: &str) -> Option<&BTreeMap<NaiveDate, f64>> {
+        self.0.get(index)
+    }
+
+    /// Rate of 'index' published for 'date', if any.
+    pub fn get(&self, index: &str, date: &NaiveDate) -> Option<f64> {
+        self.0.get(index)?.get(date).copied()
+    }
+
+    /// Rate of 'index' published for 'date'.
+    pub fn fixing(&self, index: &str, date: &NaiveDate) -> Result<f64> {
+        self.0
+            .get(index)
+            .ok_or_else(|| Error::UnknownIndex(index.to_string()))?
+            .get(date)
+            .copied()
+            .ok_or_else(|| Error::MissingFixing(index.to_string(), *date))
+    }
+
+    /// Fixing date and published rate of 'index', for the period starting 'start'.
+    pub fn fixing_for(
+        &self,
+        index: &str,
+        start: &NaiveDate,
+        fixing_lag: u32,
+        calendar: &Calendar,
+    ) -> Result<(NaiveDate, f64)> {
+        let date = fixing_date(start, fixing_lag, calendar)?;
+        Ok((date, self.fixing(index, &date)?))
+    }
+}
+
+//  --- Trait implementations: Concrete
+
+impl From<BTreeMap<String, BTreeMap<NaiveDate, f64>>> for Fixings {
+    fn from(value: BTreeMap<String, BTreeMap<NaiveDate, f64>>) -> Self {
+        Self(value)
+    }
+}
+
+//  --- Standalone functions
+
+/// Date the rate of the period starting 'start' fixes on, 'fixing_lag' business days
+/// earlier in 'calendar'.
+pub fn fixing_date(start: &NaiveDate, fixing_lag: u32, calendar: &Calendar) -> Result<NaiveDate> {
+    calendar
+        .add_business_days(start, -(fixing_lag as i64))
+        .ok_or(Error::InvalidFixingDate(*start))
+}
+
+//  --- Unit tests
+#[cfg(test)]
+mod test_fixings {
+
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::test_utils::date;
+
+    #[test]
+    fn test_fixings() {
+        let mut fixings = Fixings::new().series(
+            "JIBAR3M",
+            &BTreeMap::from([(date(2024, 1, 12), 0.0835), (date(2024, 1, 15), 0.084)]),
+        );
+        fixings.insert("ZARONIA", date(2024, 1, 15), 0.0825);
+        assert_eq!(fixings.indices(), vec!["JIBAR3M", "ZARONIA"]);
+        assert_eq!(fixings.get("JIBAR3M", &date(2024, 1, 15)), Some(0.084));
+        assert_eq!(fixings.get("JIBAR3M", &date(2024, 1, 16)), None);
+
+        // A period starting Monday fixes the Friday before, with a one day lag.
+        let calendar = Calendar::default();
+        let (fixing_date, rate) = fixings
+            .fixing_for("JIBAR3M", &date(2024, 1, 15), 1, &calendar)
+            .unwrap();
+        assert_eq!(fixing_date, date(2024, 1, 12));
+        assert_approx_eq!(rate, 0.0835, 1e-12);
+
+        assert!(matches!(
+            fixings.fixing("JIBAR3M", &date(2024, 1, 16)),
+            Err(Error::MissingFixing(index, d)) if index == "JIBAR3M" && d == date(2024, 1, 16)
+        ));
+        assert!(matches!(
+            fixings.fixing("SOFR", &date(2024, 1, 15)),
+            Err(Error::UnknownIndex(_))
+        ));
+    }
+
+    #[test]
+    fn test_load() {
+        let fixings = Fixings::new().load("JIBAR3M", "jibar").unwrap();
+        assert!(fixings.get("JIBAR3M", &date(2022, 12, 30)).is_some());
+        assert!(matches!(
+            Fixings::new().load("JIBAR3M", "missing"),
+            Err(Error::MarketDataError(_))
+        ));
+    }
+}
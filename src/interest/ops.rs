@@ -28,7 +28,7 @@ pub enum Error {
 //  --- Enums
 
 /// Interest calculation conventions.
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub enum InterestConventions {
     /// Simple interest calculation convention.
     Simple,
@@ -40,7 +40,7 @@ pub enum InterestConventions {
     Continuous,
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, Default)]
 pub enum DiscreteCompoundingFrequencies {
     Weekly,
     Monthly,
@@ -48,6 +48,7 @@ pub enum DiscreteCompoundingFrequencies {
     Quarterly,
     TriAnnually,
     SemiAnnually,
+    #[default]
     Annually,
 }
 
@@ -57,12 +58,6 @@ impl Default for InterestConventions {
     }
 }
 
-impl Default for DiscreteCompoundingFrequencies {
-    fn default() -> Self {
-        Self::Annually
-    }
-}
-
 //  --- Structs
 
 struct Simple;
@@ -0,0 +1,214 @@
+//! Overnight rates compounded in arrears.
+//!
+//! Risk free rates (SOFR, SONIA, €STR, ZARONIA) fix daily, and accrue over an interest
+//! period as the daily compounded rate
+//!
+//! R = (Π(1 + ri.τi) - 1) / Σ τi,
+//!
+//! where ri is the rate observed for business day i, and τi the year fraction it is
+//! applied over. As the rate is only known at the end of the period, the conventions
+//! below move the observations earlier, leaving time to settle:
+//! - Lookback: each day observes the rate 'p' business days earlier.
+//! - Observation shift: the lookback also shifts the weights, such that τi spans the
+//!   observation period rather than the interest period.
+//! - Lockout: the rate observed on the cut-off date, 'k' business days before the end
+//!   of the period, applies to the remaining days.
+//!
+//! A payment delay, paying 'n' business days after the period ends, is set as the
+//! payment lag of the schedule.
+//!
+//! ```rust,ignore
+//! let compounding = OvernightCompounding::new().lookback(5).observation_shift(true);
+//! let observations = compounding.observations(&start, &end, &calendar, &dcc)?;
+//! ```
+
+use chrono::NaiveDate;
+
+use crate::conventions::day_count::{DayCount, DayCountConventions};
+use crate::time::calendar::Calendar;
+
+//  --- Structs
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct OvernightCompounding {
+    lookback: u32,
+    observation_shift: bool,
+    lockout: u32,
+}
+
+/// Rate observed for a single business day of an interest period.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Observation {
+    /// Date the rate is published for.
+    pub fixing_date: NaiveDate,
+    /// Business day following the fixing date, over which the rate is projected.
+    pub fixing_end: NaiveDate,
+    /// Year fraction the rate is applied over.
+    pub weight: f64,
+}
+
+//  --- Implementations
+
+impl OvernightCompounding {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of business days each day observes the rate before.
+    pub fn lookback(self, lookback: u32) -> Self {
+        Self { lookback, ..self }
+    }
+
+    /// Weight rates by the observation period, rather than the interest period.
+    pub fn observation_shift(self, observation_shift: bool) -> Self {
+        Self {
+            observation_shift,
+            ..self
+        }
+    }
+
+    /// Number of business days before the end of the period the rate is cut off. A
+    /// lockout spanning the period applies the first observation to every day.
+    pub fn lockout(self, lockout: u32) -> Self {
+        Self { lockout, ..self }
+    }
+
+    /// Daily observations over the interest period ['start', 'end'), counting business
+    /// days in 'calendar'. None if a date falls outside the supported range.
+    pub fn observations(
+        &self,
+        start: &NaiveDate,
+        end: &NaiveDate,
+        calendar: &Calendar,
+        day_count: &DayCountConventions,
+    ) -> Option<Vec<Observation>> {
+        let mut days = vec![*start];
+        loop {
+            let next = calendar.add_business_days(days.last()?, 1)?;
+            if next >= *end {
+                break;
+            }
+            days.push(next);
+        }
+        days.push(*end);
+
+        let lookback = -(self.lookback as i64);
+        let observed = days
+            .iter()
+            .map(|day| calendar.add_business_days(day, lookback))
+            .collect::<Option<Vec<NaiveDate>>>()?;
+
+        let n = days.len() - 1;
+        let mut observations = (0..n)
+            .map(|i| {
+                let fixing_date = observed[i];
+                let weight = if self.observation_shift {
+                    day_count.year_fraction(&observed[i], &observed[i + 1])
+                } else {
+                    day_count.year_fraction(&days[i], &days[i + 1])
+                };
+                Some(Observation {
+                    fixing_date,
+                    fixing_end: calendar.add_business_days(&fixing_date, 1)?,
+                    weight,
+                })
+            })
+            .collect::<Option<Vec<Observation>>>()?;
+
+        // Days from the cut-off date onwards observe the rate of the cut-off date.
+        let lockout = (self.lockout as usize).min(n);
+        if lockout > 0 {
+            let cut_off = observations[n - lockout];
+            for observation in observations[n - lockout..].iter_mut() {
+                observation.fixing_date = cut_off.fixing_date;
+                observation.fixing_end = cut_off.fixing_end;
+            }
+        }
+        Some(observations)
+    }
+}
+
+//  --- Standalone functions
+
+/// Daily compounded rate, (Π(1 + ri.τi) - 1) / Σ τi, of 'rates' applied over 'weights'.
+pub fn compounded_rate(rates: &[f64], weights: &[f64]) -> f64 {
+    let growth = rates
+        .iter()
+        .zip(weights.iter())
+        .fold(1.0, |acc, (r, w)| acc * (1.0 + r * w));
+    (growth - 1.0) / weights.iter().sum::<f64>()
+}
+
+//  --- Unit tests
+#[cfg(test)]
+mod test_overnight {
+
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::test_utils::date;
+
+    #[test]
+    fn test_observations() {
+        // Monday 2024-01-01 to Monday 2024-01-15, with 2024-01-09 a holiday.
+        let calendar = Calendar::new(&[date(2024, 1, 9)]);
+        let dcc = DayCountConventions::Actual360;
+        let (start, end) = (date(2024, 1, 1), date(2024, 1, 15));
+
+        let observations = OvernightCompounding::new()
+            .observations(&start, &end, &calendar, &dcc)
+            .unwrap();
+        assert_eq!(observations.len(), 9);
+        assert_eq!(observations[0].fixing_date, start);
+        // Friday accrues over the weekend, Monday over the holiday.
+        assert_approx_eq!(observations[4].weight, 3.0 / 360.0, 1e-12);
+        assert_approx_eq!(observations[5].weight, 2.0 / 360.0, 1e-12);
+        let total: f64 = observations.iter().map(|o| o.weight).sum();
+        assert_approx_eq!(total, 14.0 / 360.0, 1e-12);
+
+        // A two day lookback observes earlier rates over the same weights.
+        let lookback = OvernightCompounding::new()
+            .lookback(2)
+            .observations(&start, &end, &calendar, &dcc)
+            .unwrap();
+        assert_eq!(lookback[0].fixing_date, date(2023, 12, 28));
+        assert_eq!(lookback[0].fixing_end, date(2023, 12, 29));
+        assert_approx_eq!(lookback[4].weight, 3.0 / 360.0, 1e-12);
+
+        // With an observation shift, the weights span the observation period.
+        let shift = OvernightCompounding::new()
+            .lookback(2)
+            .observation_shift(true)
+            .observations(&start, &end, &calendar, &dcc)
+            .unwrap();
+        assert_approx_eq!(shift[1].weight, 3.0 / 360.0, 1e-12);
+        let total: f64 = shift.iter().map(|o| o.weight).sum();
+        assert_approx_eq!(total, 14.0 / 360.0, 1e-12);
+
+        // Lockout repeats the rate of the cut-off date.
+        let lockout = OvernightCompounding::new()
+            .lockout(2)
+            .observations(&start, &end, &calendar, &dcc)
+            .unwrap();
+        assert_eq!(lockout[7].fixing_date, date(2024, 1, 11));
+        assert_eq!(lockout[8].fixing_date, date(2024, 1, 11));
+        assert_eq!(lockout[6].fixing_date, date(2024, 1, 10));
+
+        // A lockout spanning the period observes the first rate on every day.
+        for k in [9, 20] {
+            let lockout = OvernightCompounding::new()
+                .lockout(k)
+                .observations(&start, &end, &calendar, &dcc)
+                .unwrap();
+            assert!(lockout.iter().all(|o| o.fixing_date == start));
+            assert!(lockout.iter().all(|o| o.fixing_end == date(2024, 1, 2)));
+        }
+    }
+
+    #[test]
+    fn test_compounded_rate() {
+        let rate = compounded_rate(&[0.05, 0.05], &[1.0 / 360.0, 3.0 / 360.0]);
+        let expected = ((1.0 + 0.05 / 360.0) * (1.0 + 0.15 / 360.0) - 1.0) * 90.0;
+        assert_approx_eq!(rate, expected, 1e-14);
+        assert_approx_eq!(compounded_rate(&[0.05], &[0.5]), 0.05, 1e-14);
+    }
+}
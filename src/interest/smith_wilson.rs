@@ -0,0 +1,377 @@
+//! Smith-Wilson curves.
+//!
+//! Builds a discount function through liquid market discount factors, up to the last
+//! liquid point (LLP), which is extrapolated towards an ultimate forward rate (UFR).
+//! The convergence parameter alpha sets how quickly forwards beyond the LLP approach
+//! the UFR. If not given, alpha is calibrated as the smallest value, no less than
+//! MIN_ALPHA, for which the forward at the convergence point lies within the tolerance
+//! of the UFR. By default, the convergence point is the later of LLP + 40 and 60 years,
+//! and the tolerance is one basis point.
+//!
+//! The UFR is quoted with annual compounding, such that the forward intensity
+//! converged to is ln(1 + UFR).
+//!
+//! ```rust,ignore
+//! let curve = SmithWilsonBuilder::new(0.0345)
+//!     .last_liquid_point(20.0)
+//!     .build(&market_discount_factors)?;
+//!
+//! let df = curve.discount(&100.0);
+//! ```
+
+use super::term_structure::{Term, TermStructure};
+use crate::math::interpolation::smith_wilson::SmithWilson;
+use crate::math::interpolation::{self, Interpolate, Interpolator};
+
+//  --- Constants
+
+/// Smallest alpha considered during calibration.
+const MIN_ALPHA: f64 = 0.05;
+
+/// Largest alpha considered during calibration.
+const MAX_ALPHA: f64 = 1.0;
+
+/// Precision to which alpha is calibrated.
+const ALPHA_PRECISION: f64 = 1e-6;
+
+//  --- Errors
+type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+    NoLiquidPoints,
+    InvalidAlpha(f64),
+    ConvergencePointBeforeLastLiquidPoint(f64, f64),
+    CalibrationFailed(f64),
+    InterpolationError(interpolation::Error),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoLiquidPoints => write!(
+                f,
+                "At least one discount factor after time zero, up to the last liquid point, is required."
+            ),
+            Self::InvalidAlpha(alpha) => write!(f, "Alpha should be positive, {} was given.", alpha),
+            Self::ConvergencePointBeforeLastLiquidPoint(convergence, llp) => write!(
+                f,
+                "Convergence point {} should follow the last liquid point {}.",
+                convergence, llp
+            ),
+            Self::CalibrationFailed(gap) => write!(
+                f,
+                "No alpha up to {} converges to the UFR, the forward differs by {}.",
+                MAX_ALPHA, gap
+            ),
+            Self::InterpolationError(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<interpolation::Error> for Error {
+    fn from(value: interpolation::Error) -> Self {
+        Self::InterpolationError(value)
+    }
+}
+
+//  --- Structs
+
+#[derive(Clone, Debug)]
+pub struct SmithWilsonBuilder {
+    ufr: f64,
+    alpha: Option<f64>,
+    last_liquid_point: Option<f64>,
+    convergence_point: Option<f64>,
+    tolerance: f64,
+}
+
+#[derive(Clone, Debug)]
+pub struct SmithWilsonCurve {
+    last_liquid_point: f64,
+    discount_function: SmithWilson,
+}
+
+//  --- Implementations
+
+impl SmithWilsonBuilder {
+    /// 'ufr' is the annually compounded ultimate forward rate.
+    pub fn new(ufr: f64) -> Self {
+        Self {
+            ufr,
+            alpha: None,
+            last_liquid_point: None,
+            convergence_point: None,
+            tolerance: 1e-4,
+        }
+    }
+
+    /// Fixed convergence parameter, in place of calibration.
+    pub fn alpha(self, alpha: f64) -> Self {
+        Self {
+            alpha: Some(alpha),
+            ..self
+        }
+    }
+
+    /// Time of the last liquid point. Market points after it are ignored. Defaults
+    /// to the last market point.
+    pub fn last_liquid_point(self, last_liquid_point: f64) -> Self {
+        Self {
+            last_liquid_point: Some(last_liquid_point),
+            ..self
+        }
+    }
+
+    /// Time at which the forward should lie within the tolerance of the UFR.
+    pub fn convergence_point(self, convergence_point: f64) -> Self {
+        Self {
+            convergence_point: Some(convergence_point),
+            ..self
+        }
+    }
+
+    /// Largest acceptable difference between the forward at the convergence point and the UFR.
+    pub fn tolerance(self, tolerance: f64) -> Self {
+        Self { tolerance, ..self }
+    }
+
+    /// Continuously compounded ultimate forward rate.
+    fn ultimate_intensity(&self) -> f64 {
+        self.ufr.ln_1p()
+    }
+
+    /// Builds the curve through 'market' discount factors, keyed by time in years.
+    pub fn build(&self, market: &Term<f64>) -> Result<SmithWilsonCurve> {
+        let (x, y) = market.unpack();
+        self.fit(&x, &y)
+    }
+
+    fn fit(&self, x: &[f64], discount_factors: &[f64]) -> Result<SmithWilsonCurve> {
+        let last_market_point = x.last().copied().unwrap_or(0.0);
+        let last_liquid_point = self.last_liquid_point.unwrap_or(last_market_point);
+        let (x, y): (Vec<f64>, Vec<f64>) = x
+            .iter()
+            .zip(discount_factors.iter())
+            .filter(|(t, _)| **t > 0.0 && **t <= last_liquid_point)
+            .unzip();
+        if x.is_empty() {
+            return Err(Error::NoLiquidPoints);
+        }
+
+        let fit = |alpha: f64| -> Result<SmithWilson> {
+            Ok(SmithWilson::new(&x, &y, self.ultimate_intensity(), alpha)?)
+        };
+
+        let discount_function = match self.alpha {
+            Some(alpha) if alpha <= 0.0 => return Err(Error::InvalidAlpha(alpha)),
+            Some(alpha) => fit(alpha)?,
+            None => self.calibrate(last_liquid_point, fit)?,
+        };
+
+        Ok(SmithWilsonCurve {
+            last_liquid_point,
+            discount_function,
+        })
+    }
+
+    /// Smallest alpha for which the forward at the convergence point lies within the
+    /// tolerance of the UFR, found by bisection.
+    fn calibrate<F>(&self, last_liquid_point: f64, fit: F) -> Result<SmithWilson>
+    where
+        F: Fn(f64) -> Result<SmithWilson>,
+    {
+        let convergence_point = self
+            .convergence_point
+            .unwrap_or((last_liquid_point + 40.0).max(60.0));
+        if convergence_point <= last_liquid_point {
+            return Err(Error::ConvergencePointBeforeLastLiquidPoint(
+                convergence_point,
+                last_liquid_point,
+            ));
+        }
+
+        let ufr_intensity = self.ultimate_intensity();
+        let gap = |curve: &SmithWilson| (curve.forward(&convergence_point) - ufr_intensity).abs();
+
+        let lower = fit(MIN_ALPHA)?;
+        if gap(&lower) <= self.tolerance {
+            return Ok(lower);
+        }
+        let mut upper = fit(MAX_ALPHA)?;
+        if gap(&upper) > self.tolerance {
+            return Err(Error::CalibrationFailed(gap(&upper)));
+        }
+
+        let (mut low, mut high) = (MIN_ALPHA, MAX_ALPHA);
+        while high - low > ALPHA_PRECISION {
+            let mid = 0.5 * (low + high);
+            let curve = fit(mid)?;
+            if gap(&curve) <= self.tolerance {
+                high = mid;
+                upper = curve;
+            } else {
+                low = mid;
+            }
+        }
+        Ok(upper)
+    }
+}
+
+impl SmithWilsonCurve {
+    pub fn alpha(&self) -> f64 {
+        self.discount_function.alpha()
+    }
+
+    pub fn last_liquid_point(&self) -> f64 {
+        self.last_liquid_point
+    }
+
+    /// Discount factor at time 't'.
+    pub fn discount(&self, t: &f64) -> f64 {
+        self.discount_function.value(t)
+    }
+
+    /// Continuously compounded zero rate at time 't'.
+    pub fn zero_rate(&self, t: &f64) -> f64 {
+        -self.discount(t).ln() / t
+    }
+
+    /// Continuously compounded instantaneous forward rate at time 't'.
+    pub fn forward(&self, t: &f64) -> f64 {
+        self.discount_function.forward(t)
+    }
+
+    /// Discount factors at each of 'times'.
+    pub fn discount_factors(&self, times: &[f64]) -> Term<f64> {
+        let y: Vec<f64> = times.iter().map(|t| self.discount(t)).collect();
+        Term::new(times, &y)
+    }
+}
+
+//  --- Trait implementations: Concrete
+
+impl Interpolator for SmithWilsonCurve {
+    fn value(&self, xp: &f64) -> f64 {
+        self.discount_function.value(xp)
+    }
+
+    fn derivative(&self, xp: &f64) -> f64 {
+        self.discount_function.derivative(xp)
+    }
+}
+
+/// Fits the curve to discount factors 'y' at times 'x' on each call, returning NaN
+/// on failure. Use 'build' to evaluate many points.
+impl Interpolate<Vec<f64>, f64> for SmithWilsonBuilder {
+    fn interpolate(&self, x: &Vec<f64>, y: &Vec<f64>, xp: &f64) -> f64 {
+        self.fit(x, y)
+            .map(|curve| curve.discount(xp))
+            .unwrap_or(f64::NAN)
+    }
+}
+
+//  --- Unit tests
+#[cfg(test)]
+mod test_smith_wilson {
+
+    use super::*;
+    use crate::assert_approx_eq;
+
+    const UFR: f64 = 0.0345;
+
+    fn market() -> Term<f64> {
+        let x = vec![1.0, 2.0, 3.0, 5.0, 7.0, 10.0, 15.0, 20.0, 30.0];
+        let y: Vec<f64> = x
+            .iter()
+            .map(|t: &f64| (1.0 + 0.025 + 0.0008 * t.min(20.0)).powf(-t))
+            .collect();
+        Term::new(&x, &y)
+    }
+
+    #[test]
+    fn test_reproduces_liquid_points() {
+        let market = market();
+        let curve = SmithWilsonBuilder::new(UFR)
+            .last_liquid_point(20.0)
+            .build(&market)
+            .unwrap();
+
+        assert_approx_eq!(curve.discount(&0.0), 1.0, 1e-15);
+        for (t, df) in market.x().iter().zip(market.y().iter()) {
+            if *t <= 20.0 {
+                assert_approx_eq!(curve.discount(t), df, 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_alpha_calibration() {
+        let builder = SmithWilsonBuilder::new(UFR).last_liquid_point(20.0);
+        let curve = builder.build(&market()).unwrap();
+        let alpha = curve.alpha();
+
+        // Converged within one basis point at 60 years, and a smaller alpha does not.
+        assert!((MIN_ALPHA..=MAX_ALPHA).contains(&alpha));
+        assert!((curve.forward(&60.0) - UFR.ln_1p()).abs() <= 1e-4);
+        if alpha > MIN_ALPHA + 1e-3 {
+            let slower = builder
+                .clone()
+                .alpha(alpha - 1e-3)
+                .build(&market())
+                .unwrap();
+            assert!((slower.forward(&60.0) - UFR.ln_1p()).abs() > 1e-4);
+        }
+
+        // Long dated liabilities.
+        assert_approx_eq!(curve.forward(&150.0), UFR.ln_1p(), 1e-5);
+        assert!(curve.discount(&120.0) > 0.0);
+    }
+
+    #[test]
+    fn test_fixed_alpha() {
+        let curve = SmithWilsonBuilder::new(UFR)
+            .alpha(0.1)
+            .build(&market())
+            .unwrap();
+        assert_approx_eq!(curve.alpha(), 0.1);
+        assert_approx_eq!(curve.last_liquid_point(), 30.0);
+        assert_approx_eq!(curve.zero_rate(&30.0), -market().y()[8].ln() / 30.0, 1e-12);
+    }
+
+    #[test]
+    fn test_interpolate() {
+        let (x, y) = market().unpack();
+        let builder = SmithWilsonBuilder::new(UFR).last_liquid_point(20.0);
+        let curve = builder.build(&market()).unwrap();
+
+        let xp = vec![4.0, 25.0, 100.0];
+        let interpolated: Vec<f64> = builder.interpolate(&x, &y, &xp);
+        for (t, df) in xp.iter().zip(interpolated.iter()) {
+            assert_approx_eq!(curve.discount(t), df, 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        let market = market();
+        assert!(matches!(
+            SmithWilsonBuilder::new(UFR)
+                .last_liquid_point(0.5)
+                .build(&market),
+            Err(Error::NoLiquidPoints)
+        ));
+        assert!(matches!(
+            SmithWilsonBuilder::new(UFR).alpha(-0.1).build(&market),
+            Err(Error::InvalidAlpha(_))
+        ));
+        assert!(matches!(
+            SmithWilsonBuilder::new(UFR)
+                .convergence_point(10.0)
+                .build(&market),
+            Err(Error::ConvergencePointBeforeLastLiquidPoint(_, _))
+        ));
+    }
+}
@@ -0,0 +1,1384 @@
+//! Interest rate swaps.
+//!
+//! A swap exchanges the coupons of two legs. Each leg accrues on its own schedule, at
+//! either a fixed rate or a floating rate plus a spread, paying N.(r + s).τ on each
+//! payment date. The direction of a leg sets the sign of its cash flows, such that the
+//! NPV of the swap is the sum of the leg PVs.
+//!
+//! Floating rates fix a number of business days before each accrual period starts.
+//! Rates that fixed before the valuation date are taken from the historical fixings of
+//! the index of the leg, rates fixing on the valuation date are published or projected
+//! per [`ValuationDateFixings`], and the others are projected off the forward curve as the simple forward rate
+//! over the accrual period, F = (P(start) / P(end) - 1) / τ. Only cash flows paid
+//! after the valuation date are valued, discounted to the valuation date.
+//!
+//! A floating leg may reset more often than it pays, in which case the rates fixed
+//! over the sub-periods of each payment period are compounded or averaged into a
+//! single coupon, following the ISDA conventions:
+//! - Straight compounding, spread included: Π(1 + (ri + s).τi) - 1.
+//! - Compounding with the spread as simple interest: Π(1 + ri.τi) - 1 + s.τ.
+//! - Flat compounding: each sub-period accrues (ri + s).τi on the notional, plus ri.τi
+//!   on the interest accrued over the previous sub-periods.
+//! - Simple averaging: (Σ ri.τi / Σ τi + s).τ.
+//!
+//! Overnight index legs compound the daily fixings of a risk free rate in arrears over
+//! each period, per [`OvernightCompounding`], and pay the compounded rate plus spread.
+//!
+//! The notional of a leg is either constant, given per period, or amortises or accretes
+//! from an initial notional. Principal exchanges, where configured, are reported
+//! alongside the coupons and included in the PV of the leg.
+//!
+//! The par rate is the fixed rate, or the spread of a floating leg, on the quoted leg
+//! that sets the NPV to zero. The quoted leg is the first fixed leg, or the first leg
+//! if both float, and the annuity Σ N.τ.P(payment) is that of the quoted leg. The NPV
+//! is linear in the quote, and the par rate follows from the annuity, unless the spread
+//! compounds over resets, in which case the par rate is solved by repricing the leg.
+//!
+//! ```rust,ignore
+//! let schedule = ScheduleBuilder::new(effective, termination, IntervalPeriod::Months(3));
+//! let swap = InterestRateSwap::new(
+//!     SwapLeg::fixed(Direction::Receive, notional, schedule.clone(), 0.085),
+//!     SwapLeg::floating(Direction::Pay, notional, schedule)
+//!         .index("JIBAR3M")
+//!         .fixing_calendar(calendar),
+//! );
+//! let fixings = Fixings::new().load("JIBAR3M", "jibar")?;
+//!
+//! let valuation = SwapPricer::new(valuation_date, discount_curve)
+//!     .forward_curve(forward_curve)
+//!     .fixings(&fixings)
+//!     .price(&swap)?;
+//! println!("{}", valuation.legs()[1]);
+//! ```
+
+use std::fmt;
+
+use chrono::NaiveDate;
+
+use super::fixings::{self, Fixings, ValuationDateFixings};
+use super::overnight::{compounded_rate, OvernightCompounding};
+use super::yield_curve::YieldCurve;
+use crate::conventions::day_count::{self, DayCountConventions};
+use crate::math::interpolation;
+use crate::math::solver::{self, RootFinder};
+use crate::time::calendar::Calendar;
+use crate::time::periods::IntervalPeriod;
+use crate::time::schedule::{self, Schedule, ScheduleBuilder};
+
+//  --- Constants
+
+/// Residual, in NPV per unit of annuity, at which the par rate solver stops.
+const PAR_RATE_TOLERANCE: f64 = 1e-12;
+
+//  --- Errors
+type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+    MismatchedNotionals(usize, usize),
+    FixingError(fixings::Error),
+    ScheduleError(schedule::Error),
+    InterpolationError(interpolation::Error),
+    SolverError(solver::Error),
+    ObservationScheduleError(NaiveDate, NaiveDate),
+    UnsupportedResets(Coupons),
+    DayCountError(day_count::Error),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MismatchedNotionals(periods, given) => write!(
+                f,
+                "{} notionals are expected, one per period, {} were given.",
+                periods, given
+            ),
+            Self::FixingError(e) => write!(f, "{}", e),
+            Self::ScheduleError(e) => write!(f, "{}", e),
+            Self::InterpolationError(e) => write!(f, "{}", e),
+            Self::SolverError(e) => write!(f, "Failed to solve the par rate: {}", e),
+            Self::ObservationScheduleError(start, end) => write!(
+                f,
+                "The daily observations of the period from {} to {} fall outside the calendar range.",
+                start, end
+            ),
+            Self::UnsupportedResets(coupon) => {
+                write!(f, "Resets are only supported on floating legs, not {:?}.", coupon)
+            }
+            Self::DayCountError(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<fixings::Error> for Error {
+    fn from(value: fixings::Error) -> Self {
+        Self::FixingError(value)
+    }
+}
+
+impl From<schedule::Error> for Error {
+    fn from(value: schedule::Error) -> Self {
+        Self::ScheduleError(value)
+    }
+}
+
+impl From<interpolation::Error> for Error {
+    fn from(value: interpolation::Error) -> Self {
+        Self::InterpolationError(value)
+    }
+}
+
+impl From<solver::Error> for Error {
+    fn from(value: solver::Error) -> Self {
+        Self::SolverError(value)
+    }
+}
+
+impl From<day_count::Error> for Error {
+    fn from(value: day_count::Error) -> Self {
+        Self::DayCountError(value)
+    }
+}
+
+//  --- Enums
+
+/// Whether the coupons of a leg are received or paid.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Direction {
+    #[default]
+    Receive,
+    Pay,
+}
+
+/// Rate a leg accrues at, before its spread.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Coupons {
+    Fixed(f64),
+    Floating,
+    /// Overnight rate, compounded daily in arrears.
+    Overnight(OvernightCompounding),
+}
+
+/// Combination of the rates fixed over the sub-periods of a payment period.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum CompoundingMethods {
+    /// ISDA 'Compounding', with the spread compounded.
+    #[default]
+    Straight,
+    /// ISDA 'Compounding treating Spread as simple interest'.
+    SpreadExclusive,
+    /// ISDA 'Flat Compounding'.
+    Flat,
+    /// Average of the rates, weighted by the length of each sub-period.
+    Averaging,
+}
+
+/// Notional outstanding over each period of a leg.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Notionals {
+    Constant(f64),
+    /// Notional of each period, in order, one per period.
+    Explicit(Vec<f64>),
+    /// Amortises in equal amounts from the initial notional, to zero at termination.
+    Linear(f64),
+    /// Amortises such that interest at 'rate' plus principal is level in each period.
+    Annuity {
+        initial: f64,
+        rate: f64,
+    },
+    /// Accretes by capitalising interest at 'rate', N(i + 1) = N(i).(1 + rate.τ(i)).
+    Accreting {
+        initial: f64,
+        rate: f64,
+    },
+}
+
+/// Principal exchanged on a leg, in addition to its coupons.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum PrincipalExchanges {
+    #[default]
+    None,
+    /// Changes in the notional on each payment date, and the notional outstanding
+    /// at termination.
+    Final,
+    /// As 'Final', with the initial notional exchanged at the effective date.
+    InitialAndFinal,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum CashFlowTypes {
+    #[default]
+    Interest,
+    Principal,
+}
+
+//  --- Structs
+
+#[derive(Clone, Debug)]
+pub struct SwapLeg {
+    direction: Direction,
+    notional: Notionals,
+    principal_exchange: PrincipalExchanges,
+    schedule: ScheduleBuilder,
+    calendar: Calendar,
+    day_count: DayCountConventions,
+    coupon: Coupons,
+    spread: f64,
+    index: String,
+    fixing_calendar: Calendar,
+    fixing_lag: u32,
+    reset_frequency: Option<IntervalPeriod>,
+    compounding: CompoundingMethods,
+}
+
+#[derive(Clone, Debug)]
+pub struct InterestRateSwap {
+    legs: [SwapLeg; 2],
+}
+
+#[derive(Clone, Debug)]
+pub struct SwapPricer {
+    valuation_date: NaiveDate,
+    discount_curve: YieldCurve,
+    forward_curve: Option<YieldCurve>,
+    fixings: Fixings,
+    valuation_date_fixings: ValuationDateFixings,
+}
+
+/// Coupon of a single accrual period, or an exchange of principal on the payment date.
+/// Amounts and present values are signed by the direction of the leg.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CashFlow {
+    pub kind: CashFlowTypes,
+    pub accrual_start: NaiveDate,
+    pub accrual_end: NaiveDate,
+    pub payment_date: NaiveDate,
+    pub fixing_date: Option<NaiveDate>,
+    pub notional: f64,
+    pub year_fraction: f64,
+    pub rate: f64,
+    pub spread: f64,
+    pub amount: f64,
+    pub discount_factor: f64,
+    pub present_value: f64,
+}
+
+#[derive(Clone, Debug)]
+pub struct LegValuation {
+    direction: Direction,
+    coupon: Coupons,
+    spread: f64,
+    cash_flows: Vec<CashFlow>,
+    pv: f64,
+    annuity: f64,
+}
+
+#[derive(Clone, Debug)]
+pub struct SwapValuation {
+    legs: Vec<LegValuation>,
+    npv: f64,
+    par_rate: f64,
+    annuity: f64,
+}
+
+//  --- Implementations
+
+impl Direction {
+    /// Sign of the cash flows: positive if received, negative if paid.
+    pub fn sign(&self) -> f64 {
+        match self {
+            Self::Receive => 1.0,
+            Self::Pay => -1.0,
+        }
+    }
+}
+
+impl Notionals {
+    /// Notional over each period, given the year fraction of each period.
+    pub fn amounts(&self, year_fractions: &[f64]) -> Result<Vec<f64>> {
+        let n = year_fractions.len();
+        let amounts = match self {
+            Self::Constant(notional) => vec![*notional; n],
+            Self::Explicit(notionals) => {
+                if notionals.len() != n {
+                    return Err(Error::MismatchedNotionals(n, notionals.len()));
+                }
+                notionals.clone()
+            }
+            Self::Linear(initial) => (0..n)
+                .map(|i| initial * (n - i) as f64 / n as f64)
+                .collect(),
+            Self::Annuity { initial, rate } => {
+                // Level payment A = N(0) / Σ Π 1 / (1 + rate.τ(j)).
+                let mut discount = 1.0;
+                let mut sum = 0.0;
+                for tau in year_fractions {
+                    discount /= 1.0 + rate * tau;
+                    sum += discount;
+                }
+                let payment = initial / sum;
+                let mut notional = *initial;
+                year_fractions
+                    .iter()
+                    .map(|tau| {
+                        let current = notional;
+                        notional = notional * (1.0 + rate * tau) - payment;
+                        current
+                    })
+                    .collect()
+            }
+            Self::Accreting { initial, rate } => {
+                let mut notional = *initial;
+                year_fractions
+                    .iter()
+                    .map(|tau| {
+                        let current = notional;
+                        notional *= 1.0 + rate * tau;
+                        current
+                    })
+                    .collect()
+            }
+        };
+        Ok(amounts)
+    }
+}
+
+impl CompoundingMethods {
+    /// Interest per unit of notional over a payment period, from the 'rates' fixed
+    /// over sub-periods of 'year_fractions'.
+    pub fn interest(&self, rates: &[f64], year_fractions: &[f64], spread: f64) -> f64 {
+        let year_fraction: f64 = year_fractions.iter().sum();
+        let periods = rates.iter().zip(year_fractions.iter());
+        match self {
+            Self::Straight => {
+                periods.fold(1.0, |acc, (r, tau)| acc * (1.0 + (r + spread) * tau)) - 1.0
+            }
+            Self::SpreadExclusive => {
+                periods.fold(1.0, |acc, (r, tau)| acc * (1.0 + r * tau)) - 1.0
+                    + spread * year_fraction
+            }
+            Self::Flat => periods.fold(0.0, |acc, (r, tau)| {
+                acc + (r + spread) * tau + acc * r * tau
+            }),
+            Self::Averaging => {
+                let accrued: f64 = periods.map(|(r, tau)| r * tau).sum();
+                accrued + spread * year_fraction
+            }
+        }
+    }
+}
+
+impl SwapLeg {
+    fn new(
+        direction: Direction,
+        notional: Notionals,
+        schedule: ScheduleBuilder,
+        coupon: Coupons,
+    ) -> Self {
+        Self {
+            direction,
+            notional,
+            principal_exchange: PrincipalExchanges::default(),
+            schedule,
+            calendar: Calendar::default(),
+            day_count: DayCountConventions::default(),
+            coupon,
+            spread: 0.0,
+            index: String::new(),
+            fixing_calendar: Calendar::default(),
+            fixing_lag: 0,
+            reset_frequency: None,
+            compounding: CompoundingMethods::default(),
+        }
+    }
+
+    pub fn fixed<N: Into<Notionals>>(
+        direction: Direction,
+        notional: N,
+        schedule: ScheduleBuilder,
+        rate: f64,
+    ) -> Self {
+        Self::new(direction, notional.into(), schedule, Coupons::Fixed(rate))
+    }
+
+    pub fn floating<N: Into<Notionals>>(
+        direction: Direction,
+        notional: N,
+        schedule: ScheduleBuilder,
+    ) -> Self {
+        Self::new(direction, notional.into(), schedule, Coupons::Floating)
+    }
+
+    /// Leg compounding the daily fixings of an overnight rate in arrears. The fixing
+    /// calendar sets the days the rate is observed on.
+    pub fn overnight<N: Into<Notionals>>(
+        direction: Direction,
+        notional: N,
+        schedule: ScheduleBuilder,
+        compounding: OvernightCompounding,
+    ) -> Self {
+        Self::new(
+            direction,
+            notional.into(),
+            schedule,
+            Coupons::Overnight(compounding),
+        )
+    }
+
+    /// Number of business days after the end of each period the coupon is paid.
+    pub fn payment_delay(self, payment_delay: u32) -> Self {
+        Self {
+            schedule: self.schedule.payment_lag(payment_delay),
+            ..self
+        }
+    }
+
+    pub fn principal_exchange(self, principal_exchange: PrincipalExchanges) -> Self {
+        Self {
+            principal_exchange,
+            ..self
+        }
+    }
+
+    /// Calendar the schedule is adjusted against.
+    pub fn calendar(self, calendar: Calendar) -> Self {
+        Self { calendar, ..self }
+    }
+
+    /// Day count the coupons accrue under.
+    pub fn day_count(self, day_count: DayCountConventions) -> Self {
+        Self { day_count, ..self }
+    }
+
+    /// Spread over the rate, in decimal.
+    pub fn spread(self, spread: f64) -> Self {
+        Self { spread, ..self }
+    }
+
+    /// Name of the floating rate index, keying its historical fixings.
+    pub fn index(self, index: &str) -> Self {
+        Self {
+            index: index.to_string(),
+            ..self
+        }
+    }
+
+    /// Calendar the fixing lag is counted in.
+    pub fn fixing_calendar(self, fixing_calendar: Calendar) -> Self {
+        Self {
+            fixing_calendar,
+            ..self
+        }
+    }
+
+    /// Number of business days the rate fixes before the accrual period starts.
+    pub fn fixing_lag(self, fixing_lag: u32) -> Self {
+        Self { fixing_lag, ..self }
+    }
+
+    /// Frequency a floating rate resets at, if more frequent than the payment frequency.
+    /// Pricing a fixed or overnight leg with a reset frequency is an error, as overnight
+    /// legs compound daily per their `OvernightCompounding`.
+    pub fn reset_frequency(self, reset_frequency: IntervalPeriod) -> Self {
+        Self {
+            reset_frequency: Some(reset_frequency),
+            ..self
+        }
+    }
+
+    /// Combination of the rates fixed within a payment period. Only applies to the
+    /// resets of a floating leg with a reset frequency.
+    pub fn compounding(self, compounding: CompoundingMethods) -> Self {
+        Self {
+            compounding,
+            ..self
+        }
+    }
+
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    pub fn notional(&self) -> &Notionals {
+        &self.notional
+    }
+
+    pub fn index_name(&self) -> &str {
+        &self.index
+    }
+
+    pub fn coupon(&self) -> Coupons {
+        self.coupon
+    }
+
+    pub fn schedule(&self) -> Result<Schedule> {
+        Ok(self.schedule.build(&self.calendar)?)
+    }
+
+    /// Whether the spread compounds over the resets of a payment period, such that
+    /// the coupon is not linear in the spread.
+    fn compounds_spread(&self) -> bool {
+        self.coupon == Coupons::Floating
+            && self.reset_frequency.is_some()
+            && matches!(
+                self.compounding,
+                CompoundingMethods::Straight | CompoundingMethods::Flat
+            )
+    }
+
+    /// Reset periods, rolled as the payment schedule at the reset frequency.
+    fn reset_schedule(&self) -> Result<Option<Schedule>> {
+        match self.reset_frequency {
+            Some(_) if self.coupon != Coupons::Floating => {
+                Err(Error::UnsupportedResets(self.coupon))
+            }
+            Some(reset_frequency) => Ok(Some(
+                self.schedule
+                    .clone()
+                    .tenor(reset_frequency)
+                    .build(&self.calendar)?,
+            )),
+            None => Ok(None),
+        }
+    }
+
+    /// Date the rate of the period accruing from 'start' fixes on.
+    pub fn fixing_date(&self, start: &NaiveDate) -> Result<NaiveDate> {
+        Ok(fixings::fixing_date(
+            start,
+            self.fixing_lag,
+            &self.fixing_calendar,
+        )?)
+    }
+}
+
+impl InterestRateSwap {
+    pub fn new(first: SwapLeg, second: SwapLeg) -> Self {
+        Self {
+            legs: [first, second],
+        }
+    }
+
+    pub fn legs(&self) -> &[SwapLeg] {
+        &self.legs
+    }
+}
+
+impl SwapPricer {
+    pub fn new(valuation_date: NaiveDate, discount_curve: YieldCurve) -> Self {
+        Self {
+            valuation_date,
+            discount_curve,
+            forward_curve: None,
+            fixings: Fixings::new(),
+            valuation_date_fixings: ValuationDateFixings::default(),
+        }
+    }
+
+    /// Curve floating rates are projected off. Defaults to the discount curve.
+    pub fn forward_curve(self, forward_curve: YieldCurve) -> Self {
+        Self {
+            forward_curve: Some(forward_curve),
+            ..self
+        }
+    }
+
+    /// Historical rates of the indices legs float on.
+    pub fn fixings(self, fixings: &Fixings) -> Self {
+        Self {
+            fixings: fixings.clone(),
+            ..self
+        }
+    }
+
+    /// Rate of periods fixing on the valuation date.
+    pub fn valuation_date_fixings(self, valuation_date_fixings: ValuationDateFixings) -> Self {
+        Self {
+            valuation_date_fixings,
+            ..self
+        }
+    }
+
+    pub fn price(&self, swap: &InterestRateSwap) -> Result<SwapValuation> {
+        let legs = swap
+            .legs()
+            .iter()
+            .map(|leg| self.price_leg(leg))
+            .collect::<Result<Vec<LegValuation>>>()?;
+        let npv: f64 = legs.iter().map(|leg| leg.pv).sum();
+
+        let index = legs
+            .iter()
+            .position(|leg| matches!(leg.coupon, Coupons::Fixed(_)))
+            .unwrap_or(0);
+        let quoted = &legs[index];
+        let mut par_rate = quoted.quote() - npv / (quoted.direction.sign() * quoted.annuity);
+        let annuity = quoted.annuity;
+
+        let leg = &swap.legs()[index];
+        if leg.compounds_spread() && par_rate.is_finite() {
+            // The linear par rate is a first order estimate, refined by repricing.
+            let other = npv - quoted.pv;
+            let residual = |spread: f64| {
+                self.price_leg(&leg.clone().spread(spread))
+                    .map(|v| (other + v.pv) / annuity)
+                    .unwrap_or(f64::NAN)
+            };
+            par_rate = RootFinder::new()
+                .tolerance(PAR_RATE_TOLERANCE)
+                .secant(residual, quoted.quote(), par_rate)?
+                .root();
+        }
+
+        Ok(SwapValuation {
+            legs,
+            npv,
+            par_rate,
+            annuity,
+        })
+    }
+
+    pub fn price_leg(&self, leg: &SwapLeg) -> Result<LegValuation> {
+        let sign = leg.direction.sign();
+        let schedule = leg.schedule()?;
+        let periods = schedule.periods();
+        let year_fractions = periods
+            .iter()
+            .map(|p| {
+                leg.day_count
+                    .try_year_fraction(&p.adjusted_start, &p.adjusted_end)
+            })
+            .collect::<std::result::Result<Vec<f64>, _>>()?;
+        let notionals = leg.notional.amounts(&year_fractions)?;
+        let reset_schedule = leg.reset_schedule()?;
+
+        let mut cash_flows = Vec::new();
+        if leg.principal_exchange == PrincipalExchanges::InitialAndFinal {
+            let date = periods[0].adjusted_start;
+            if date > self.valuation_date {
+                cash_flows.push(self.principal(-sign * notionals[0], notionals[0], &date)?);
+            }
+        }
+        for (i, period) in periods.iter().enumerate() {
+            if period.payment_date <= self.valuation_date {
+                continue;
+            }
+            let notional = notionals[i];
+            let year_fraction = year_fractions[i];
+            let (fixing_date, rate) = match leg.coupon {
+                Coupons::Fixed(rate) => (None, rate),
+                Coupons::Floating => {
+                    // Reset periods within the payment period, clipped to its bounds.
+                    let resets: Vec<(NaiveDate, NaiveDate, NaiveDate)> = match &reset_schedule {
+                        Some(resets) => resets
+                            .periods()
+                            .iter()
+                            .filter(|r| {
+                                r.adjusted_end > period.adjusted_start
+                                    && r.adjusted_start < period.adjusted_end
+                            })
+                            .map(|r| {
+                                (
+                                    r.adjusted_start,
+                                    r.adjusted_start.max(period.adjusted_start),
+                                    r.adjusted_end.min(period.adjusted_end),
+                                )
+                            })
+                            .collect(),
+                        None => vec![(
+                            period.adjusted_start,
+                            period.adjusted_start,
+                            period.adjusted_end,
+                        )],
+                    };
+
+                    let mut rates = Vec::with_capacity(resets.len());
+                    let mut fractions = Vec::with_capacity(resets.len());
+                    for (reset, start, end) in &resets {
+                        let tau = leg.day_count.try_year_fraction(start, end)?;
+                        let fixing_date = leg.fixing_date(reset)?;
+                        rates.push(self.floating_rate(
+                            &leg.index,
+                            &fixing_date,
+                            start,
+                            end,
+                            tau,
+                        )?);
+                        fractions.push(tau);
+                    }
+                    let interest = leg.compounding.interest(&rates, &fractions, leg.spread);
+                    // Equivalent simple rate over the period, before the spread.
+                    let rate = interest / year_fraction - leg.spread;
+                    (Some(leg.fixing_date(&resets[0].0)?), rate)
+                }
+                Coupons::Overnight(compounding) => {
+                    let observations = compounding
+                        .observations(
+                            &period.adjusted_start,
+                            &period.adjusted_end,
+                            &leg.fixing_calendar,
+                            &leg.day_count,
+                        )
+                        .ok_or(Error::ObservationScheduleError(
+                            period.adjusted_start,
+                            period.adjusted_end,
+                        ))?;
+
+                    let mut rates = Vec::with_capacity(observations.len());
+                    let mut weights = Vec::with_capacity(observations.len());
+                    for o in &observations {
+                        let tau = leg
+                            .day_count
+                            .try_year_fraction(&o.fixing_date, &o.fixing_end)?;
+                        rates.push(self.floating_rate(
+                            &leg.index,
+                            &o.fixing_date,
+                            &o.fixing_date,
+                            &o.fixing_end,
+                            tau,
+                        )?);
+                        weights.push(o.weight);
+                    }
+                    let fixing_date = observations.last().map(|o| o.fixing_date);
+                    (fixing_date, compounded_rate(&rates, &weights))
+                }
+            };
+            let amount = sign * notional * (rate + leg.spread) * year_fraction;
+            let discount_factor = self.discount(&period.payment_date)?;
+
+            cash_flows.push(CashFlow {
+                kind: CashFlowTypes::Interest,
+                accrual_start: period.adjusted_start,
+                accrual_end: period.adjusted_end,
+                payment_date: period.payment_date,
+                fixing_date,
+                notional,
+                year_fraction,
+                rate,
+                spread: leg.spread,
+                amount,
+                discount_factor,
+                present_value: amount * discount_factor,
+            });
+
+            if leg.principal_exchange != PrincipalExchanges::None {
+                let repaid = notional - notionals.get(i + 1).unwrap_or(&0.0);
+                if repaid != 0.0 {
+                    cash_flows.push(self.principal(
+                        sign * repaid,
+                        notional,
+                        &period.payment_date,
+                    )?);
+                }
+            }
+        }
+
+        let pv = cash_flows.iter().map(|c| c.present_value).sum();
+        let annuity = cash_flows
+            .iter()
+            .filter(|c| c.kind == CashFlowTypes::Interest)
+            .map(|c| c.notional * c.year_fraction * c.discount_factor)
+            .sum();
+
+        Ok(LegValuation {
+            direction: leg.direction,
+            coupon: leg.coupon,
+            spread: leg.spread,
+            cash_flows,
+            pv,
+            annuity,
+        })
+    }
+
+    /// Exchange of 'amount' of principal on 'date', out of 'notional'.
+    fn principal(&self, amount: f64, notional: f64, date: &NaiveDate) -> Result<CashFlow> {
+        let discount_factor = self.discount(date)?;
+        Ok(CashFlow {
+            kind: CashFlowTypes::Principal,
+            accrual_start: *date,
+            accrual_end: *date,
+            payment_date: *date,
+            fixing_date: None,
+            notional,
+            year_fraction: 0.0,
+            rate: 0.0,
+            spread: 0.0,
+            amount,
+            discount_factor,
+            present_value: amount * discount_factor,
+        })
+    }
+
+    /// Discount factor from 'date' to the valuation date.
+    fn discount(&self, date: &NaiveDate) -> Result<f64> {
+        Ok(self.discount_curve.try_discount(date)?
+            / self.discount_curve.try_discount(&self.valuation_date)?)
+    }
+
+    /// Historical fixing of 'index' if the rate fixed before the valuation date, else
+    /// the simple forward rate between 'start' and 'end'. Rates fixing on the valuation
+    /// date follow the valuation date rule.
+    fn floating_rate(
+        &self,
+        index: &str,
+        fixing_date: &NaiveDate,
+        start: &NaiveDate,
+        end: &NaiveDate,
+        year_fraction: f64,
+    ) -> Result<f64> {
+        if *fixing_date < self.valuation_date {
+            return Ok(self.fixings.fixing(index, fixing_date)?);
+        }
+        if *fixing_date == self.valuation_date {
+            match self.valuation_date_fixings {
+                ValuationDateFixings::Published => {
+                    return Ok(self.fixings.fixing(index, fixing_date)?)
+                }
+                ValuationDateFixings::PublishedIfAvailable => {
+                    if let Some(rate) = self.fixings.get(index, fixing_date) {
+                        return Ok(rate);
+                    }
+                }
+                ValuationDateFixings::Projected => {}
+            }
+        }
+        let curve = self.forward_curve.as_ref().unwrap_or(&self.discount_curve);
+        let growth = curve.try_discount(start)? / curve.try_discount(end)?;
+        Ok((growth - 1.0) / year_fraction)
+    }
+}
+
+impl LegValuation {
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    pub fn cash_flows(&self) -> &[CashFlow] {
+        &self.cash_flows
+    }
+
+    /// Present value, signed by the direction of the leg.
+    pub fn pv(&self) -> f64 {
+        self.pv
+    }
+
+    /// Present value of one unit of rate, Σ N.τ.P(payment).
+    pub fn annuity(&self) -> f64 {
+        self.annuity
+    }
+
+    /// Fixed rate, or spread of a floating leg.
+    fn quote(&self) -> f64 {
+        match self.coupon {
+            Coupons::Fixed(rate) => rate + self.spread,
+            Coupons::Floating | Coupons::Overnight(_) => self.spread,
+        }
+    }
+}
+
+impl SwapValuation {
+    pub fn legs(&self) -> &[LegValuation] {
+        &self.legs
+    }
+
+    pub fn npv(&self) -> f64 {
+        self.npv
+    }
+
+    /// Rate on the quoted leg that sets the NPV to zero. Not a number if the quoted
+    /// leg has no outstanding cash flows.
+    pub fn par_rate(&self) -> f64 {
+        self.par_rate
+    }
+
+    /// Annuity of the quoted leg.
+    pub fn annuity(&self) -> f64 {
+        self.annuity
+    }
+}
+
+//  --- Trait implementations: Concrete
+
+impl From<f64> for Notionals {
+    fn from(value: f64) -> Self {
+        Self::Constant(value)
+    }
+}
+
+impl fmt::Display for LegValuation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<11}{:<12}{:<12}{:<12}{:<12}{:>18}{:>12}{:>12}{:>10}{:>18}{:>12}{:>18}",
+            "Type",
+            "Start",
+            "End",
+            "Payment",
+            "Fixing",
+            "Notional",
+            "Fraction",
+            "Rate",
+            "Spread",
+            "Amount",
+            "DF",
+            "PV"
+        )?;
+        for c in &self.cash_flows {
+            let fixing_date = c.fixing_date.map(|d| d.to_string()).unwrap_or_default();
+            writeln!(
+                f,
+                "{:<11}{:<12}{:<12}{:<12}{:<12}{:>18.2}{:>12.6}{:>12.6}{:>10.6}{:>18.2}{:>12.8}{:>18.2}",
+                format!("{:?}", c.kind),
+                c.accrual_start.to_string(),
+                c.accrual_end.to_string(),
+                c.payment_date.to_string(),
+                fixing_date,
+                c.notional,
+                c.year_fraction,
+                c.rate,
+                c.spread,
+                c.amount,
+                c.discount_factor,
+                c.present_value
+            )?;
+        }
+        writeln!(f, "{:>167.2}", self.pv)
+    }
+}
+
+//  --- Unit tests
+#[cfg(test)]
+mod test_swap {
+
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::interest::term_structure::{Term, TermStructure};
+    use crate::test_utils::date;
+
+    /// Flat continuously compounded curve.
+    fn flat_curve(reference_date: NaiveDate, rate: f64) -> YieldCurve {
+        let t: Vec<f64> = (1..=40).map(|i| i as f64 * 0.5).collect();
+        let df: Vec<f64> = t.iter().map(|t| (-rate * t).exp()).collect();
+        YieldCurve::new(reference_date, &Term::new(&t, &df))
+    }
+
+    fn schedule(tenor: IntervalPeriod) -> ScheduleBuilder {
+        ScheduleBuilder::new(date(2024, 1, 15), date(2029, 1, 15), tenor)
+    }
+
+    #[test]
+    fn test_floating_leg() {
+        // A floating leg projected and discounted off one curve is worth
+        // N.(P(start) - P(end)).
+        let curve = flat_curve(date(2024, 1, 15), 0.05);
+        let leg = SwapLeg::floating(Direction::Receive, 1e6, schedule(IntervalPeriod::Months(3)));
+        let valuation = SwapPricer::new(date(2024, 1, 15), curve.clone())
+            .price_leg(&leg)
+            .unwrap();
+
+        let expected = 1e6 * (1.0 - curve.discount(&date(2029, 1, 15)));
+        assert_approx_eq!(valuation.pv(), expected, 1e-6);
+        assert_eq!(valuation.cash_flows().len(), 20);
+        assert_eq!(
+            valuation.cash_flows()[0].fixing_date,
+            Some(date(2024, 1, 15))
+        );
+    }
+
+    #[test]
+    fn test_par_swap() {
+        let valuation_date = date(2024, 1, 15);
+        let pricer = SwapPricer::new(valuation_date, flat_curve(valuation_date, 0.05))
+            .forward_curve(flat_curve(valuation_date, 0.055));
+        let swap = |rate: f64| {
+            InterestRateSwap::new(
+                SwapLeg::fixed(
+                    Direction::Receive,
+                    1e6,
+                    schedule(IntervalPeriod::Years(1)),
+                    rate,
+                ),
+                SwapLeg::floating(Direction::Pay, 1e6, schedule(IntervalPeriod::Months(3))),
+            )
+        };
+
+        let valuation = pricer.price(&swap(0.05)).unwrap();
+        let legs = valuation.legs();
+        assert_approx_eq!(valuation.npv(), legs[0].pv() + legs[1].pv(), 1e-8);
+        assert!(legs[0].pv() > 0.0 && legs[1].pv() < 0.0);
+        assert_approx_eq!(valuation.annuity(), legs[0].annuity(), 1e-8);
+
+        let par = pricer.price(&swap(valuation.par_rate())).unwrap();
+        assert_approx_eq!(par.npv(), 0.0, 1e-6);
+        assert_approx_eq!(par.par_rate(), valuation.par_rate(), 1e-12);
+    }
+
+    #[test]
+    fn test_basis_swap() {
+        // With floating legs only, the par rate is the spread on the first leg.
+        let valuation_date = date(2024, 1, 15);
+        let pricer = SwapPricer::new(valuation_date, flat_curve(valuation_date, 0.05))
+            .forward_curve(flat_curve(valuation_date, 0.055));
+        let swap = |spread: f64| {
+            InterestRateSwap::new(
+                SwapLeg::floating(Direction::Pay, 1e6, schedule(IntervalPeriod::Months(3)))
+                    .spread(spread),
+                SwapLeg::floating(Direction::Receive, 1e6, schedule(IntervalPeriod::Months(6))),
+            )
+        };
+
+        let par_spread = pricer.price(&swap(0.0)).unwrap().par_rate();
+        assert_approx_eq!(pricer.price(&swap(par_spread)).unwrap().npv(), 0.0, 1e-6);
+
+        // Monthly resets compounded into annual payments, spread included, are not
+        // linear in the spread.
+        let swap = |spread: f64| {
+            InterestRateSwap::new(
+                SwapLeg::floating(Direction::Pay, 1e6, schedule(IntervalPeriod::Years(1)))
+                    .reset_frequency(IntervalPeriod::Months(1))
+                    .spread(spread),
+                SwapLeg::floating(Direction::Receive, 1e6, schedule(IntervalPeriod::Months(3))),
+            )
+        };
+        let par_spread = pricer.price(&swap(0.0)).unwrap().par_rate();
+        assert_approx_eq!(pricer.price(&swap(par_spread)).unwrap().npv(), 0.0, 1e-6);
+        let valuation = pricer.price(&swap(0.01)).unwrap();
+        assert_approx_eq!(valuation.par_rate(), par_spread, 1e-10);
+    }
+
+    #[test]
+    fn test_fixings() {
+        // Mid period, the current rate is taken from the fixings and settled
+        // cash flows are excluded.
+        let valuation_date = date(2024, 3, 1);
+        let curve = flat_curve(valuation_date, 0.05);
+        let leg = SwapLeg::floating(Direction::Pay, 1e6, schedule(IntervalPeriod::Months(3)))
+            .index("JIBAR3M")
+            .fixing_lag(1)
+            .spread(0.001);
+
+        let fixings = Fixings::new().series(
+            "JIBAR3M",
+            &BTreeMap::from([(date(2024, 1, 12), 0.08), (date(2024, 4, 12), 0.07)]),
+        );
+        let valuation = SwapPricer::new(valuation_date, curve.clone())
+            .fixings(&fixings)
+            .price_leg(&leg)
+            .unwrap();
+        let first = valuation.cash_flows()[0];
+        assert_eq!(first.fixing_date, Some(date(2024, 1, 12)));
+        assert_approx_eq!(first.rate, 0.08, 1e-12);
+        assert_approx_eq!(first.amount, -1e6 * 0.081 * first.year_fraction, 1e-6);
+
+        let valuation = SwapPricer::new(date(2024, 5, 1), curve)
+            .fixings(&fixings)
+            .price_leg(&leg)
+            .unwrap();
+        assert_eq!(valuation.cash_flows().len(), 19);
+    }
+
+    #[test]
+    fn test_valuation_date_fixings() {
+        // The current period fixes on the valuation date.
+        let valuation_date = date(2024, 4, 15);
+        let curve = flat_curve(valuation_date, 0.05);
+        let leg = SwapLeg::floating(Direction::Receive, 1e6, schedule(IntervalPeriod::Months(3)))
+            .index("JIBAR3M");
+        let fixings = Fixings::new().series("JIBAR3M", &BTreeMap::from([(valuation_date, 0.08)]));
+        let rate = |rule: ValuationDateFixings, fixings: &Fixings| {
+            SwapPricer::new(valuation_date, curve.clone())
+                .fixings(fixings)
+                .valuation_date_fixings(rule)
+                .price_leg(&leg)
+                .map(|v| v.cash_flows()[0].rate)
+        };
+
+        let projected = rate(ValuationDateFixings::Projected, &fixings).unwrap();
+        assert!((projected - 0.08).abs() > 1e-3);
+        for rule in [
+            ValuationDateFixings::Published,
+            ValuationDateFixings::PublishedIfAvailable,
+        ] {
+            assert_approx_eq!(rate(rule, &fixings).unwrap(), 0.08, 1e-12);
+        }
+
+        // Without a published rate, the rate is projected unless it is required.
+        let empty = Fixings::new().series("JIBAR3M", &BTreeMap::new());
+        assert_approx_eq!(
+            rate(ValuationDateFixings::PublishedIfAvailable, &empty).unwrap(),
+            projected,
+            1e-12
+        );
+        assert!(matches!(
+            rate(ValuationDateFixings::Published, &empty),
+            Err(Error::FixingError(fixings::Error::MissingFixing(_, d))) if d == valuation_date
+        ));
+    }
+
+    #[test]
+    fn test_compounding() {
+        let rates = [0.05, 0.06];
+        let year_fractions = [0.25, 0.25];
+        let interest = |method: CompoundingMethods| method.interest(&rates, &year_fractions, 0.01);
+        assert_approx_eq!(interest(CompoundingMethods::Straight), 0.0327625, 1e-12);
+        assert_approx_eq!(
+            interest(CompoundingMethods::SpreadExclusive),
+            0.0326875,
+            1e-12
+        );
+        assert_approx_eq!(interest(CompoundingMethods::Flat), 0.032725, 1e-12);
+        assert_approx_eq!(interest(CompoundingMethods::Averaging), 0.0325, 1e-12);
+
+        // A single reset accrues simple interest under every method.
+        for method in [
+            CompoundingMethods::Straight,
+            CompoundingMethods::SpreadExclusive,
+            CompoundingMethods::Flat,
+            CompoundingMethods::Averaging,
+        ] {
+            assert_approx_eq!(method.interest(&[0.05], &[0.5], 0.01), 0.03, 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_reset_frequency() {
+        // Quarterly resets compounded into semi-annual payments, off one curve,
+        // are worth N.(P(start) - P(end)).
+        let curve = flat_curve(date(2024, 1, 15), 0.05);
+        let pricer = SwapPricer::new(date(2024, 1, 15), curve.clone());
+        let leg = SwapLeg::floating(Direction::Receive, 1e6, schedule(IntervalPeriod::Months(6)))
+            .reset_frequency(IntervalPeriod::Months(3));
+        let valuation = pricer.price_leg(&leg).unwrap();
+        let expected = 1e6 * (1.0 - curve.discount(&date(2029, 1, 15)));
+        assert_approx_eq!(valuation.pv(), expected, 1e-6);
+        assert_eq!(valuation.cash_flows().len(), 10);
+
+        // Averaging accrues less than compounding, and flat compounding sits between
+        // the straight and spread exclusive methods.
+        let pv = |method: CompoundingMethods| {
+            pricer
+                .price_leg(&leg.clone().spread(0.01).compounding(method))
+                .unwrap()
+                .pv()
+        };
+        assert!(pv(CompoundingMethods::Averaging) < pv(CompoundingMethods::SpreadExclusive));
+        assert!(pv(CompoundingMethods::SpreadExclusive) < pv(CompoundingMethods::Flat));
+        assert!(pv(CompoundingMethods::Flat) < pv(CompoundingMethods::Straight));
+    }
+
+    #[test]
+    fn test_overnight_leg() {
+        // Daily compounded forwards off one curve telescope, such that the leg is
+        // worth N.(P(start) - P(end)).
+        let valuation_date = date(2024, 1, 15);
+        let curve = flat_curve(valuation_date, 0.05);
+        let leg = SwapLeg::overnight(
+            Direction::Receive,
+            1e6,
+            schedule(IntervalPeriod::Years(1)),
+            OvernightCompounding::new(),
+        )
+        .day_count(DayCountConventions::Actual360);
+        let valuation = SwapPricer::new(valuation_date, curve.clone())
+            .price_leg(&leg)
+            .unwrap();
+        let expected = 1e6 * (1.0 - curve.discount(&date(2029, 1, 15)));
+        assert_approx_eq!(valuation.pv(), expected, 1e-6);
+
+        // Mid period, published fixings are compounded with the projected rates, and
+        // the coupon is paid after the payment delay.
+        let valuation_date = date(2024, 3, 1);
+        let leg = SwapLeg::overnight(
+            Direction::Receive,
+            1e6,
+            schedule(IntervalPeriod::Years(1)),
+            OvernightCompounding::new().lookback(2),
+        )
+        .index("ZARONIA")
+        .day_count(DayCountConventions::Actual360)
+        .payment_delay(2)
+        .spread(0.001);
+        let pricer = SwapPricer::new(valuation_date, flat_curve(valuation_date, 0.05));
+        assert!(matches!(
+            pricer.price_leg(&leg),
+            Err(Error::FixingError(fixings::Error::UnknownIndex(_)))
+        ));
+
+        let rates: BTreeMap<NaiveDate, f64> = date(2024, 1, 1)
+            .iter_days()
+            .take_while(|d| *d < valuation_date)
+            .map(|d| (d, 0.04))
+            .collect();
+        let fixings = Fixings::new().series("ZARONIA", &rates);
+        let valuation = pricer.fixings(&fixings).price_leg(&leg).unwrap();
+        let first = valuation.cash_flows()[0];
+        assert_eq!(first.payment_date, date(2025, 1, 17));
+        assert_eq!(first.fixing_date, Some(date(2025, 1, 10)));
+        assert!(first.rate > 0.04 && first.rate < 0.052);
+        assert_approx_eq!(
+            first.amount,
+            1e6 * (first.rate + 0.001) * first.year_fraction,
+            1e-6
+        );
+    }
+
+    #[test]
+    fn test_notionals() {
+        let year_fractions = [0.5, 0.25, 0.5, 0.25];
+        let linear = Notionals::Linear(100.0).amounts(&year_fractions).unwrap();
+        assert_eq!(linear, vec![100.0, 75.0, 50.0, 25.0]);
+
+        let explicit = Notionals::Explicit(vec![90.0, 90.0, 75.0, 60.0])
+            .amounts(&year_fractions)
+            .unwrap();
+        assert_eq!(explicit, vec![90.0, 90.0, 75.0, 60.0]);
+
+        // Interest plus principal is level, and the notional is repaid in full.
+        let rate = 0.08;
+        let annuity = Notionals::Annuity {
+            initial: 100.0,
+            rate,
+        }
+        .amounts(&year_fractions)
+        .unwrap();
+        let payments: Vec<f64> = (0..4)
+            .map(|i| {
+                let next = annuity.get(i + 1).unwrap_or(&0.0);
+                annuity[i] * rate * year_fractions[i] + annuity[i] - next
+            })
+            .collect();
+        for payment in &payments {
+            assert_approx_eq!(*payment, payments[0], 1e-10);
+        }
+        assert_approx_eq!(annuity[0], 100.0, 1e-12);
+
+        let accreting = Notionals::Accreting {
+            initial: 100.0,
+            rate,
+        }
+        .amounts(&year_fractions)
+        .unwrap();
+        assert_approx_eq!(accreting[1], 104.0, 1e-12);
+        assert_approx_eq!(accreting[3], 104.0 * 1.02 * 1.04, 1e-12);
+
+        assert!(matches!(
+            Notionals::Explicit(vec![1.0; 5]).amounts(&year_fractions),
+            Err(Error::MismatchedNotionals(4, 5))
+        ));
+        assert!(matches!(
+            Notionals::Explicit(vec![90.0, 60.0]).amounts(&year_fractions),
+            Err(Error::MismatchedNotionals(4, 2))
+        ));
+    }
+
+    #[test]
+    fn test_principal_exchange() {
+        // A forward starting amortising floater with all principal exchanged, projected
+        // and discounted off one curve, is worth nothing.
+        let valuation_date = date(2023, 7, 15);
+        let curve = flat_curve(valuation_date, 0.05);
+        let leg = SwapLeg::floating(
+            Direction::Receive,
+            Notionals::Linear(1e6),
+            schedule(IntervalPeriod::Months(6)),
+        )
+        .principal_exchange(PrincipalExchanges::InitialAndFinal);
+        let valuation = SwapPricer::new(valuation_date, curve)
+            .price_leg(&leg)
+            .unwrap();
+        assert_approx_eq!(valuation.pv(), 0.0, 1e-6);
+
+        let principal: Vec<&CashFlow> = valuation
+            .cash_flows()
+            .iter()
+            .filter(|c| c.kind == CashFlowTypes::Principal)
+            .collect();
+        assert_eq!(principal.len(), 11);
+        assert_approx_eq!(principal[0].amount, -1e6, 1e-6);
+        assert_approx_eq!(principal[1].amount, 1e5, 1e-6);
+        let total: f64 = principal.iter().map(|c| c.amount).sum();
+        assert_approx_eq!(total, 0.0, 1e-6);
+
+        // Coupons accrue on the notional outstanding over each period.
+        let coupons: Vec<&CashFlow> = valuation
+            .cash_flows()
+            .iter()
+            .filter(|c| c.kind == CashFlowTypes::Interest)
+            .collect();
+        assert_approx_eq!(coupons[9].notional, 1e5, 1e-6);
+    }
+
+    #[test]
+    fn test_swap_errors() {
+        let valuation_date = date(2024, 3, 1);
+        let pricer = SwapPricer::new(valuation_date, flat_curve(valuation_date, 0.05));
+        let leg = SwapLeg::floating(Direction::Pay, 1e6, schedule(IntervalPeriod::Months(3)))
+            .index("JIBAR3M");
+        let fixings =
+            Fixings::new().series("JIBAR3M", &BTreeMap::from([(date(2024, 1, 12), 0.08)]));
+        assert!(matches!(
+            pricer.clone().fixings(&fixings).price_leg(&leg),
+            Err(Error::FixingError(fixings::Error::MissingFixing(index, d)))
+                if index == "JIBAR3M" && d == date(2024, 1, 15)
+        ));
+
+        let leg = SwapLeg::fixed(
+            Direction::Pay,
+            1e6,
+            ScheduleBuilder::new(
+                date(2029, 1, 15),
+                date(2024, 1, 15),
+                IntervalPeriod::Years(1),
+            ),
+            0.05,
+        );
+        assert!(matches!(
+            pricer.price_leg(&leg),
+            Err(Error::ScheduleError(_))
+        ));
+
+        // A day count which can not be evaluated is reported, rather than priced as NaN.
+        let leg = SwapLeg::fixed(
+            Direction::Pay,
+            1e6,
+            schedule(IntervalPeriod::Years(1)),
+            0.05,
+        )
+        .day_count(DayCountConventions::Actual365L(IntervalPeriod::Months(0)));
+        assert!(matches!(
+            pricer.price_leg(&leg),
+            Err(Error::DayCountError(_))
+        ));
+
+        // Overnight legs compound daily, and do not reset.
+        let leg = SwapLeg::overnight(
+            Direction::Pay,
+            1e6,
+            schedule(IntervalPeriod::Years(1)),
+            OvernightCompounding::new(),
+        )
+        .reset_frequency(IntervalPeriod::Months(3));
+        assert!(matches!(
+            pricer.price_leg(&leg),
+            Err(Error::UnsupportedResets(Coupons::Overnight(_)))
+        ));
+
+        // Looking back from the earliest supported date.
+        let start = NaiveDate::MIN;
+        let end = start + chrono::Days::new(10);
+        let leg = SwapLeg::overnight(
+            Direction::Pay,
+            1e6,
+            ScheduleBuilder::new(start, end, IntervalPeriod::Days(10)),
+            OvernightCompounding::new().lookback(1),
+        );
+        assert!(matches!(
+            SwapPricer::new(start, flat_curve(start, 0.05)).price_leg(&leg),
+            Err(Error::ObservationScheduleError(s, e)) if s == start && e == end
+        ));
+    }
+}
@@ -60,8 +60,31 @@
 // The purpose of module is not to convert individual rates, but rather a curve.
 use super::ops::{InterestConventions, TimeValueOfMoney};
 use super::term_structure::{Term, TermStructure};
+use crate::math::solver::{self, RootFinder};
 
 //  --- Errors
+type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+    SolverError(solver::Error),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::SolverError(e) => write!(f, "Failed to solve the swap rate: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<solver::Error> for Error {
+    fn from(value: solver::Error) -> Self {
+        Self::SolverError(value)
+    }
+}
 
 //  --- Types
 type Point = (f64, f64);
@@ -69,14 +92,63 @@ type Points<'a> = (&'a [f64], &'a [f64]);
 
 //  --- Enums
 
+/// Curves are keyed by the time, in years, from the valuation date to each pillar.
+/// Swap and par curves assume a payment at every pillar, such that both are solved
+/// from the same equation.
+#[derive(Clone, Debug)]
 pub enum RateTypes {
     Swap(InterestConventions),
     Discount,
     Spot(InterestConventions),
     Forward(InterestConventions),
+    /// Alias of `Swap`, as a par curve paying at every pillar is the swap curve.
     Par(InterestConventions),
 }
 
+//  --- Traits
+pub trait RateConversion<A> {
+    /// Converts a curve of this rate type into discount factors.
+    fn to_discount(&self, curve: &A) -> Result<A>;
+
+    /// Converts a curve of discount factors into this rate type.
+    fn discount_to(&self, curve: &A) -> Result<A>;
+
+    /// Converts a curve of this rate type into 'other', through discount factors.
+    fn convert(&self, other: &Self, curve: &A) -> Result<A> {
+        other.discount_to(&self.to_discount(curve)?)
+    }
+}
+
+//  --- Trait implementations: Concrete
+
+impl RateConversion<Term<f64>> for RateTypes {
+    fn to_discount(&self, curve: &Term<f64>) -> Result<Term<f64>> {
+        let (x, y) = curve.unpack();
+        let df = match self {
+            Self::Discount => y,
+            Self::Spot(convention) => spot_curve_to_discount(convention, &x, &y),
+            Self::Forward(convention) => forward_curve_to_discount(convention, &x, &y),
+            Self::Swap(convention) | Self::Par(convention) => {
+                swap_curve_to_discount(convention, &x, &y)
+            }
+        };
+        Ok(Term::new(&x, &df))
+    }
+
+    fn discount_to(&self, curve: &Term<f64>) -> Result<Term<f64>> {
+        let (x, df) = curve.unpack();
+        let y = match self {
+            Self::Discount => df,
+            Self::Spot(convention) => discount_to_spot(convention, curve),
+            Self::Forward(convention) => discount_curve_to_forward(convention, &x, &df),
+            Self::Swap(convention) | Self::Par(convention) => {
+                discount_curve_to_swap(convention, &x, &df)?
+            }
+        };
+        Ok(Term::new(&x, &y))
+    }
+}
+
 //  --- Standalone functions
 
 pub fn discount_to_spot(convention: &InterestConventions, curve: &Term<f64>) -> Vec<f64> {
@@ -86,6 +158,7 @@ pub fn discount_to_spot(convention: &InterestConventions, curve: &Term<f64>) ->
         .collect()
 }
 
+/// Forward rates between consecutive pillars, excluding the rate up to the first pillar.
 pub fn discount_to_forward(convention: &InterestConventions, curve: &Term<f64>) -> Vec<f64> {
     curve
         .clone()
@@ -95,13 +168,73 @@ pub fn discount_to_forward(convention: &InterestConventions, curve: &Term<f64>)
         .collect()
 }
 
+/// Period lengths between consecutive pillars, starting from the valuation date.
+fn periods(x: &[f64]) -> Vec<f64> {
+    std::iter::once(0.0)
+        .chain(x.iter().copied())
+        .zip(x.iter())
+        .map(|(a, b)| b - a)
+        .collect()
+}
+
+fn spot_curve_to_discount(convention: &InterestConventions, x: &[f64], y: &[f64]) -> Vec<f64> {
+    x.iter()
+        .zip(y.iter())
+        .map(|(n, r)| spot_to_discount(convention, &(*n, *r)))
+        .collect()
+}
+
+fn forward_curve_to_discount(convention: &InterestConventions, x: &[f64], y: &[f64]) -> Vec<f64> {
+    let mut short_point = (0.0, 0.0);
+    periods(x)
+        .iter()
+        .zip(x.iter().zip(y.iter()))
+        .map(|(period, (n, forward))| {
+            let spot = forward_to_long_spot(convention, &(*period, *forward), &short_point);
+            short_point = (*n, spot);
+            spot_to_discount(convention, &short_point)
+        })
+        .collect()
+}
+
+fn discount_curve_to_forward(convention: &InterestConventions, x: &[f64], df: &[f64]) -> Vec<f64> {
+    std::iter::once(1.0)
+        .chain(df.iter().copied())
+        .zip(df.iter())
+        .zip(periods(x))
+        .map(|((short, long), period)| convention.rate(&period, &(long / short)))
+        .collect()
+}
+
+fn swap_curve_to_discount(convention: &InterestConventions, x: &[f64], y: &[f64]) -> Vec<f64> {
+    let periods = periods(x);
+    let mut df: Vec<f64> = Vec::with_capacity(x.len());
+    for (i, swap_rate) in y.iter().enumerate() {
+        let df_points = (&periods[..i], &df[..i]);
+        df.push(swap_to_discount(
+            convention,
+            &(periods[i], *swap_rate),
+            &df_points,
+        ));
+    }
+    df
+}
+
+fn discount_curve_to_swap(
+    convention: &InterestConventions,
+    x: &[f64],
+    df: &[f64],
+) -> Result<Vec<f64>> {
+    let periods = periods(x);
+    (1..=df.len())
+        .map(|i| discount_to_swap(convention, &(&periods[..i], &df[..i])))
+        .collect()
+}
+
 //  --- Swap rate conversions ---
-// fn swap_to_discount
-// fn swap_to_spot
-// fn swap_to_forward
-// fn swap_to_par
+// Swap curves are converted through discount factors, see 'RateConversion'.
 
-// This works.
+// Where Point = (n, r) of the final payment, and Points = (n, df) of earlier payments.
 pub fn swap_to_discount(
     convention: &InterestConventions,
     swap_point: &Point,
@@ -116,7 +249,7 @@ pub fn swap_to_discount(
         / (convention.fv(&swap_point.0, &swap_point.1))
 }
 
-// Double check this logic.
+// Where Point = (n, r) of the final payment, and Points = (n, r) of earlier payments.
 pub fn swap_to_spot(
     convention: &InterestConventions,
     swap_point: &Point,
@@ -132,26 +265,33 @@ pub fn swap_to_spot(
 }
 
 //  --- Discount rate conversions ---
-// fn discount_to_swap
-// fn discount_to_spot
-// fn discount_to_forward
-// fn discount_to_par
-
-// Where Point = (n, df).
-/*
-fn discount_to_spot(convention: &InterestConventions, point: &Point) -> f64 {
-    convention.rate(&point.0, &point.1)
+// Discount curves are converted into other rate types, see 'RateConversion'.
+
+/// Swap rate that prices a swap, paying at every point, to par.
+/// Where Points = (n, df), and n is the length of each period.
+pub fn discount_to_swap(convention: &InterestConventions, df_points: &Points) -> Result<f64> {
+    let (n, df) = df_points;
+    let annuity: f64 = n.iter().zip(df.iter()).map(|(a, b)| a * b).sum();
+    let simple_rate = (1.0 - df.last().unwrap_or(&1.0)) / annuity;
+
+    match convention {
+        InterestConventions::Simple => Ok(simple_rate),
+        _ => Ok(RootFinder::new()
+            .target(1.0)
+            .tolerance(1e-14)
+            .newton(
+                |swap_rate| discount_and_swap_check(convention, &swap_rate, df_points),
+                simple_rate,
+            )?
+            .root()),
+    }
 }
-    */
 
 //  --- Spot rate conversions ---
-// fn spot_to_swap
-// fn spot_to_discount
-// fn spot_to_forward
-// fn spot_to_par
+// Spot curves are converted through discount factors, see 'RateConversion'.
 
 // Where Point = (n, r).
-fn spot_to_discount(convention: &InterestConventions, point: &Point) -> f64 {
+pub fn spot_to_discount(convention: &InterestConventions, point: &Point) -> f64 {
     convention.pv(&point.0, &point.1)
 }
 // Where Point = (n, r).
@@ -168,12 +308,10 @@ pub fn spot_to_forward(
 }
 
 //  --- Forward rate conversions ---
-// fn forward_to_swap
-// fn forward_to_discount
-// fn forward_to_spot
-// fn forward_to_par
+// Forward curves are converted through discount factors, see 'RateConversion'.
 
-fn forward_to_short_spot(
+// Where forward Point = (period, r), and long Point = (n, r).
+pub fn forward_to_short_spot(
     convention: &InterestConventions,
     forward_point: &Point,
     long_point: &Point,
@@ -185,7 +323,8 @@ fn forward_to_short_spot(
     convention.rate(&n, &pv)
 }
 
-fn forward_to_long_spot(
+// Where forward Point = (period, r), and short Point = (n, r).
+pub fn forward_to_long_spot(
     convention: &InterestConventions,
     forward_point: &Point,
     short_point: &Point,
@@ -198,10 +337,7 @@ fn forward_to_long_spot(
 }
 
 //  --- Par rate conversions ---
-// fn par_to_swap
-// fn par_to_discount
-// fn par_to_spot
-// fn par_to_forward
+// Par rates satisfy the same equation as swap rates, see 'RateConversion'.
 
 //  --- Checks ---
 // (To be used with solver)
@@ -210,7 +346,6 @@ fn forward_to_long_spot(
 //(S/m).Df1 + (S/m).Df2 ... + (1+ S/m)^nm.Dfn = 1
 // Returns the result of the above.
 // Should be 1.0;
-// Double check this logic.
 pub fn discount_and_swap_check(
     convention: &InterestConventions,
     swap_rate: &f64,
@@ -236,7 +371,7 @@ pub fn discount_and_swap_check(
 }
 
 // Should be zero.
-fn forward_and_spot_check(
+pub fn forward_and_spot_check(
     convention: &InterestConventions,
     short_point: &Point,
     forward_point: &Point,
@@ -318,4 +453,126 @@ mod test_interest_types {
             assert_approx_eq!(value_check, 1.0);
         }
     }
+
+    mod test_rate_conversion {
+
+        use super::*;
+
+        fn conventions() -> Vec<InterestConventions> {
+            vec![
+                InterestConventions::Simple,
+                InterestConventions::Discrete(DiscreteCompoundingFrequencies::SemiAnnually),
+                InterestConventions::Continuous,
+            ]
+        }
+
+        fn rate_types(convention: &InterestConventions) -> Vec<RateTypes> {
+            vec![
+                RateTypes::Discount,
+                RateTypes::Spot(convention.clone()),
+                RateTypes::Forward(convention.clone()),
+                RateTypes::Swap(convention.clone()),
+                RateTypes::Par(convention.clone()),
+            ]
+        }
+
+        fn discount_curve() -> Term<f64> {
+            let x = vec![0.25, 0.5, 1.0, 2.0, 3.0, 5.0, 10.0];
+            let df = vec![0.98, 0.961, 0.925, 0.853, 0.786, 0.662, 0.421];
+            Term::new(&x, &df)
+        }
+
+        #[test]
+        fn test_round_trip() {
+            let discount = discount_curve();
+            for convention in conventions() {
+                for from in rate_types(&convention) {
+                    let curve = RateTypes::Discount.convert(&from, &discount).unwrap();
+                    for to in rate_types(&convention) {
+                        let converted = from.convert(&to, &curve).unwrap();
+                        let round_trip = to.convert(&from, &converted).unwrap();
+                        for (a, b) in round_trip.y().iter().zip(curve.y().iter()) {
+                            assert_approx_eq!(a, b, 1e-12);
+                        }
+                        let df = to.to_discount(&converted).unwrap();
+                        for (a, b) in df.y().iter().zip(discount.y().iter()) {
+                            assert_approx_eq!(a, b, 1e-12);
+                        }
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn test_swap_curve_prices_to_par() {
+            let discount = discount_curve();
+            let convention =
+                InterestConventions::Discrete(DiscreteCompoundingFrequencies::Quarterly);
+            let swap = RateTypes::Discount
+                .convert(&RateTypes::Swap(convention.clone()), &discount)
+                .unwrap();
+
+            let (x, df) = discount.unpack();
+            let n = periods(&x);
+            for (i, swap_rate) in swap.y().iter().enumerate() {
+                let value = discount_and_swap_check(&convention, swap_rate, &(&n[..=i], &df[..=i]));
+                assert_approx_eq!(value, 1.0, 1e-12);
+            }
+        }
+
+        #[test]
+        fn test_solver_error() {
+            let discount = Term::new(&[1.0, 2.0], &[0.95, f64::NAN]);
+            let result = RateTypes::Discount
+                .convert(&RateTypes::Swap(InterestConventions::Continuous), &discount);
+            assert!(matches!(result, Err(Error::SolverError(_))));
+        }
+
+        #[test]
+        fn test_first_rates_coincide() {
+            // Swap, spot and forward rates to the first pillar are the same.
+            let discount = discount_curve();
+            let convention = InterestConventions::Continuous;
+            let spot = RateTypes::Discount
+                .convert(&RateTypes::Spot(convention.clone()), &discount)
+                .unwrap();
+            let forward = RateTypes::Discount
+                .convert(&RateTypes::Forward(convention.clone()), &discount)
+                .unwrap();
+            assert_approx_eq!(spot.y()[0], forward.y()[0], 1e-12);
+
+            let convention = InterestConventions::Simple;
+            let spot = RateTypes::Discount
+                .convert(&RateTypes::Spot(convention.clone()), &discount)
+                .unwrap();
+            let swap = RateTypes::Discount
+                .convert(&RateTypes::Swap(convention), &discount)
+                .unwrap();
+            assert_approx_eq!(spot.y()[0], swap.y()[0], 1e-12);
+        }
+
+        #[test]
+        fn test_forward_and_spot_points() {
+            let convention =
+                InterestConventions::Discrete(DiscreteCompoundingFrequencies::Annually);
+            let short_point = (1.0, 0.07);
+            let long_point = (3.0, 0.08);
+            let forward = spot_to_forward(&convention, &short_point, &long_point);
+            let forward_point = (2.0, forward);
+
+            let check =
+                forward_and_spot_check(&convention, &short_point, &forward_point, &long_point);
+            assert_approx_eq!(check, 0.0, 1e-12);
+            assert_approx_eq!(
+                forward_to_long_spot(&convention, &forward_point, &short_point),
+                long_point.1,
+                1e-12
+            );
+            assert_approx_eq!(
+                forward_to_short_spot(&convention, &forward_point, &long_point),
+                short_point.1,
+                1e-12
+            );
+        }
+    }
 }
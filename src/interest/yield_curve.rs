@@ -0,0 +1,403 @@
+//! Yield curves.
+//!
+//! A yield curve holds discount factors at pillars, keyed by the time from its
+//! reference date under its day count, and answers date based queries. Between
+//! pillars, the curve interpolates the chosen quantity (discount factors, zero rates
+//! or log discount factors), which is converted back into a discount factor.
+//!
+//! ```rust,ignore
+//! let curve = YieldCurve::from_days(valuation_date, &load_curve("zar_disc_csa_irs")?, dcc)
+//!     .interpolation_method(InterpolationMethod::Linear)
+//!     .quantity(CurveQuantities::LogDiscountFactor);
+//!
+//! let df = curve.discount(&payment_date);
+//! let forward = curve.forward_rate(&start, &end, &dcc, &InterestConventions::Simple);
+//! ```
+
+use std::collections::BTreeMap;
+
+use chrono::{Days, NaiveDate};
+
+use super::ops::{InterestConventions, TimeValueOfMoney};
+use super::term_structure::{Term, TermStructure};
+use crate::conventions::day_count::{DayCount, DayCountConventions};
+use crate::math::interpolation::{
+    Error, Extrapolate, ExtrapolationMethod, InterpolationMethod, Interpolator, Interpolators,
+    TryInterpolate,
+};
+
+//  --- Constants
+
+/// Step, in years, used to differentiate the curve.
+const STEP: f64 = 1e-4;
+
+//  --- Enums
+
+/// Quantity interpolated between pillars.
+#[derive(Clone, Debug, Default)]
+pub enum CurveQuantities {
+    #[default]
+    DiscountFactor,
+    ZeroRate(InterestConventions),
+    LogDiscountFactor,
+}
+
+//  --- Structs
+#[derive(Clone, Debug)]
+pub struct YieldCurve {
+    reference_date: NaiveDate,
+    day_count: DayCountConventions,
+    interpolation_method: InterpolationMethod,
+    extrapolation: ExtrapolationMethod,
+    quantity: CurveQuantities,
+    pillars: Term<f64>,
+    values: Vec<f64>,
+    interpolator: Option<Interpolators>,
+}
+
+//  --- Implementations
+
+impl CurveQuantities {
+    fn of_discount(&self, t: &f64, df: &f64) -> f64 {
+        match self {
+            Self::DiscountFactor => *df,
+            Self::ZeroRate(convention) => convention.rate(t, df),
+            Self::LogDiscountFactor => df.ln(),
+        }
+    }
+
+    fn to_discount(&self, t: &f64, value: &f64) -> f64 {
+        match self {
+            Self::DiscountFactor => *value,
+            Self::ZeroRate(convention) => convention.pv(t, value),
+            Self::LogDiscountFactor => value.exp(),
+        }
+    }
+}
+
+impl YieldCurve {
+    /// Curve of discount factors, keyed by the time in years from 'reference_date'.
+    /// A discount factor of one is added at the reference date, if not already present.
+    pub fn new(reference_date: NaiveDate, discount_factors: &Term<f64>) -> Self {
+        let (mut x, mut y) = discount_factors.unpack();
+        if x.first().is_none_or(|t| *t > 0.0) {
+            x.insert(0, 0.0);
+            y.insert(0, 1.0);
+        }
+
+        let mut curve = Self {
+            reference_date,
+            day_count: DayCountConventions::default(),
+            interpolation_method: InterpolationMethod::default(),
+            extrapolation: ExtrapolationMethod::default(),
+            quantity: CurveQuantities::default(),
+            pillars: Term::new(&x, &y),
+            values: Vec::new(),
+            interpolator: None,
+        };
+        curve.update_values();
+        curve
+    }
+
+    /// Curve of discount factors, keyed by the number of days from 'reference_date'.
+    pub fn from_days(
+        reference_date: NaiveDate,
+        discount_factors: &BTreeMap<u32, f64>,
+        day_count: DayCountConventions,
+    ) -> Self {
+        let (x, y): (Vec<f64>, Vec<f64>) = discount_factors
+            .iter()
+            .filter_map(|(days, df)| {
+                let date = reference_date.checked_add_days(Days::new(*days as u64))?;
+                Some((day_count.year_fraction(&reference_date, &date), *df))
+            })
+            .unzip();
+
+        Self::new(reference_date, &Term::new(&x, &y)).day_count(day_count)
+    }
+
+    /// Day count used to convert dates into times. Pillar times are assumed
+    /// to follow the same day count.
+    pub fn day_count(self, day_count: DayCountConventions) -> Self {
+        Self { day_count, ..self }
+    }
+
+    /// Splines, Hermite and monotone convex interpolants are fit once, when set.
+    pub fn interpolation_method(self, interpolation_method: InterpolationMethod) -> Self {
+        let mut curve = Self {
+            interpolation_method,
+            ..self
+        };
+        curve.update_values();
+        curve
+    }
+
+    /// Policy beyond the last pillar. Flat forward and Smith-Wilson extrapolate the
+    /// discount factors, while other policies extrapolate the interpolated quantity.
+    pub fn extrapolation(self, extrapolation: ExtrapolationMethod) -> Self {
+        Self {
+            extrapolation,
+            ..self
+        }
+    }
+
+    /// Quantity interpolated between pillars.
+    pub fn quantity(self, quantity: CurveQuantities) -> Self {
+        let mut curve = Self { quantity, ..self };
+        curve.update_values();
+        curve
+    }
+
+    pub fn reference_date(&self) -> NaiveDate {
+        self.reference_date
+    }
+
+    /// Discount factors at each pillar, keyed by time.
+    pub fn pillars(&self) -> &Term<f64> {
+        &self.pillars
+    }
+
+    /// Time in years from the reference date.
+    pub fn time(&self, date: &NaiveDate) -> f64 {
+        self.day_count.year_fraction(&self.reference_date, date)
+    }
+
+    /// Discount factor 't' years from the reference date. Times on or before
+    /// the reference date are not discounted. Errors from the extrapolation
+    /// policy are returned as NaN, use 'try_discount_at' to report them.
+    pub fn discount_at(&self, t: &f64) -> f64 {
+        self.try_discount_at(t).unwrap_or(f64::NAN)
+    }
+
+    pub fn try_discount_at(&self, t: &f64) -> Result<f64, Error> {
+        if *t <= 0.0 {
+            return Ok(1.0);
+        }
+
+        let x = self.pillars.x();
+        if x.last().is_some_and(|last| t > last) {
+            return match self.extrapolation {
+                ExtrapolationMethod::FlatForward | ExtrapolationMethod::SmithWilson { .. } => {
+                    let df = self.pillars.y();
+                    InterpolationMethod::LogLinear.extrapolate(&x, &df, t, &self.extrapolation)
+                }
+                _ => {
+                    let value = self.interpolation_method.extrapolate(
+                        &x,
+                        &self.values,
+                        t,
+                        &self.extrapolation,
+                    )?;
+                    Ok(self.quantity.to_discount(t, &value))
+                }
+            };
+        }
+
+        let value = match &self.interpolator {
+            Some(interpolator) => interpolator.value(t),
+            None => self
+                .interpolation_method
+                .try_interpolate(&x, &self.values, t)?,
+        };
+        Ok(self.quantity.to_discount(t, &value))
+    }
+
+    pub fn discount(&self, date: &NaiveDate) -> f64 {
+        self.discount_at(&self.time(date))
+    }
+
+    pub fn try_discount(&self, date: &NaiveDate) -> Result<f64, Error> {
+        self.try_discount_at(&self.time(date))
+    }
+
+    /// Zero rate from the reference date to 'date'.
+    pub fn zero_rate(&self, date: &NaiveDate, convention: &InterestConventions) -> f64 {
+        let t = self.time(date);
+        if t <= 0.0 {
+            return convention.rate(&STEP, &self.discount_at(&STEP));
+        }
+        convention.rate(&t, &self.discount_at(&t))
+    }
+
+    /// Forward rate between 'start' and 'end', accruing under 'day_count'.
+    pub fn forward_rate(
+        &self,
+        start: &NaiveDate,
+        end: &NaiveDate,
+        day_count: &DayCountConventions,
+        convention: &InterestConventions,
+    ) -> f64 {
+        let n = day_count.year_fraction(start, end);
+        convention.rate(&n, &(self.discount(end) / self.discount(start)))
+    }
+
+    /// Continuously compounded instantaneous forward rate 't' years from the reference date.
+    pub fn instantaneous_forward(&self, t: f64) -> f64 {
+        let start = (t - STEP).max(0.0);
+        let end = start + 2.0 * STEP;
+        -(self.discount_at(&end).ln() - self.discount_at(&start).ln()) / (end - start)
+    }
+
+    fn update_values(&mut self) {
+        let (x, df) = self.pillars.unpack();
+        self.values = x
+            .iter()
+            .zip(df.iter())
+            .map(|(t, df)| self.quantity.of_discount(t, df))
+            .collect();
+
+        // Rates are undefined at the reference date, and are held flat from the first pillar.
+        if let CurveQuantities::ZeroRate(_) = self.quantity {
+            if x.first() == Some(&0.0) && self.values.len() > 1 {
+                self.values[0] = self.values[1];
+            }
+        }
+
+        self.interpolator = self
+            .interpolation_method
+            .interpolator(&x, &self.values)
+            .ok();
+    }
+}
+
+//  --- Unit tests
+#[cfg(test)]
+mod test_yield_curve {
+
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::interest::ops::DiscreteCompoundingFrequencies;
+    use crate::math::splines::SplineBoundaries;
+    use crate::test_utils::date;
+
+    const RATE: f64 = 0.06;
+
+    fn flat_curve() -> YieldCurve {
+        let x = vec![0.5, 1.0, 2.0, 5.0, 10.0];
+        let y: Vec<f64> = x.iter().map(|t| (-RATE * t).exp()).collect();
+        YieldCurve::new(date(2024, 1, 15), &Term::new(&x, &y))
+    }
+
+    #[test]
+    fn test_flat_curve() {
+        let curve = flat_curve();
+        let continuous = InterestConventions::Continuous;
+        let dcc = DayCountConventions::default();
+
+        assert_approx_eq!(curve.discount(&date(2024, 1, 15)), 1.0);
+        assert_approx_eq!(curve.zero_rate(&date(2027, 3, 1), &continuous), RATE, 1e-12);
+        assert_approx_eq!(
+            curve.zero_rate(&date(2024, 1, 15), &continuous),
+            RATE,
+            1e-12
+        );
+        assert_approx_eq!(
+            curve.forward_rate(&date(2025, 6, 1), &date(2026, 6, 1), &dcc, &continuous),
+            RATE,
+            1e-12
+        );
+        assert_approx_eq!(curve.instantaneous_forward(3.3), RATE, 1e-9);
+        assert_approx_eq!(curve.instantaneous_forward(0.0), RATE, 1e-9);
+    }
+
+    #[test]
+    fn test_forward_rate_simple() {
+        let curve = flat_curve();
+        let dcc = DayCountConventions::default();
+        let (start, end) = (date(2025, 1, 15), date(2025, 4, 15));
+
+        let forward = curve.forward_rate(&start, &end, &dcc, &InterestConventions::Simple);
+        let n = dcc.year_fraction(&start, &end);
+        assert_approx_eq!(
+            curve.discount(&start) / (1.0 + forward * n),
+            curve.discount(&end),
+            1e-12
+        );
+    }
+
+    #[test]
+    fn test_quantities() {
+        let t = 3.7;
+        let log_linear = flat_curve();
+        let linear_log_df = flat_curve()
+            .interpolation_method(InterpolationMethod::Linear)
+            .quantity(CurveQuantities::LogDiscountFactor);
+        assert_approx_eq!(
+            log_linear.discount_at(&t),
+            linear_log_df.discount_at(&t),
+            1e-12
+        );
+
+        // Linear zero rates reproduce a flat rate.
+        let convention = InterestConventions::Discrete(DiscreteCompoundingFrequencies::Annually);
+        let x = vec![1.0, 2.0, 5.0];
+        let y: Vec<f64> = x.iter().map(|t| convention.pv(t, &RATE)).collect();
+        let curve = YieldCurve::new(date(2024, 1, 15), &Term::new(&x, &y))
+            .interpolation_method(InterpolationMethod::Linear)
+            .quantity(CurveQuantities::ZeroRate(convention.clone()));
+        assert_approx_eq!(curve.discount_at(&t), convention.pv(&t, &RATE), 1e-12);
+        assert_approx_eq!(curve.discount_at(&0.25), convention.pv(&0.25, &RATE), 1e-12);
+    }
+
+    #[test]
+    fn test_from_days() {
+        let reference = date(2024, 1, 15);
+        let days = BTreeMap::from([(90, 0.985), (365, 0.94), (730, 0.88)]);
+        let curve = YieldCurve::from_days(reference, &days, DayCountConventions::Actual365Fixed);
+
+        assert_eq!(curve.pillars().x(), vec![0.0, 90.0 / 365.0, 1.0, 2.0]);
+        assert_approx_eq!(curve.discount(&date(2025, 1, 14)), 0.94, 1e-12);
+        assert!(curve.discount(&date(2025, 7, 15)) < 0.94);
+    }
+
+    #[test]
+    fn test_extrapolation() {
+        // Linear discount factors would otherwise turn negative.
+        let curve = flat_curve().interpolation_method(InterpolationMethod::Linear);
+        assert_approx_eq!(curve.discount_at(&30.0), (-RATE * 30.0).exp(), 1e-12);
+        assert_approx_eq!(curve.instantaneous_forward(25.0), RATE, 1e-9);
+
+        let curve = flat_curve().extrapolation(ExtrapolationMethod::Error);
+        assert!(curve.try_discount(&date(2054, 1, 15)).is_err());
+        assert!(curve.discount_at(&30.0).is_nan());
+        assert!(curve.try_discount(&date(2030, 1, 15)).is_ok());
+
+        let ufr_intensity = 0.045;
+        let curve = flat_curve().extrapolation(ExtrapolationMethod::SmithWilson {
+            ufr_intensity,
+            alpha: 0.15,
+        });
+        assert_approx_eq!(curve.discount_at(&10.0), (-RATE * 10.0).exp(), 1e-12);
+        assert_approx_eq!(curve.instantaneous_forward(120.0), ufr_intensity, 1e-4);
+    }
+
+    #[test]
+    fn test_spline_forwards_are_smooth() {
+        let x = vec![0.25, 0.5, 1.0, 2.0, 3.0, 5.0, 10.0];
+        let zero_rates = [0.071, 0.073, 0.076, 0.079, 0.082, 0.087, 0.095];
+        let y: Vec<f64> = x
+            .iter()
+            .zip(zero_rates.iter())
+            .map(|(t, r): (&f64, &f64)| (-r * t).exp())
+            .collect();
+
+        for quantity in [
+            CurveQuantities::ZeroRate(InterestConventions::Continuous),
+            CurveQuantities::LogDiscountFactor,
+        ] {
+            let curve = YieldCurve::new(date(2024, 1, 15), &Term::new(&x, &y))
+                .quantity(quantity)
+                .interpolation_method(InterpolationMethod::CubicSpline(SplineBoundaries::Natural));
+
+            for (t, df) in x.iter().zip(y.iter()) {
+                assert_approx_eq!(curve.discount_at(t), df, 1e-12);
+            }
+            // Forward rates either side of each pillar coincide.
+            for t in &x[..x.len() - 1] {
+                let left = curve.instantaneous_forward(t - 1e-3);
+                let right = curve.instantaneous_forward(t + 1e-3);
+                assert_approx_eq!(left, right, 1e-3);
+            }
+        }
+    }
+}
@@ -23,9 +23,15 @@ pub mod core {
 }
 
 pub mod interest {
+    pub mod bootstrap;
+    pub mod fixings;
     pub mod ops;
+    pub mod overnight;
+    pub mod smith_wilson;
+    pub mod swap;
     pub mod term_structure;
     pub mod types;
+    pub mod yield_curve;
 }
 
 pub mod country;
@@ -38,6 +44,9 @@ pub mod macros {
 
 pub mod math {
     pub mod interpolation;
+    pub mod linalg;
+    pub mod optimise;
+    pub mod parametric;
     pub mod poly;
     pub mod solver;
     pub mod splines;
@@ -49,6 +58,12 @@ pub mod resources {
 }
 
 pub mod time {
+    pub mod calendar;
     pub mod ops;
     pub mod periods;
+    pub mod roll;
+    pub mod schedule;
 }
+
+#[cfg(test)]
+mod test_utils;
@@ -0,0 +1,222 @@
+//! Cubic Hermite interpolation.
+//!
+//! On each interval [xi, xi+1], the interpolant is the cubic matching the values
+//! and the slopes mi at both knots. The methods differ only in how slopes are set:
+//! - Hyman: three point (parabolic) slopes, filtered such that the interpolant is
+//!   monotone wherever the data is monotone (Hyman, 1983).
+//! - Akima: a weighted average of neighbouring secant slopes, which limits
+//!   overshooting near outliers (Akima, 1970).
+
+use super::{validate_points, Interpolator, Result};
+
+//  --- Structs
+
+#[derive(Clone, Debug)]
+pub struct HermiteSpline {
+    x: Vec<f64>,
+    y: Vec<f64>,
+    slopes: Vec<f64>,
+}
+
+//  --- Implementations
+
+impl HermiteSpline {
+    /// Hermite spline with the given slopes at each knot.
+    pub fn new(x: &[f64], y: &[f64], slopes: &[f64]) -> Result<Self> {
+        validate_points(x, y)?;
+        validate_points(x, slopes)?;
+        Ok(Self {
+            x: x.to_vec(),
+            y: y.to_vec(),
+            slopes: slopes.to_vec(),
+        })
+    }
+
+    /// Hermite spline with Hyman filtered parabolic slopes.
+    pub fn hyman(x: &[f64], y: &[f64]) -> Result<Self> {
+        validate_points(x, y)?;
+        let (h, delta) = secants(x, y);
+        let n = h.len();
+
+        let mut slopes = vec![0.0; n + 1];
+        if n == 1 {
+            slopes.fill(delta[0]);
+        } else {
+            slopes[0] = ((2.0 * h[0] + h[1]) * delta[0] - h[0] * delta[1]) / (h[0] + h[1]);
+            for i in 1..n {
+                slopes[i] = (h[i] * delta[i - 1] + h[i - 1] * delta[i]) / (h[i - 1] + h[i]);
+            }
+            slopes[n] = ((2.0 * h[n - 1] + h[n - 2]) * delta[n - 1] - h[n - 1] * delta[n - 2])
+                / (h[n - 2] + h[n - 1]);
+        }
+
+        // Hyman filter.
+        slopes[0] = limit_slope(slopes[0], delta[0], delta[0]);
+        for i in 1..n {
+            slopes[i] = limit_slope(slopes[i], delta[i - 1], delta[i]);
+        }
+        slopes[n] = limit_slope(slopes[n], delta[n - 1], delta[n - 1]);
+
+        Self::new(x, y, &slopes)
+    }
+
+    /// Hermite spline with Akima slopes. Secants beyond the ends are extrapolated linearly.
+    pub fn akima(x: &[f64], y: &[f64]) -> Result<Self> {
+        validate_points(x, y)?;
+        let (_, delta) = secants(x, y);
+        let n = delta.len();
+
+        // Extended secants, where extended[i + 2] = delta[i].
+        let mut extended = vec![0.0; n + 4];
+        extended[2..n + 2].copy_from_slice(&delta);
+        let (first, second) = (delta[0], delta.get(1).copied().unwrap_or(delta[0]));
+        extended[1] = 2.0 * first - second;
+        extended[0] = 2.0 * extended[1] - first;
+        let (last, second_last) = (
+            delta[n - 1],
+            delta.get(n - 2).copied().unwrap_or(delta[n - 1]),
+        );
+        extended[n + 2] = 2.0 * last - second_last;
+        extended[n + 3] = 2.0 * extended[n + 2] - last;
+
+        let slopes: Vec<f64> = (0..=n)
+            .map(|i| {
+                let (d0, d1, d2, d3) = (
+                    extended[i],
+                    extended[i + 1],
+                    extended[i + 2],
+                    extended[i + 3],
+                );
+                let (w1, w2) = ((d3 - d2).abs(), (d1 - d0).abs());
+                if w1 + w2 == 0.0 {
+                    (d1 + d2) / 2.0
+                } else {
+                    (w1 * d1 + w2 * d2) / (w1 + w2)
+                }
+            })
+            .collect();
+
+        Self::new(x, y, &slopes)
+    }
+
+    pub fn slopes(&self) -> &[f64] {
+        &self.slopes
+    }
+
+    /// Index of the interval containing 'xp', its width, and the position of
+    /// 'xp' within it, scaled to [0, 1].
+    fn segment(&self, xp: &f64) -> (usize, f64, f64) {
+        let i = self
+            .x
+            .partition_point(|a| a <= xp)
+            .saturating_sub(1)
+            .min(self.x.len() - 2);
+        let h = self.x[i + 1] - self.x[i];
+        (i, h, (xp - self.x[i]) / h)
+    }
+}
+
+//  --- Trait implementations: Concrete
+
+impl Interpolator for HermiteSpline {
+    /// Points outside the knots are extrapolated with the end cubics.
+    fn value(&self, xp: &f64) -> f64 {
+        let (i, h, t) = self.segment(xp);
+        let (t2, t3) = (t * t, t * t * t);
+        (2.0 * t3 - 3.0 * t2 + 1.0) * self.y[i]
+            + (t3 - 2.0 * t2 + t) * h * self.slopes[i]
+            + (-2.0 * t3 + 3.0 * t2) * self.y[i + 1]
+            + (t3 - t2) * h * self.slopes[i + 1]
+    }
+
+    fn derivative(&self, xp: &f64) -> f64 {
+        let (i, h, t) = self.segment(xp);
+        let t2 = t * t;
+        (6.0 * t2 - 6.0 * t) / h * self.y[i]
+            + (3.0 * t2 - 4.0 * t + 1.0) * self.slopes[i]
+            + (-6.0 * t2 + 6.0 * t) / h * self.y[i + 1]
+            + (3.0 * t2 - 2.0 * t) * self.slopes[i + 1]
+    }
+}
+
+//  --- Standalone functions
+
+/// Interval widths and secant slopes.
+fn secants(x: &[f64], y: &[f64]) -> (Vec<f64>, Vec<f64>) {
+    let h: Vec<f64> = x.windows(2).map(|a| a[1] - a[0]).collect();
+    let delta = y
+        .windows(2)
+        .zip(h.iter())
+        .map(|(a, h)| (a[1] - a[0]) / h)
+        .collect();
+    (h, delta)
+}
+
+/// Limits 'slope' at a knot between secants 'left' and 'right', such that the
+/// interpolant is monotone. Slopes at extrema are set to zero.
+fn limit_slope(slope: f64, left: f64, right: f64) -> f64 {
+    if left * right <= 0.0 || slope * left <= 0.0 {
+        return 0.0;
+    }
+    let bound = 3.0 * left.abs().min(right.abs());
+    slope.signum() * slope.abs().min(bound)
+}
+
+//  --- Unit tests
+#[cfg(test)]
+mod test_hermite {
+
+    use super::*;
+    use crate::assert_approx_eq;
+
+    #[test]
+    fn test_interpolates_knots() -> Result<()> {
+        let x = vec![0.0, 1.0, 2.5, 4.0, 5.0];
+        let y = vec![1.0, 2.0, 2.2, 5.0, 4.0];
+        for spline in [HermiteSpline::hyman(&x, &y)?, HermiteSpline::akima(&x, &y)?] {
+            for (a, b) in x.iter().zip(y.iter()) {
+                assert_approx_eq!(spline.value(a), b, 1e-12);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_hyman_preserves_monotonicity() -> Result<()> {
+        // Steps, on which unfiltered slopes overshoot.
+        let x = vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0];
+        let y = vec![0.0, 0.0, 0.1, 1.0, 1.0, 1.0];
+        let spline = HermiteSpline::hyman(&x, &y)?;
+
+        let grid: Vec<f64> = (0..=500).map(|i| i as f64 / 100.0).collect();
+        for xp in grid {
+            assert!(spline.derivative(&xp) >= -1e-12);
+            assert!(spline.value(&xp) >= -1e-12 && spline.value(&xp) <= 1.0 + 1e-12);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_reproduces_linear_data() -> Result<()> {
+        let x = vec![0.0, 0.5, 2.0, 3.0];
+        let y: Vec<f64> = x.iter().map(|a| 2.0 * a + 1.0).collect();
+        for spline in [HermiteSpline::hyman(&x, &y)?, HermiteSpline::akima(&x, &y)?] {
+            assert_approx_eq!(spline.value(&1.3), 3.6, 1e-12);
+            assert_approx_eq!(spline.derivative(&2.7), 2.0, 1e-12);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_derivative() -> Result<()> {
+        let x = vec![0.0, 1.0, 2.0, 3.0];
+        let y = vec![0.0, 1.0, 4.0, 9.0];
+        let spline = HermiteSpline::akima(&x, &y)?;
+        let eps = 1e-6;
+        for xp in [0.3, 1.5, 2.8] {
+            let numerical = (spline.value(&(xp + eps)) - spline.value(&(xp - eps))) / (2.0 * eps);
+            assert_approx_eq!(spline.derivative(&xp), numerical, 1e-6);
+        }
+        Ok(())
+    }
+}
@@ -1,56 +1,132 @@
 #![deny(clippy::unwrap_used)]
 #![deny(clippy::expect_used)]
 
-// TODO if xp = 0, return 1.0.
 // TODO complete this module.
 // TODO implement unit tests.
 // TODO use WolframAlpha to create unit tests.
 // TODO first x and y values should always be x0 and y0. Make these updated in the code.
 // TODO, fit curves taking into account all available x and y points, don't do it on isolated points.
 
-// To solve for the coefficients of polynomial functions, you generally use systems of linear equations,
-// especially when you have a set of points that the polynomial must pass through.
-// For a polynomial of degree 𝑛, you need 𝑛 + 1 points to determine the coefficients uniquely.
-// Example:
-// - Lagrange Interpolation
-// - Newton Interpolation
-
-// Lagrange interpolation provides a way to fit a polynomial to a set of data points by leveraging basis
-// polynomials that are constructed to pass through each point exactly. It’s particularly useful for
-// constructing polynomials from a small number of data points.
+// Polynomials through all points (Lagrange, Newton and barycentric forms), and least
+// squares polynomials, are in 'math::poly'.
 
 // Nelson-Siegel.
 // Fit Nelson-Siegel similar to how a neural network parameters are set.
 
-// Research 'Lagrange Interpolation'.
+pub mod hermite;
+pub mod monotone_convex;
+pub mod smith_wilson;
 
-// https://medium.com/theleanprogrammer/polynomial-curve-fitting-in-machine-learning-aa0c967d789b
+use super::parametric::{self, CurveFitter, ParametricCurve, ParametricModels};
+use super::splines::{self, CubicSpline, SplineBoundaries};
+use hermite::HermiteSpline;
+use monotone_convex::MonotoneConvex;
+use smith_wilson::SmithWilson;
 
 //  --- Errors
+type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+    MismatchedLengths(usize, usize),
+    InsufficientPoints(usize),
+    UnorderedPoints,
+    SingularSystem,
+    UnsupportedMethod(InterpolationMethod),
+    OutOfRange(f64),
+    SplineError(splines::Error),
+    ParametricError(parametric::Error),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MismatchedLengths(x, y) => {
+                write!(f, "x has {} points, while y has {} points.", x, y)
+            }
+            Self::InsufficientPoints(n) => {
+                write!(f, "At least 2 points are required, {} were given.", n)
+            }
+            Self::UnorderedPoints => write!(f, "x should be strictly increasing."),
+            Self::SingularSystem => write!(f, "The interpolation equations are singular."),
+            Self::OutOfRange(xp) => write!(f, "{} is outside the range of x.", xp),
+            Self::UnsupportedMethod(method) => {
+                write!(f, "{:?} can not be fit to the points in advance.", method)
+            }
+            Self::SplineError(err) => write!(f, "{}", err),
+            Self::ParametricError(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<splines::Error> for Error {
+    fn from(value: splines::Error) -> Self {
+        Self::SplineError(value)
+    }
+}
+
+impl From<parametric::Error> for Error {
+    fn from(value: parametric::Error) -> Self {
+        Self::ParametricError(value)
+    }
+}
 
 //  --- Types
 type QuadraticPoints<'a> = (&'a f64, &'a f64, &'a f64);
-type CubicPoints<'a> = (&'a f64, &'a f64, &'a f64, &'a f64);
 
 //  --- Enums
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, Default)]
 pub enum InterpolationMethod {
     Linear,
     PiecewiseLinear,
+    // Parametric curves, fit to all points by least squares, such that the points are
+    // not reproduced exactly. Use 'math::parametric::CurveFitter' for weights, bounds
+    // and fit diagnostics.
     NelsonSiegel,
     NelsonSiegelSvensson,
-    CubicSpline,  // Focuses on smoothness between intervals.
-    CubicHermite, // Focuses on fitting both function and derivatives.
+    // Focuses on smoothness between intervals. The coefficients are solved on each call,
+    // use 'math::splines::CubicSpline' directly to evaluate many points.
+    CubicSpline(SplineBoundaries),
+    // Hermite cubics with Hyman filtered slopes, monotone wherever the data is monotone.
+    CubicHermite,
+    #[default]
     LogLinear,
     Quadratic,
     Exponential,
     Akima,
+    // Hagan-West, on the integral of the forward, such as -ln(DF) or r.t.
+    MonotoneConvex,
 }
 
-impl Default for InterpolationMethod {
-    fn default() -> Self {
-        Self::LogLinear
-    }
+/// Treatment of points outside the range of 'x'.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ExtrapolationMethod {
+    /// The first or last 'y'. Suited to rates, which are not anchored at 'x' = 0.
+    Flat,
+    /// Extends the first or last interval linearly.
+    Linear,
+    /// Treats 'y' as discount factors, holding the forward of the first or last interval
+    /// flat. Before the first point, the interval starts from a discount factor of one
+    /// at 'x' = 0, if 'x' does not already include it.
+    #[default]
+    FlatForward,
+    /// Treats 'y' as discount factors, converging to the ultimate forward rate at a
+    /// speed set by alpha. 'ufr_intensity' is continuously compounded, that is
+    /// ln(1 + UFR) of an annually compounded UFR.
+    SmithWilson { ufr_intensity: f64, alpha: f64 },
+    /// Points outside the range are an error.
+    Error,
+}
+
+/// Interpolants fit to all points in advance.
+#[derive(Clone, Debug)]
+pub enum Interpolators {
+    CubicSpline(CubicSpline),
+    Hermite(HermiteSpline),
+    MonotoneConvex(MonotoneConvex),
+    Parametric(ParametricCurve),
 }
 
 //  --- Structs
@@ -60,40 +136,63 @@ pub struct Quadratic;
 pub struct Exponential;
 
 //  --- Traits
+
+/// Interpolation within the range of 'x'. Points outside the range follow
+/// 'ExtrapolationMethod::Error', and are not a number, use 'Extrapolate' to choose
+/// another policy.
 pub trait Interpolate<A, B> {
     fn interpolate(&self, x: &A, y: &A, xp: &B) -> B;
 }
 
-//  --- Trait implementations: Concrete
+/// Interpolation which may fail, such as when fitting all points at once, or for
+/// points outside the range of 'x'.
+pub trait TryInterpolate<A, B> {
+    fn try_interpolate(&self, x: &A, y: &A, xp: &B) -> Result<B>;
+}
 
-impl Interpolate<Vec<f64>, f64> for InterpolationMethod {
-    fn interpolate(&self, x: &Vec<f64>, y: &Vec<f64>, xp: &f64) -> f64 {
+/// Interpolation within the range of 'x', and an explicit policy outside of it.
+pub trait Extrapolate<A, B> {
+    fn extrapolate(&self, x: &A, y: &A, xp: &B, extrapolation: &ExtrapolationMethod) -> Result<B>;
+}
+
+/// Interpolant with a known derivative.
+pub trait Interpolator {
+    fn value(&self, xp: &f64) -> f64;
+    fn derivative(&self, xp: &f64) -> f64;
+}
+
+//  --- Implementations
+
+impl InterpolationMethod {
+    /// Fits the interpolant to 'x' and 'y', such that many points can be evaluated.
+    /// Only methods that fit all points at once are supported.
+    pub fn interpolator(&self, x: &[f64], y: &[f64]) -> Result<Interpolators> {
         match self {
-            Self::Linear => Linear.interpolate(x, y, xp),
-            Self::PiecewiseLinear => todo!(),
-            Self::NelsonSiegel => todo!(),
-            Self::NelsonSiegelSvensson => todo!(),
-            Self::CubicSpline => todo!(),
-            Self::CubicHermite => todo!(),
-            Self::LogLinear => LogLinear.interpolate(x, y, xp),
-            Self::Quadratic => Quadratic.interpolate(x, y, xp),
-            Self::Exponential => Exponential.interpolate(x, y, xp),
-            Self::Akima => todo!(),
+            Self::CubicSpline(boundary) => Ok(Interpolators::CubicSpline(CubicSpline::new(
+                x, y, *boundary,
+            )?)),
+            Self::CubicHermite => Ok(Interpolators::Hermite(HermiteSpline::hyman(x, y)?)),
+            Self::Akima => Ok(Interpolators::Hermite(HermiteSpline::akima(x, y)?)),
+            Self::MonotoneConvex => Ok(Interpolators::MonotoneConvex(MonotoneConvex::new(x, y)?)),
+            Self::NelsonSiegel => Ok(Interpolators::Parametric(
+                CurveFitter::new(ParametricModels::NelsonSiegel)
+                    .fit(x, y)?
+                    .curve()
+                    .clone(),
+            )),
+            Self::NelsonSiegelSvensson => Ok(Interpolators::Parametric(
+                CurveFitter::new(ParametricModels::NelsonSiegelSvensson)
+                    .fit(x, y)?
+                    .curve()
+                    .clone(),
+            )),
+            _ => Err(Error::UnsupportedMethod(*self)),
         }
     }
 }
 
-impl Interpolate<Vec<f64>, f64> for Linear {
-    fn interpolate(&self, x: &Vec<f64>, y: &Vec<f64>, xp: &f64) -> f64 {
-        // TODO is the below condition appropriate?
-        if xp <= &0.0 {
-            if xp < &0.0 {
-                return 0.0;
-            } else {
-                return 1.0;
-            }
-        };
-
+impl Linear {
+    fn value(&self, x: &[f64], y: &[f64], xp: &f64) -> f64 {
         let index = partition_index(x, xp);
         let (x1, x2) = (x[index], x[index + 1]);
         let (y1, y2) = (y[index], y[index + 1]);
@@ -102,16 +201,8 @@ impl Interpolate<Vec<f64>, f64> for Linear {
     }
 }
 
-impl Interpolate<Vec<f64>, f64> for LogLinear {
-    fn interpolate(&self, x: &Vec<f64>, y: &Vec<f64>, xp: &f64) -> f64 {
-        if xp <= &0.0 {
-            if xp < &0.0 {
-                return 0.0;
-            } else {
-                return 1.0;
-            }
-        };
-
+impl LogLinear {
+    fn value(&self, x: &[f64], y: &[f64], xp: &f64) -> f64 {
         let index = partition_index(x, xp);
         let (x1, x2) = (x[index], x[index + 1]);
         let (y1, y2) = (y[index], y[index + 1]);
@@ -121,17 +212,15 @@ impl Interpolate<Vec<f64>, f64> for LogLinear {
     }
 }
 
-impl Interpolate<Vec<f64>, f64> for Quadratic {
-    fn interpolate(&self, x: &Vec<f64>, y: &Vec<f64>, xp: &f64) -> f64 {
-        if xp <= &0.0 {
-            if xp < &0.0 {
-                return 0.0;
-            } else {
-                return 1.0;
-            }
-        };
+impl Quadratic {
+    /// Fewer than three points are interpolated linearly.
+    fn value(&self, x: &[f64], y: &[f64], xp: &f64) -> f64 {
+        if x.len() < 3 {
+            return Linear.value(x, y, xp);
+        }
 
-        let index = partition_index(x, xp);
+        // The last interval is fit with the two points before it.
+        let index = partition_index(x, xp).min(x.len() - 3);
 
         // TODO, is this the best wat to index? Should i not .max(1) then index and start at -1?
         let x_values = (&x[index], &x[index + 1], &x[index + 2]);
@@ -143,16 +232,8 @@ impl Interpolate<Vec<f64>, f64> for Quadratic {
     }
 }
 
-impl Interpolate<Vec<f64>, f64> for Exponential {
-    fn interpolate(&self, x: &Vec<f64>, y: &Vec<f64>, xp: &f64) -> f64 {
-        if xp <= &0.0 {
-            if xp < &0.0 {
-                return 0.0;
-            } else {
-                return 1.0;
-            }
-        };
-
+impl Exponential {
+    fn value(&self, x: &[f64], y: &[f64], xp: &f64) -> f64 {
         let index = partition_index(x, xp);
         let (x1, x2) = (x[index], x[index + 1]);
         let (y1, y2) = (y[index], y[index + 1]);
@@ -161,6 +242,131 @@ impl Interpolate<Vec<f64>, f64> for Exponential {
     }
 }
 
+impl ExtrapolationMethod {
+    /// Value at 'xp', which lies outside the range of 'x'.
+    fn value(&self, x: &[f64], y: &[f64], xp: &f64) -> Result<f64> {
+        let n = x.len() - 1;
+        // End interval nearest to 'xp', ordered such that 'xp' lies beyond the second point.
+        let ((x1, y1), (x2, y2)) = if *xp < x[0] {
+            ((x[1], y[1]), (x[0], y[0]))
+        } else {
+            ((x[n - 1], y[n - 1]), (x[n], y[n]))
+        };
+
+        match self {
+            Self::Flat => Ok(y2),
+            Self::Linear => Ok(y2 + (y2 - y1) / (x2 - x1) * (xp - x2)),
+            Self::FlatForward => {
+                if *xp < x[0] && x[0] > 0.0 {
+                    return Ok(y2.powf(xp / x2));
+                }
+                Ok(y2 * (y2 / y1).powf((xp - x2) / (x2 - x1)))
+            }
+            Self::SmithWilson {
+                ufr_intensity,
+                alpha,
+            } => Ok(SmithWilson::new(x, y, *ufr_intensity, *alpha)?.value(xp)),
+            Self::Error => Err(Error::OutOfRange(*xp)),
+        }
+    }
+}
+
+//  --- Trait implementations: Concrete
+
+/// Methods that fit all points at once are fit on each call, and report a failed fit.
+/// Use 'interpolator' to fit once and evaluate many points. Points outside the range
+/// of 'x' are an error for every method.
+impl TryInterpolate<Vec<f64>, f64> for InterpolationMethod {
+    fn try_interpolate(&self, x: &Vec<f64>, y: &Vec<f64>, xp: &f64) -> Result<f64> {
+        validate_range(x, y, xp)?;
+        match self {
+            Self::Linear => Linear.try_interpolate(x, y, xp),
+            Self::PiecewiseLinear => Err(Error::UnsupportedMethod(*self)),
+            Self::CubicSpline(_)
+            | Self::CubicHermite
+            | Self::Akima
+            | Self::MonotoneConvex
+            | Self::NelsonSiegel
+            | Self::NelsonSiegelSvensson => Ok(self.interpolator(x, y)?.value(xp)),
+            Self::LogLinear => LogLinear.try_interpolate(x, y, xp),
+            Self::Quadratic => Quadratic.try_interpolate(x, y, xp),
+            Self::Exponential => Exponential.try_interpolate(x, y, xp),
+        }
+    }
+}
+
+impl Interpolator for Interpolators {
+    fn value(&self, xp: &f64) -> f64 {
+        match self {
+            Self::CubicSpline(spline) => spline.value(xp),
+            Self::Hermite(spline) => spline.value(xp),
+            Self::MonotoneConvex(curve) => curve.value(xp),
+            Self::Parametric(curve) => curve.rate(xp),
+        }
+    }
+
+    fn derivative(&self, xp: &f64) -> f64 {
+        match self {
+            Self::CubicSpline(spline) => spline.derivative(xp),
+            Self::Hermite(spline) => spline.derivative(xp),
+            Self::MonotoneConvex(curve) => curve.derivative(xp),
+            Self::Parametric(curve) => curve.rate_derivative(xp),
+        }
+    }
+}
+
+impl TryInterpolate<Vec<f64>, f64> for Linear {
+    fn try_interpolate(&self, x: &Vec<f64>, y: &Vec<f64>, xp: &f64) -> Result<f64> {
+        validate_range(x, y, xp)?;
+        Ok(self.value(x, y, xp))
+    }
+}
+
+impl Interpolate<Vec<f64>, f64> for Linear {
+    fn interpolate(&self, x: &Vec<f64>, y: &Vec<f64>, xp: &f64) -> f64 {
+        self.try_interpolate(x, y, xp).unwrap_or(f64::NAN)
+    }
+}
+
+impl TryInterpolate<Vec<f64>, f64> for LogLinear {
+    fn try_interpolate(&self, x: &Vec<f64>, y: &Vec<f64>, xp: &f64) -> Result<f64> {
+        validate_range(x, y, xp)?;
+        Ok(self.value(x, y, xp))
+    }
+}
+
+impl Interpolate<Vec<f64>, f64> for LogLinear {
+    fn interpolate(&self, x: &Vec<f64>, y: &Vec<f64>, xp: &f64) -> f64 {
+        self.try_interpolate(x, y, xp).unwrap_or(f64::NAN)
+    }
+}
+
+impl TryInterpolate<Vec<f64>, f64> for Quadratic {
+    fn try_interpolate(&self, x: &Vec<f64>, y: &Vec<f64>, xp: &f64) -> Result<f64> {
+        validate_range(x, y, xp)?;
+        Ok(self.value(x, y, xp))
+    }
+}
+
+impl Interpolate<Vec<f64>, f64> for Quadratic {
+    fn interpolate(&self, x: &Vec<f64>, y: &Vec<f64>, xp: &f64) -> f64 {
+        self.try_interpolate(x, y, xp).unwrap_or(f64::NAN)
+    }
+}
+
+impl TryInterpolate<Vec<f64>, f64> for Exponential {
+    fn try_interpolate(&self, x: &Vec<f64>, y: &Vec<f64>, xp: &f64) -> Result<f64> {
+        validate_range(x, y, xp)?;
+        Ok(self.value(x, y, xp))
+    }
+}
+
+impl Interpolate<Vec<f64>, f64> for Exponential {
+    fn interpolate(&self, x: &Vec<f64>, y: &Vec<f64>, xp: &f64) -> f64 {
+        self.try_interpolate(x, y, xp).unwrap_or(f64::NAN)
+    }
+}
+
 //  --- Trait implementations: Blanket
 impl<A> Interpolate<Vec<f64>, Vec<f64>> for A
 where
@@ -171,18 +377,75 @@ where
     }
 }
 
+impl<A> Extrapolate<Vec<f64>, f64> for A
+where
+    A: TryInterpolate<Vec<f64>, f64>,
+{
+    fn extrapolate(
+        &self,
+        x: &Vec<f64>,
+        y: &Vec<f64>,
+        xp: &f64,
+        extrapolation: &ExtrapolationMethod,
+    ) -> Result<f64> {
+        validate_points(x, y)?;
+        if (x[0]..=x[x.len() - 1]).contains(xp) {
+            return self.try_interpolate(x, y, xp);
+        }
+        extrapolation.value(x, y, xp)
+    }
+}
+
+impl<A> Extrapolate<Vec<f64>, Vec<f64>> for A
+where
+    A: TryInterpolate<Vec<f64>, f64>,
+{
+    fn extrapolate(
+        &self,
+        x: &Vec<f64>,
+        y: &Vec<f64>,
+        xp: &Vec<f64>,
+        extrapolation: &ExtrapolationMethod,
+    ) -> Result<Vec<f64>> {
+        xp.iter()
+            .map(|a| self.extrapolate(x, y, a, extrapolation))
+            .collect()
+    }
+}
+
 //  --- Standalone functions
+/// Checks that 'x' and 'y' are of equal length, with at least two
+/// points, and that 'x' is strictly increasing.
+fn validate_points(x: &[f64], y: &[f64]) -> Result<()> {
+    if x.len() != y.len() {
+        return Err(Error::MismatchedLengths(x.len(), y.len()));
+    }
+    if x.len() < 2 {
+        return Err(Error::InsufficientPoints(x.len()));
+    }
+    if x.windows(2).any(|a| a[0] >= a[1]) {
+        return Err(Error::UnorderedPoints);
+    }
+    Ok(())
+}
+
+/// Checks the points, and that 'xp' lies within the range of 'x'.
+fn validate_range(x: &[f64], y: &[f64], xp: &f64) -> Result<()> {
+    validate_points(x, y)?;
+    if !(x[0]..=x[x.len() - 1]).contains(xp) {
+        return Err(Error::OutOfRange(*xp));
+    }
+    Ok(())
+}
+
 /// Return the index of the point that partitions 'x'. The index
 /// is clipped at 0 and length less 2.
 fn partition_index(x: &[f64], xp: &f64) -> usize {
     let values_smaller: Vec<&f64> = x.iter().filter(|element| *element <= xp).collect();
 
-    // Convert 'length' into 'index'.
+    // Convert 'length' into 'index', clipping the value at zero.
     let index_count = values_smaller.len().max(1) - 1;
 
-    // Clips the value at zero.
-    let index_count = index_count.max(0);
-
     // 'x'.len() - 1 would represent the last index
     // within the collection. However, there should
     // be left room for index + 1 (which is equivalent to len - 1);
@@ -241,4 +504,214 @@ mod test_interpolation {
         assert_approx_eq!(b, 7.75);
         assert_approx_eq!(c, 0.516667);
     }
+
+    #[test]
+    fn test_nelson_siegel() -> Result<()> {
+        let curve =
+            ParametricCurve::new(ParametricModels::NelsonSiegel, &[0.06, -0.02, 0.01, 1.5])?;
+        let x = vec![0.5, 1.0, 2.0, 3.0, 5.0, 7.0, 10.0, 20.0];
+        let y: Vec<f64> = x.iter().map(|t| curve.rate(t)).collect();
+        let interpolated = InterpolationMethod::NelsonSiegel.try_interpolate(&x, &y, &4.0)?;
+        assert_approx_eq!(interpolated, curve.rate(&4.0), 1e-9);
+        Ok(())
+    }
+
+    #[test]
+    fn test_extrapolation() -> Result<()> {
+        let x = vec![0.5, 1.0, 2.0];
+        let df = vec![0.98, 0.95, 0.9];
+        let method = InterpolationMethod::LogLinear;
+
+        // Within the range, the policy is not applied.
+        let within = method.extrapolate(&x, &df, &1.5, &ExtrapolationMethod::Error)?;
+        assert_approx_eq!(within, method.try_interpolate(&x, &df, &1.5)?);
+
+        let flat_forward = ExtrapolationMethod::FlatForward;
+        assert_approx_eq!(method.extrapolate(&x, &df, &0.0, &flat_forward)?, 1.0);
+        assert_approx_eq!(
+            method.extrapolate(&x, &df, &0.25, &flat_forward)?,
+            0.98_f64.sqrt(),
+            1e-12
+        );
+        assert_approx_eq!(
+            method.extrapolate(&x, &df, &3.0, &flat_forward)?,
+            0.9 * 0.9 / 0.95,
+            1e-12
+        );
+
+        // Rates are held flat, rather than anchored at one.
+        let rates = vec![0.07, 0.072, 0.075];
+        let linear = InterpolationMethod::Linear;
+        assert_approx_eq!(
+            linear.extrapolate(&x, &rates, &0.0, &ExtrapolationMethod::Flat)?,
+            0.07
+        );
+        assert_approx_eq!(
+            linear.extrapolate(&x, &rates, &3.0, &ExtrapolationMethod::Linear)?,
+            0.078,
+            1e-12
+        );
+
+        assert!(matches!(
+            method.extrapolate(&x, &df, &2.5, &ExtrapolationMethod::Error),
+            Err(Error::OutOfRange(_))
+        ));
+        let points = method.extrapolate(&x, &df, &vec![0.1, 1.0, 4.0], &flat_forward)?;
+        assert_eq!(points.len(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_smith_wilson_extrapolation() -> Result<()> {
+        let x = vec![1.0, 2.0, 5.0, 10.0];
+        let df: Vec<f64> = x.iter().map(|t: &f64| (-0.03 * t).exp()).collect();
+        let ufr_intensity = 0.04;
+        let extrapolation = ExtrapolationMethod::SmithWilson {
+            ufr_intensity,
+            alpha: 0.2,
+        };
+        let method = InterpolationMethod::LogLinear;
+
+        assert_approx_eq!(
+            method.extrapolate(&x, &df, &0.0, &extrapolation)?,
+            1.0,
+            1e-12
+        );
+        let (a, b) = (
+            method.extrapolate(&x, &df, &150.0, &extrapolation)?,
+            method.extrapolate(&x, &df, &151.0, &extrapolation)?,
+        );
+        assert_approx_eq!((a / b).ln(), ufr_intensity, 1e-4);
+        Ok(())
+    }
+
+    #[test]
+    fn test_interpolator_errors() {
+        let method = InterpolationMethod::MonotoneConvex;
+        assert!(matches!(
+            method.interpolator(&[0.0, 1.0], &[0.0]),
+            Err(Error::MismatchedLengths(2, 1))
+        ));
+        assert!(matches!(
+            method.interpolator(&[1.0, 0.0], &[0.0, 1.0]),
+            Err(Error::UnorderedPoints)
+        ));
+        assert!(matches!(
+            InterpolationMethod::Linear.interpolator(&[0.0, 1.0], &[0.0, 1.0]),
+            Err(Error::UnsupportedMethod(InterpolationMethod::Linear))
+        ));
+    }
+
+    #[test]
+    fn test_quadratic() -> Result<()> {
+        // Exact for the quadratic through the points, including the last interval.
+        let x = vec![1.0, 2.0, 3.0, 4.0];
+        let y: Vec<f64> = x.iter().map(|a| a * a).collect();
+        assert_approx_eq!(Quadratic.try_interpolate(&x, &y, &3.5)?, 12.25, 1e-12);
+        assert_approx_eq!(Quadratic.try_interpolate(&x, &y, &4.0)?, 16.0, 1e-12);
+        assert_approx_eq!(Quadratic.try_interpolate(&x, &y, &1.5)?, 2.25, 1e-12);
+        Ok(())
+    }
+
+    #[test]
+    fn test_edges() -> Result<()> {
+        // A zero rate curve with a pillar at zero is not anchored at one.
+        let x = vec![0.0, 1.0, 2.0];
+        let rates = vec![0.05, 0.06, 0.07];
+        let flat = ExtrapolationMethod::Flat;
+        assert_approx_eq!(Linear.extrapolate(&x, &rates, &0.0, &flat)?, 0.05);
+        assert_approx_eq!(Linear.interpolate(&x, &rates, &0.0), 0.05);
+        assert_approx_eq!(Quadratic.interpolate(&x, &rates, &0.0), 0.05, 1e-12);
+        assert_approx_eq!(Exponential.interpolate(&x, &rates, &0.0), 0.05, 1e-12);
+
+        // Negative 'x' within the range is interpolated.
+        let (x, y) = (vec![-1.0, 1.0], vec![0.9, 1.1]);
+        assert_approx_eq!(Linear.extrapolate(&x, &y, &-0.5, &flat)?, 0.95, 1e-12);
+        assert_approx_eq!(LogLinear.interpolate(&x, &y, &-1.0), 0.9, 1e-12);
+
+        // Outside the range, interpolation follows the error policy.
+        assert!(Linear.interpolate(&x, &y, &2.0).is_nan());
+        assert!(matches!(
+            Linear.try_interpolate(&x, &y, &2.0),
+            Err(Error::OutOfRange(_))
+        ));
+        assert!(matches!(
+            InterpolationMethod::CubicHermite.try_interpolate(&x, &y, &-2.0),
+            Err(Error::OutOfRange(_))
+        ));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_zar_curves {
+    use super::*;
+    use crate::resources::market_data::load_curve;
+
+    const CURVES: [&str; 3] = ["zar_disc_csa_irs", "zar_swap", "zar_swap_irs"];
+    const METHODS: [InterpolationMethod; 3] = [
+        InterpolationMethod::CubicHermite,
+        InterpolationMethod::Akima,
+        InterpolationMethod::MonotoneConvex,
+    ];
+
+    /// Times in years, and -ln(DF), the integral of the instantaneous forward.
+    fn load(name: &str) -> std::result::Result<(Vec<f64>, Vec<f64>), Box<dyn std::error::Error>> {
+        let curve = load_curve::<u32, f64>(name)?;
+        let (mut x, mut y) = (vec![0.0], vec![0.0]);
+        for (days, df) in curve {
+            x.push(days as f64 / 365.0);
+            y.push(-df.ln());
+        }
+        Ok((x, y))
+    }
+
+    #[test]
+    fn test_discount_factors_are_monotone() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        for name in CURVES {
+            let (x, y) = load(name)?;
+            let days = (x[x.len() - 1] * 365.0) as u32;
+            for method in METHODS {
+                let interpolator = method.interpolator(&x, &y)?;
+                let mut previous = 1.0;
+                for day in 1..=days {
+                    let df = (-interpolator.value(&(day as f64 / 365.0))).exp();
+                    assert!(df < previous, "{:?} on {} at day {}", method, name, day);
+                    previous = df;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_forwards_are_positive() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        for name in CURVES {
+            let (x, y) = load(name)?;
+            let days = (x[x.len() - 1] * 365.0) as u32;
+            for method in METHODS {
+                let interpolator = method.interpolator(&x, &y)?;
+                for day in 0..=days {
+                    let forward = interpolator.derivative(&(day as f64 / 365.0));
+                    assert!(forward > 0.0, "{:?} on {} at day {}", method, name, day);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_hyman_discount_factors() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        // Interpolating the discount factors directly.
+        for name in CURVES {
+            let (x, y) = load(name)?;
+            let df: Vec<f64> = y.iter().map(|a| (-a).exp()).collect();
+            let interpolator = InterpolationMethod::CubicHermite.interpolator(&x, &df)?;
+            let days = (x[x.len() - 1] * 365.0) as u32;
+            for day in 0..=days {
+                assert!(interpolator.derivative(&(day as f64 / 365.0)) < 0.0);
+            }
+        }
+        Ok(())
+    }
 }
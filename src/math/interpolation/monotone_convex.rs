@@ -0,0 +1,247 @@
+//! Monotone convex interpolation (Hagan and West, 2006).
+//!
+//! The data 'y' is the integral of a forward curve, such as -ln(DF) or r.t, and the
+//! derivative of the interpolant is the forward. On each interval [xi-1, xi], the
+//! discrete forward is fd_i = (yi - yi-1) / (xi - xi-1), and the instantaneous forward is
+//! f(x) = fd_i + g((x - xi-1) / (xi - xi-1)), where g integrates to zero over [0, 1],
+//! such that the data is reproduced exactly.
+//!
+//! Forwards at the knots are weighted averages of the neighbouring discrete forwards.
+//! The function g is then chosen, from four shapes, to match these knot forwards while
+//! keeping the forward curve continuous and, where the discrete forwards share a sign,
+//! of that same sign. Points outside the knots are extrapolated with flat forwards.
+
+use super::{validate_points, Interpolator, Result};
+
+//  --- Structs
+
+#[derive(Clone, Debug)]
+pub struct MonotoneConvex {
+    x: Vec<f64>,
+    y: Vec<f64>,
+    discrete_forwards: Vec<f64>,
+    forwards: Vec<f64>,
+}
+
+//  --- Implementations
+
+impl MonotoneConvex {
+    pub fn new(x: &[f64], y: &[f64]) -> Result<Self> {
+        validate_points(x, y)?;
+        let n = x.len() - 1;
+        let h: Vec<f64> = x.windows(2).map(|a| a[1] - a[0]).collect();
+        let discrete_forwards: Vec<f64> = y
+            .windows(2)
+            .zip(h.iter())
+            .map(|(a, h)| (a[1] - a[0]) / h)
+            .collect();
+
+        let mut forwards = vec![0.0; n + 1];
+        for i in 1..n {
+            forwards[i] = (h[i - 1] * discrete_forwards[i] + h[i] * discrete_forwards[i - 1])
+                / (h[i - 1] + h[i]);
+        }
+        if n == 1 {
+            forwards.fill(discrete_forwards[0]);
+        } else {
+            forwards[0] = discrete_forwards[0] - 0.5 * (forwards[1] - discrete_forwards[0]);
+            forwards[n] =
+                discrete_forwards[n - 1] - 0.5 * (forwards[n - 1] - discrete_forwards[n - 1]);
+        }
+
+        // Collar the knot forwards, such that the forward curve keeps the sign of the
+        // discrete forwards.
+        let sign = if discrete_forwards.iter().all(|f| *f >= 0.0) {
+            Some(1.0)
+        } else if discrete_forwards.iter().all(|f| *f <= 0.0) {
+            Some(-1.0)
+        } else {
+            None
+        };
+        if let Some(s) = sign {
+            for (i, forward) in forwards.iter_mut().enumerate() {
+                let left = discrete_forwards.get(i.wrapping_sub(1)).map(|f| s * f);
+                let right = discrete_forwards.get(i).map(|f| s * f);
+                let bound = 2.0
+                    * left
+                        .unwrap_or(f64::INFINITY)
+                        .min(right.unwrap_or(f64::INFINITY));
+                *forward = s * (s * *forward).clamp(0.0, bound);
+            }
+        }
+
+        Ok(Self {
+            x: x.to_vec(),
+            y: y.to_vec(),
+            discrete_forwards,
+            forwards,
+        })
+    }
+
+    /// Instantaneous forwards at the knots.
+    pub fn forwards(&self) -> &[f64] {
+        &self.forwards
+    }
+
+    /// Index of the interval [xi-1, xi] containing 'xp', returned as i.
+    fn segment(&self, xp: &f64) -> usize {
+        self.x
+            .partition_point(|a| a <= xp)
+            .clamp(1, self.x.len() - 1)
+    }
+
+    /// Deviation from the discrete forward, g(x), and its integral G(x), on interval 'i',
+    /// where 'x' is the position within the interval, scaled to [0, 1].
+    fn deviation(&self, i: usize, x: f64) -> (f64, f64) {
+        let fd = self.discrete_forwards[i - 1];
+        let (g0, g1) = (self.forwards[i - 1] - fd, self.forwards[i] - fd);
+
+        if g0 == 0.0 && g1 == 0.0 {
+            return (0.0, 0.0);
+        }
+
+        // (i) Quadratic, with no turning point within the interval.
+        if (g0 < 0.0 && -0.5 * g0 <= g1 && g1 <= -2.0 * g0)
+            || (g0 > 0.0 && -0.5 * g0 >= g1 && g1 >= -2.0 * g0)
+        {
+            let g = g0 * (1.0 - 4.0 * x + 3.0 * x.powi(2)) + g1 * (-2.0 * x + 3.0 * x.powi(2));
+            let integral = g0 * (x - 2.0 * x.powi(2) + x.powi(3)) + g1 * (-x.powi(2) + x.powi(3));
+            return (g, integral);
+        }
+
+        // (ii) Flat, then quadratic towards the end of the interval.
+        if (g0 < 0.0 && g1 > -2.0 * g0) || (g0 > 0.0 && g1 < -2.0 * g0) {
+            let eta = (g1 + 2.0 * g0) / (g1 - g0);
+            if x <= eta {
+                return (g0, g0 * x);
+            }
+            let scale = (x - eta) / (1.0 - eta);
+            let g = g0 + (g1 - g0) * scale.powi(2);
+            let integral = g0 * x + (g1 - g0) * (x - eta) * scale.powi(2) / 3.0;
+            return (g, integral);
+        }
+
+        // (iii) Quadratic from the start of the interval, then flat.
+        if (g0 > 0.0 && 0.0 > g1 && g1 > -0.5 * g0) || (g0 < 0.0 && 0.0 < g1 && g1 < -0.5 * g0) {
+            let eta = 3.0 * g1 / (g1 - g0);
+            if x >= eta {
+                return (g1, g1 * x + (g0 - g1) * eta / 3.0);
+            }
+            let g = g1 + (g0 - g1) * ((eta - x) / eta).powi(2);
+            let integral =
+                g1 * x + (g0 - g1) * (eta.powi(3) - (eta - x).powi(3)) / (3.0 * eta.powi(2));
+            return (g, integral);
+        }
+
+        // (iv) Two quadratics, meeting at a turning point within the interval.
+        let eta = g1 / (g1 + g0);
+        let a = -g0 * g1 / (g0 + g1);
+        if x <= eta && eta > 0.0 {
+            let g = a + (g0 - a) * ((eta - x) / eta).powi(2);
+            let integral =
+                a * x + (g0 - a) * (eta.powi(3) - (eta - x).powi(3)) / (3.0 * eta.powi(2));
+            return (g, integral);
+        }
+        let scale = (x - eta) / (1.0 - eta);
+        let g = a + (g1 - a) * scale.powi(2);
+        let integral = a * x + (g0 - a) * eta / 3.0 + (g1 - a) * (x - eta) * scale.powi(2) / 3.0;
+        (g, integral)
+    }
+}
+
+//  --- Trait implementations: Concrete
+
+impl Interpolator for MonotoneConvex {
+    fn value(&self, xp: &f64) -> f64 {
+        let n = self.x.len() - 1;
+        if *xp < self.x[0] {
+            return self.y[0] + self.forwards[0] * (xp - self.x[0]);
+        }
+        if *xp > self.x[n] {
+            return self.y[n] + self.forwards[n] * (xp - self.x[n]);
+        }
+
+        let i = self.segment(xp);
+        let h = self.x[i] - self.x[i - 1];
+        let (_, integral) = self.deviation(i, (xp - self.x[i - 1]) / h);
+        self.y[i - 1] + (xp - self.x[i - 1]) * self.discrete_forwards[i - 1] + h * integral
+    }
+
+    fn derivative(&self, xp: &f64) -> f64 {
+        let n = self.x.len() - 1;
+        if *xp < self.x[0] {
+            return self.forwards[0];
+        }
+        if *xp > self.x[n] {
+            return self.forwards[n];
+        }
+
+        let i = self.segment(xp);
+        let h = self.x[i] - self.x[i - 1];
+        let (g, _) = self.deviation(i, (xp - self.x[i - 1]) / h);
+        self.discrete_forwards[i - 1] + g
+    }
+}
+
+//  --- Unit tests
+#[cfg(test)]
+mod test_monotone_convex {
+
+    use super::*;
+    use crate::assert_approx_eq;
+
+    #[test]
+    fn test_reproduces_data() -> Result<()> {
+        let x = vec![0.0, 0.5, 1.0, 2.0, 5.0, 10.0];
+        let y = vec![0.0, 0.035, 0.072, 0.15, 0.4, 0.85];
+        let curve = MonotoneConvex::new(&x, &y)?;
+        for (a, b) in x.iter().zip(y.iter()) {
+            assert_approx_eq!(curve.value(a), b, 1e-12);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_forwards_are_continuous_and_positive() -> Result<()> {
+        // Discrete forwards that jump sharply, in both directions.
+        let x = vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0];
+        let y = vec![0.0, 0.01, 0.2, 0.215, 0.235, 0.5];
+        let curve = MonotoneConvex::new(&x, &y)?;
+
+        let eps = 1e-9;
+        for xp in &x[1..x.len() - 1] {
+            assert_approx_eq!(
+                curve.derivative(&(xp - eps)),
+                curve.derivative(&(xp + eps)),
+                1e-6
+            );
+        }
+        for i in 0..=500 {
+            assert!(curve.derivative(&(i as f64 / 100.0)) >= 0.0);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_derivative() -> Result<()> {
+        let x = vec![0.0, 0.25, 1.0, 3.0, 7.0];
+        let y = vec![0.0, 0.02, 0.06, 0.25, 0.5];
+        let curve = MonotoneConvex::new(&x, &y)?;
+        let eps = 1e-6;
+        for xp in [0.1, 0.6, 2.2, 5.5] {
+            let numerical = (curve.value(&(xp + eps)) - curve.value(&(xp - eps))) / (2.0 * eps);
+            assert_approx_eq!(curve.derivative(&xp), numerical, 1e-6);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_flat_forward() -> Result<()> {
+        let x = vec![0.0, 1.0, 3.0];
+        let y = vec![0.0, 0.05, 0.15];
+        let curve = MonotoneConvex::new(&x, &y)?;
+        assert_approx_eq!(curve.value(&2.0), 0.1, 1e-12);
+        assert_approx_eq!(curve.derivative(&4.0), 0.05, 1e-12);
+        Ok(())
+    }
+}
@@ -0,0 +1,183 @@
+//! Smith-Wilson discount functions.
+//!
+//! The discount factor at time t is P(t) = e^(-ωt) + Σj ζj.W(t, uj), where ω is the
+//! continuously compounded ultimate forward rate, uj are the times of the observed
+//! discount factors, and W is the Wilson function
+//! W(t, u) = e^(-ω(t + u)).(α.min(t, u) - e^(-α.max(t, u)).sinh(α.min(t, u))).
+//!
+//! The weights ζ are solved such that the observed discount factors are reproduced.
+//! Beyond the last observation, the forward converges to ω at a speed set by α.
+//!
+//! ω is taken as an intensity, 'ufr_intensity'. An annually compounded UFR converts
+//! to ln(1 + UFR), as done by `interest::smith_wilson::SmithWilsonBuilder`.
+
+use super::super::linalg::solve_linear_system;
+use super::{validate_points, Error, Interpolator, Result};
+
+//  --- Structs
+
+#[derive(Clone, Debug)]
+pub struct SmithWilson {
+    ufr_intensity: f64,
+    alpha: f64,
+    x: Vec<f64>,
+    weights: Vec<f64>,
+}
+
+//  --- Implementations
+
+impl SmithWilson {
+    /// Fits the discount function to 'discount_factors' at times 'x'. Points at or
+    /// before time zero are ignored, as the discount factor there is one by construction.
+    pub fn new(
+        x: &[f64],
+        discount_factors: &[f64],
+        ufr_intensity: f64,
+        alpha: f64,
+    ) -> Result<Self> {
+        let (x, discount_factors): (Vec<f64>, Vec<f64>) = x
+            .iter()
+            .zip(discount_factors.iter())
+            .filter(|(t, _)| **t > 0.0)
+            .unzip();
+        if x.is_empty() {
+            return Err(Error::InsufficientPoints(0));
+        }
+        if x.len() > 1 {
+            validate_points(&x, &discount_factors)?;
+        }
+
+        let wilson: Vec<Vec<f64>> = x
+            .iter()
+            .map(|t| {
+                x.iter()
+                    .map(|u| wilson(t, u, ufr_intensity, alpha))
+                    .collect()
+            })
+            .collect();
+        let rhs: Vec<f64> = x
+            .iter()
+            .zip(discount_factors.iter())
+            .map(|(u, p)| p - (-ufr_intensity * u).exp())
+            .collect();
+        let weights = solve_linear_system(&wilson, &rhs).map_err(|_| Error::SingularSystem)?;
+
+        Ok(Self {
+            ufr_intensity,
+            alpha,
+            x,
+            weights,
+        })
+    }
+
+    pub fn ufr_intensity(&self) -> f64 {
+        self.ufr_intensity
+    }
+
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+
+    /// Continuously compounded instantaneous forward rate at time 't'.
+    pub fn forward(&self, t: &f64) -> f64 {
+        -self.derivative(t) / self.value(t)
+    }
+}
+
+//  --- Trait implementations: Concrete
+
+impl Interpolator for SmithWilson {
+    fn value(&self, xp: &f64) -> f64 {
+        let wilson: f64 = self
+            .x
+            .iter()
+            .zip(self.weights.iter())
+            .map(|(u, w)| w * wilson(xp, u, self.ufr_intensity, self.alpha))
+            .sum();
+        (-self.ufr_intensity * xp).exp() + wilson
+    }
+
+    fn derivative(&self, xp: &f64) -> f64 {
+        let wilson: f64 = self
+            .x
+            .iter()
+            .zip(self.weights.iter())
+            .map(|(u, w)| w * wilson_derivative(xp, u, self.ufr_intensity, self.alpha))
+            .sum();
+        -self.ufr_intensity * (-self.ufr_intensity * xp).exp() + wilson
+    }
+}
+
+//  --- Standalone functions
+
+fn wilson(t: &f64, u: &f64, ufr_intensity: f64, alpha: f64) -> f64 {
+    let (min, max) = (t.min(*u), t.max(*u));
+    (-ufr_intensity * (t + u)).exp() * (alpha * min - (-alpha * max).exp() * (alpha * min).sinh())
+}
+
+/// Derivative of the Wilson function with respect to 't'.
+fn wilson_derivative(t: &f64, u: &f64, ufr_intensity: f64, alpha: f64) -> f64 {
+    let slope = if t < u {
+        alpha - alpha * (-alpha * u).exp() * (alpha * t).cosh()
+    } else {
+        alpha * (-alpha * t).exp() * (alpha * u).sinh()
+    };
+    -ufr_intensity * wilson(t, u, ufr_intensity, alpha) + (-ufr_intensity * (t + u)).exp() * slope
+}
+
+//  --- Unit tests
+#[cfg(test)]
+mod test_smith_wilson {
+
+    use super::*;
+    use crate::assert_approx_eq;
+
+    const UFR: f64 = 0.039;
+    const ALPHA: f64 = 0.1;
+
+    fn market() -> (Vec<f64>, Vec<f64>) {
+        let x = vec![1.0, 2.0, 3.0, 5.0, 10.0, 20.0];
+        let rates = [0.031, 0.033, 0.034, 0.035, 0.036, 0.037];
+        let y = x
+            .iter()
+            .zip(rates.iter())
+            .map(|(t, r): (&f64, &f64)| (-r * t).exp())
+            .collect();
+        (x, y)
+    }
+
+    #[test]
+    fn test_reproduces_discount_factors() -> Result<()> {
+        let (x, y) = market();
+        let curve = SmithWilson::new(&x, &y, UFR, ALPHA)?;
+        assert_approx_eq!(curve.value(&0.0), 1.0, 1e-15);
+        for (t, df) in x.iter().zip(y.iter()) {
+            assert_approx_eq!(curve.value(t), df, 1e-12);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_converges_to_ufr() -> Result<()> {
+        let (x, y) = market();
+        let curve = SmithWilson::new(&x, &y, UFR, ALPHA)?;
+        assert_approx_eq!(curve.forward(&150.0), UFR, 1e-4);
+
+        let (t, eps) = (30.0, 1e-5);
+        let numerical = (curve.value(&(t + eps)) - curve.value(&(t - eps))) / (2.0 * eps);
+        assert_approx_eq!(curve.derivative(&t), numerical, 1e-9);
+        let numerical = (curve.value(&(7.0 + eps)) - curve.value(&(7.0 - eps))) / (2.0 * eps);
+        assert_approx_eq!(curve.derivative(&7.0), numerical, 1e-9);
+        Ok(())
+    }
+
+    #[test]
+    fn test_ignores_origin() -> Result<()> {
+        let (mut x, mut y) = market();
+        x.insert(0, 0.0);
+        y.insert(0, 1.0);
+        let curve = SmithWilson::new(&x, &y, UFR, ALPHA)?;
+        assert_approx_eq!(curve.value(&5.0), y[4], 1e-12);
+        Ok(())
+    }
+}
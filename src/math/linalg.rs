@@ -0,0 +1,772 @@
+//! Dense linear algebra.
+//!
+//! A row-major matrix, with the decompositions used across the crate:
+//! - LU with partial pivoting, PA = LU, for square systems, determinants and inverses.
+//! - Cholesky, A = LL', for symmetric positive definite systems, such as covariances.
+//! - Householder QR, A = QR, for least squares, min ||Ax - b||.
+//! - Jacobi eigen-decomposition, A = VΛV', for symmetric matrices, such as correlations.
+//! - Thomas algorithm for tridiagonal systems, such as spline second derivatives.
+//!
+//! ```rust,ignore
+//! let a = Matrix::from_rows(&[vec![4.0, 2.0], vec![2.0, 3.0]])?;
+//! let x = Lu::new(&a)?.solve(&[1.0, 2.0])?;
+//! let l = Cholesky::new(&a)?.lower();
+//! let beta = Qr::new(&design)?.solve(&y)?;
+//! let eigen = SymmetricEigen::new(&correlation)?;
+//! ```
+
+use std::ops::{Index, IndexMut};
+
+//  --- Constants
+
+/// Pivots smaller than this, relative to the largest element, are treated as zero.
+const PIVOT_TOLERANCE: f64 = 1e-14;
+
+/// Relative asymmetry tolerated in a symmetric matrix.
+const SYMMETRY_TOLERANCE: f64 = 1e-12;
+
+const MAX_SWEEPS: usize = 100;
+
+//  --- Errors
+type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    MismatchedDimensions(usize, usize),
+    NotSquare(usize, usize),
+    NotSymmetric,
+    Singular,
+    NotPositiveDefinite,
+    NoConvergence,
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MismatchedDimensions(expected, given) => {
+                write!(
+                    f,
+                    "Expected dimension {}, while {} was given.",
+                    expected, given
+                )
+            }
+            Self::NotSquare(rows, cols) => {
+                write!(
+                    f,
+                    "Matrix with {} rows and {} columns is not square.",
+                    rows, cols
+                )
+            }
+            Self::NotSymmetric => write!(f, "Matrix is not symmetric."),
+            Self::Singular => write!(f, "Matrix is singular, or rank deficient."),
+            Self::NotPositiveDefinite => write!(f, "Matrix is not positive definite."),
+            Self::NoConvergence => write!(f, "Eigen-decomposition did not converge."),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+//  --- Structs
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Matrix {
+    rows: usize,
+    cols: usize,
+    data: Vec<f64>,
+}
+
+/// LU decomposition with partial pivoting. L (unit diagonal) and U share storage.
+#[derive(Clone, Debug)]
+pub struct Lu {
+    lu: Matrix,
+    permutation: Vec<usize>,
+    sign: f64,
+}
+
+#[derive(Clone, Debug)]
+pub struct Cholesky {
+    lower: Matrix,
+}
+
+/// Householder QR decomposition. R is stored in the upper triangle, and the
+/// Householder vectors, which define Q, separately.
+#[derive(Clone, Debug)]
+pub struct Qr {
+    r: Matrix,
+    reflectors: Vec<Vec<f64>>,
+}
+
+/// Eigenvalues, in ascending order, and eigenvectors, as the columns of a matrix.
+#[derive(Clone, Debug)]
+pub struct SymmetricEigen {
+    values: Vec<f64>,
+    vectors: Matrix,
+}
+
+//  --- Implementations
+
+impl Matrix {
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        Self {
+            rows,
+            cols,
+            data: vec![0.0; rows * cols],
+        }
+    }
+
+    pub fn identity(n: usize) -> Self {
+        let mut identity = Self::zeros(n, n);
+        for i in 0..n {
+            identity[(i, i)] = 1.0;
+        }
+        identity
+    }
+
+    pub fn from_rows(rows: &[Vec<f64>]) -> Result<Self> {
+        let cols = rows.first().map(|row| row.len()).unwrap_or(0);
+        if let Some(row) = rows.iter().find(|row| row.len() != cols) {
+            return Err(Error::MismatchedDimensions(cols, row.len()));
+        }
+        Ok(Self {
+            rows: rows.len(),
+            cols,
+            data: rows.concat(),
+        })
+    }
+
+    pub fn diagonal(values: &[f64]) -> Self {
+        let mut diagonal = Self::zeros(values.len(), values.len());
+        for (i, v) in values.iter().enumerate() {
+            diagonal[(i, i)] = *v;
+        }
+        diagonal
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn is_square(&self) -> bool {
+        self.rows == self.cols
+    }
+
+    pub fn row(&self, i: usize) -> &[f64] {
+        &self.data[i * self.cols..(i + 1) * self.cols]
+    }
+
+    pub fn column(&self, j: usize) -> Vec<f64> {
+        (0..self.rows).map(|i| self[(i, j)]).collect()
+    }
+
+    pub fn to_rows(&self) -> Vec<Vec<f64>> {
+        (0..self.rows).map(|i| self.row(i).to_vec()).collect()
+    }
+
+    pub fn transpose(&self) -> Self {
+        let mut transposed = Self::zeros(self.cols, self.rows);
+        for i in 0..self.rows {
+            for j in 0..self.cols {
+                transposed[(j, i)] = self[(i, j)];
+            }
+        }
+        transposed
+    }
+
+    pub fn multiply(&self, other: &Matrix) -> Result<Matrix> {
+        if self.cols != other.rows {
+            return Err(Error::MismatchedDimensions(self.cols, other.rows));
+        }
+        let mut product = Self::zeros(self.rows, other.cols);
+        for i in 0..self.rows {
+            for k in 0..self.cols {
+                let a = self[(i, k)];
+                for j in 0..other.cols {
+                    product[(i, j)] += a * other[(k, j)];
+                }
+            }
+        }
+        Ok(product)
+    }
+
+    pub fn multiply_vector(&self, x: &[f64]) -> Result<Vec<f64>> {
+        if self.cols != x.len() {
+            return Err(Error::MismatchedDimensions(self.cols, x.len()));
+        }
+        Ok((0..self.rows).map(|i| dot(self.row(i), x)).collect())
+    }
+
+    /// Whether the matrix is square and symmetric, within a relative tolerance.
+    pub fn is_symmetric(&self) -> bool {
+        let scale = self.max_abs().max(f64::MIN_POSITIVE);
+        self.is_square()
+            && (0..self.rows).all(|i| {
+                (0..i).all(|j| (self[(i, j)] - self[(j, i)]).abs() <= SYMMETRY_TOLERANCE * scale)
+            })
+    }
+
+    fn max_abs(&self) -> f64 {
+        self.data.iter().fold(0.0, |acc, v| acc.max(v.abs()))
+    }
+
+    fn validate_square(&self) -> Result<()> {
+        match self.is_square() {
+            true => Ok(()),
+            false => Err(Error::NotSquare(self.rows, self.cols)),
+        }
+    }
+}
+
+impl Lu {
+    pub fn new(a: &Matrix) -> Result<Self> {
+        a.validate_square()?;
+        let n = a.rows;
+        let tolerance = PIVOT_TOLERANCE * a.max_abs().max(f64::MIN_POSITIVE);
+        let mut lu = a.clone();
+        let mut permutation: Vec<usize> = (0..n).collect();
+        let mut sign = 1.0;
+
+        for k in 0..n {
+            let pivot = (k..n)
+                .max_by(|i, j| lu[(*i, k)].abs().total_cmp(&lu[(*j, k)].abs()))
+                .ok_or(Error::Singular)?;
+            if lu[(pivot, k)].abs() <= tolerance || !lu[(pivot, k)].is_finite() {
+                return Err(Error::Singular);
+            }
+            if pivot != k {
+                for j in 0..n {
+                    lu.data.swap(k * n + j, pivot * n + j);
+                }
+                permutation.swap(k, pivot);
+                sign = -sign;
+            }
+            for i in k + 1..n {
+                let factor = lu[(i, k)] / lu[(k, k)];
+                lu[(i, k)] = factor;
+                for j in k + 1..n {
+                    let u = lu[(k, j)];
+                    lu[(i, j)] -= factor * u;
+                }
+            }
+        }
+
+        Ok(Self {
+            lu,
+            permutation,
+            sign,
+        })
+    }
+
+    pub fn solve(&self, b: &[f64]) -> Result<Vec<f64>> {
+        let n = self.lu.rows;
+        if b.len() != n {
+            return Err(Error::MismatchedDimensions(n, b.len()));
+        }
+        let mut x: Vec<f64> = self.permutation.iter().map(|i| b[*i]).collect();
+        for i in 0..n {
+            let sum = dot(&self.lu.row(i)[..i], &x[..i]);
+            x[i] -= sum;
+        }
+        for i in (0..n).rev() {
+            let sum = dot(&self.lu.row(i)[i + 1..], &x[i + 1..]);
+            x[i] = (x[i] - sum) / self.lu[(i, i)];
+        }
+        Ok(x)
+    }
+
+    pub fn determinant(&self) -> f64 {
+        (0..self.lu.rows).fold(self.sign, |acc, i| acc * self.lu[(i, i)])
+    }
+
+    pub fn inverse(&self) -> Result<Matrix> {
+        let n = self.lu.rows;
+        let mut inverse = Matrix::zeros(n, n);
+        for j in 0..n {
+            let mut e = vec![0.0; n];
+            e[j] = 1.0;
+            for (i, v) in self.solve(&e)?.into_iter().enumerate() {
+                inverse[(i, j)] = v;
+            }
+        }
+        Ok(inverse)
+    }
+}
+
+impl Cholesky {
+    pub fn new(a: &Matrix) -> Result<Self> {
+        a.validate_square()?;
+        if !a.is_symmetric() {
+            return Err(Error::NotSymmetric);
+        }
+        let n = a.rows;
+        let mut lower = Matrix::zeros(n, n);
+        for i in 0..n {
+            for j in 0..=i {
+                let sum = dot(&lower.row(i)[..j], &lower.row(j)[..j]);
+                if i == j {
+                    let d = a[(i, i)] - sum;
+                    if d <= 0.0 || !d.is_finite() {
+                        return Err(Error::NotPositiveDefinite);
+                    }
+                    lower[(i, i)] = d.sqrt();
+                } else {
+                    lower[(i, j)] = (a[(i, j)] - sum) / lower[(j, j)];
+                }
+            }
+        }
+        Ok(Self { lower })
+    }
+
+    pub fn lower(&self) -> &Matrix {
+        &self.lower
+    }
+
+    pub fn solve(&self, b: &[f64]) -> Result<Vec<f64>> {
+        let n = self.lower.rows;
+        if b.len() != n {
+            return Err(Error::MismatchedDimensions(n, b.len()));
+        }
+        // L.y = b, then L'.x = y.
+        let mut x = b.to_vec();
+        for i in 0..n {
+            let sum = dot(&self.lower.row(i)[..i], &x[..i]);
+            x[i] = (x[i] - sum) / self.lower[(i, i)];
+        }
+        for i in (0..n).rev() {
+            let sum: f64 = (i + 1..n).map(|k| self.lower[(k, i)] * x[k]).sum();
+            x[i] = (x[i] - sum) / self.lower[(i, i)];
+        }
+        Ok(x)
+    }
+}
+
+impl Qr {
+    /// Decomposes 'a', which should have at least as many rows as columns.
+    pub fn new(a: &Matrix) -> Result<Self> {
+        let (m, n) = (a.rows, a.cols);
+        if m < n {
+            return Err(Error::MismatchedDimensions(n, m));
+        }
+        let mut r = a.clone();
+        let mut reflectors = Vec::with_capacity(n);
+
+        for k in 0..n {
+            let mut v: Vec<f64> = (k..m).map(|i| r[(i, k)]).collect();
+            let norm = dot(&v, &v).sqrt();
+            // Reflect away from the sign of the leading element, avoiding cancellation.
+            v[0] += if v[0] >= 0.0 { norm } else { -norm };
+            let v_norm = dot(&v, &v).sqrt();
+            if v_norm > 0.0 {
+                v.iter_mut().for_each(|a| *a /= v_norm);
+                // R <- (I - 2vv')R, on the trailing block.
+                for j in k..n {
+                    let projection: f64 = (k..m).map(|i| v[i - k] * r[(i, j)]).sum();
+                    for i in k..m {
+                        r[(i, j)] -= 2.0 * v[i - k] * projection;
+                    }
+                }
+            }
+            reflectors.push(v);
+        }
+
+        Ok(Self { r, reflectors })
+    }
+
+    /// Upper triangular factor, with as many rows as columns.
+    pub fn r(&self) -> Matrix {
+        let n = self.r.cols;
+        let mut r = Matrix::zeros(n, n);
+        for i in 0..n {
+            for j in i..n {
+                r[(i, j)] = self.r[(i, j)];
+            }
+        }
+        r
+    }
+
+    /// Solves min ||Ax - b|| for 'x'.
+    pub fn solve(&self, b: &[f64]) -> Result<Vec<f64>> {
+        let (m, n) = (self.r.rows, self.r.cols);
+        if b.len() != m {
+            return Err(Error::MismatchedDimensions(m, b.len()));
+        }
+        // Q'b, applying each reflection in turn.
+        let mut qtb = b.to_vec();
+        for (k, v) in self.reflectors.iter().enumerate() {
+            let projection = dot(v, &qtb[k..]);
+            qtb[k..]
+                .iter_mut()
+                .zip(v.iter())
+                .for_each(|(b, v)| *b -= 2.0 * v * projection);
+        }
+
+        let tolerance = PIVOT_TOLERANCE
+            * (0..n)
+                .map(|i| self.r[(i, i)].abs())
+                .fold(0.0, f64::max)
+                .max(f64::MIN_POSITIVE);
+        let mut x = vec![0.0; n];
+        for i in (0..n).rev() {
+            if self.r[(i, i)].abs() <= tolerance {
+                return Err(Error::Singular);
+            }
+            let sum = dot(&self.r.row(i)[i + 1..], &x[i + 1..]);
+            x[i] = (qtb[i] - sum) / self.r[(i, i)];
+        }
+        Ok(x)
+    }
+}
+
+impl SymmetricEigen {
+    /// Decomposes a symmetric matrix by cyclic Jacobi rotations.
+    pub fn new(a: &Matrix) -> Result<Self> {
+        a.validate_square()?;
+        if !a.is_symmetric() {
+            return Err(Error::NotSymmetric);
+        }
+        let n = a.rows;
+        let mut a = a.clone();
+        let mut vectors = Matrix::identity(n);
+        let scale = a.max_abs().max(f64::MIN_POSITIVE);
+
+        let off_diagonal = |a: &Matrix| -> f64 {
+            (0..n)
+                .flat_map(|i| (0..n).filter(move |j| *j != i).map(move |j| (i, j)))
+                .map(|(i, j)| a[(i, j)].powi(2))
+                .sum::<f64>()
+                .sqrt()
+        };
+
+        let mut converged = false;
+        for _ in 0..MAX_SWEEPS {
+            if off_diagonal(&a) <= f64::EPSILON * scale {
+                converged = true;
+                break;
+            }
+            for p in 0..n {
+                for q in p + 1..n {
+                    if a[(p, q)].abs() <= f64::MIN_POSITIVE {
+                        continue;
+                    }
+                    // Rotation zeroing a[(p, q)].
+                    let theta = (a[(q, q)] - a[(p, p)]) / (2.0 * a[(p, q)]);
+                    let t = theta.signum() / (theta.abs() + (theta.powi(2) + 1.0).sqrt());
+                    let c = 1.0 / (t.powi(2) + 1.0).sqrt();
+                    let s = t * c;
+                    rotate_columns(&mut a, p, q, c, s);
+                    rotate_rows(&mut a, p, q, c, s);
+                    rotate_columns(&mut vectors, p, q, c, s);
+                }
+            }
+        }
+        if !converged && off_diagonal(&a) > f64::EPSILON.sqrt() * scale {
+            return Err(Error::NoConvergence);
+        }
+
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|i, j| a[(*i, *i)].total_cmp(&a[(*j, *j)]));
+        let values = order.iter().map(|i| a[(*i, *i)]).collect();
+        let mut sorted = Matrix::zeros(n, n);
+        for (j, k) in order.iter().enumerate() {
+            for i in 0..n {
+                sorted[(i, j)] = vectors[(i, *k)];
+            }
+        }
+
+        Ok(Self {
+            values,
+            vectors: sorted,
+        })
+    }
+
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+
+    /// Eigenvectors, as columns, in the order of the eigenvalues.
+    pub fn vectors(&self) -> &Matrix {
+        &self.vectors
+    }
+}
+
+//  --- Trait implementations: Concrete
+
+impl Index<(usize, usize)> for Matrix {
+    type Output = f64;
+
+    fn index(&self, (i, j): (usize, usize)) -> &Self::Output {
+        &self.data[i * self.cols + j]
+    }
+}
+
+impl IndexMut<(usize, usize)> for Matrix {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut Self::Output {
+        &mut self.data[i * self.cols + j]
+    }
+}
+
+//  --- Standalone functions
+
+pub fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b.iter()).map(|(a, b)| a * b).sum()
+}
+
+/// Euclidean norm.
+pub fn norm(a: &[f64]) -> f64 {
+    dot(a, a).sqrt()
+}
+
+/// A <- A.J, for the rotation J in the (p, q) plane.
+fn rotate_columns(a: &mut Matrix, p: usize, q: usize, c: f64, s: f64) {
+    for k in 0..a.rows {
+        let (akp, akq) = (a[(k, p)], a[(k, q)]);
+        a[(k, p)] = c * akp - s * akq;
+        a[(k, q)] = s * akp + c * akq;
+    }
+}
+
+/// A <- J'.A, for the rotation J in the (p, q) plane.
+fn rotate_rows(a: &mut Matrix, p: usize, q: usize, c: f64, s: f64) {
+    for k in 0..a.cols {
+        let (apk, aqk) = (a[(p, k)], a[(q, k)]);
+        a[(p, k)] = c * apk - s * aqk;
+        a[(q, k)] = s * apk + c * aqk;
+    }
+}
+
+/// Solves A.x = b, where 'a' is given as rows, by LU decomposition.
+pub fn solve_linear_system(a: &[Vec<f64>], b: &[f64]) -> Result<Vec<f64>> {
+    Lu::new(&Matrix::from_rows(a)?)?.solve(b)
+}
+
+/// Solves a tridiagonal system with the Thomas algorithm. 'lower'[0] and
+/// 'upper'[n - 1] are ignored.
+pub fn solve_tridiagonal(
+    lower: &[f64],
+    diagonal: &[f64],
+    upper: &[f64],
+    rhs: &[f64],
+) -> Result<Vec<f64>> {
+    let n = diagonal.len();
+    if let Some(len) = [lower.len(), upper.len(), rhs.len()]
+        .into_iter()
+        .find(|len| *len != n)
+    {
+        return Err(Error::MismatchedDimensions(n, len));
+    }
+    if n == 0 {
+        return Err(Error::Singular);
+    }
+
+    let mut c = vec![0.0; n];
+    let mut d = vec![0.0; n];
+
+    // Forward sweep.
+    for i in 0..n {
+        let (pivot, d_carry) = if i == 0 {
+            (diagonal[0], rhs[0])
+        } else {
+            (
+                diagonal[i] - lower[i] * c[i - 1],
+                rhs[i] - lower[i] * d[i - 1],
+            )
+        };
+        if pivot == 0.0 || !pivot.is_finite() {
+            return Err(Error::Singular);
+        }
+        c[i] = upper[i] / pivot;
+        d[i] = d_carry / pivot;
+    }
+
+    // Back substitution.
+    for i in (0..n - 1).rev() {
+        d[i] -= c[i] * d[i + 1];
+    }
+    Ok(d)
+}
+
+//  --- Unit tests
+#[cfg(test)]
+mod test_linalg {
+
+    use super::*;
+    use crate::assert_approx_eq;
+
+    fn spd() -> Matrix {
+        Matrix::from_rows(&[
+            vec![4.0, 12.0, -16.0],
+            vec![12.0, 37.0, -43.0],
+            vec![-16.0, -43.0, 98.0],
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn test_matrix() {
+        let a = Matrix::from_rows(&[vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]).unwrap();
+        assert_eq!((a.rows(), a.cols()), (2, 3));
+        assert_eq!(a.transpose().row(2), &[3.0, 6.0]);
+        assert_eq!(a.column(1), vec![2.0, 5.0]);
+
+        let product = a.multiply(&a.transpose()).unwrap();
+        assert_eq!(product.to_rows(), vec![vec![14.0, 32.0], vec![32.0, 77.0]]);
+        assert!(product.is_symmetric());
+        assert_eq!(
+            a.multiply_vector(&[1.0, 0.0, -1.0]).unwrap(),
+            vec![-2.0, -2.0]
+        );
+
+        assert_eq!(a.multiply(&a), Err(Error::MismatchedDimensions(3, 2)));
+        assert_eq!(
+            Matrix::from_rows(&[vec![1.0], vec![1.0, 2.0]]),
+            Err(Error::MismatchedDimensions(1, 2))
+        );
+    }
+
+    #[test]
+    fn test_lu() {
+        let a = Matrix::from_rows(&[
+            vec![2.0, 1.0, -1.0],
+            vec![-3.0, -1.0, 2.0],
+            vec![-2.0, 1.0, 2.0],
+        ])
+        .unwrap();
+        let lu = Lu::new(&a).unwrap();
+        let x = lu.solve(&[8.0, -11.0, -3.0]).unwrap();
+        for (a, b) in x.iter().zip([2.0, 3.0, -1.0]) {
+            assert_approx_eq!(a, b, 1e-12);
+        }
+        assert_approx_eq!(lu.determinant(), -1.0, 1e-12);
+
+        let identity = a.multiply(&lu.inverse().unwrap()).unwrap();
+        for i in 0..3 {
+            for j in 0..3 {
+                assert_approx_eq!(identity[(i, j)], if i == j { 1.0 } else { 0.0 }, 1e-12);
+            }
+        }
+
+        let singular = Matrix::from_rows(&[vec![1.0, 2.0], vec![2.0, 4.0]]).unwrap();
+        assert_eq!(Lu::new(&singular).err(), Some(Error::Singular));
+        assert_eq!(
+            Lu::new(&Matrix::zeros(2, 3)).err(),
+            Some(Error::NotSquare(2, 3))
+        );
+    }
+
+    #[test]
+    fn test_cholesky() {
+        let cholesky = Cholesky::new(&spd()).unwrap();
+        let expected = [
+            vec![2.0, 0.0, 0.0],
+            vec![6.0, 1.0, 0.0],
+            vec![-8.0, 5.0, 3.0],
+        ];
+        assert_eq!(cholesky.lower().to_rows(), expected);
+
+        let x = [1.0, -2.0, 0.5];
+        let b = spd().multiply_vector(&x).unwrap();
+        for (a, b) in cholesky.solve(&b).unwrap().iter().zip(x.iter()) {
+            assert_approx_eq!(a, b, 1e-10);
+        }
+
+        let indefinite = Matrix::from_rows(&[vec![1.0, 2.0], vec![2.0, 1.0]]).unwrap();
+        assert_eq!(
+            Cholesky::new(&indefinite).err(),
+            Some(Error::NotPositiveDefinite)
+        );
+        let asymmetric = Matrix::from_rows(&[vec![1.0, 2.0], vec![0.0, 1.0]]).unwrap();
+        assert_eq!(Cholesky::new(&asymmetric).err(), Some(Error::NotSymmetric));
+    }
+
+    #[test]
+    fn test_qr() {
+        // Line through (0, 1), (1, 3), (2, 5), with noise that cancels.
+        let design = Matrix::from_rows(&[
+            vec![1.0, 0.0],
+            vec![1.0, 1.0],
+            vec![1.0, 2.0],
+            vec![1.0, 3.0],
+        ])
+        .unwrap();
+        let y = [1.1, 2.9, 5.1, 6.9];
+        let beta = Qr::new(&design).unwrap().solve(&y).unwrap();
+        assert_approx_eq!(beta[0], 1.06, 1e-12);
+        assert_approx_eq!(beta[1], 1.96, 1e-12);
+
+        // R'R = A'A.
+        let r = Qr::new(&design).unwrap().r();
+        let ata = design.transpose().multiply(&design).unwrap();
+        let rtr = r.transpose().multiply(&r).unwrap();
+        for i in 0..2 {
+            for j in 0..2 {
+                assert_approx_eq!(rtr[(i, j)], ata[(i, j)], 1e-12);
+            }
+        }
+
+        let rank_deficient =
+            Matrix::from_rows(&[vec![1.0, 2.0], vec![2.0, 4.0], vec![3.0, 6.0]]).unwrap();
+        assert_eq!(
+            Qr::new(&rank_deficient).unwrap().solve(&[1.0, 2.0, 3.0]),
+            Err(Error::Singular)
+        );
+        assert!(Qr::new(&Matrix::zeros(1, 2)).is_err());
+    }
+
+    #[test]
+    fn test_symmetric_eigen() {
+        let correlation = Matrix::from_rows(&[
+            vec![1.0, 0.8, 0.3],
+            vec![0.8, 1.0, 0.5],
+            vec![0.3, 0.5, 1.0],
+        ])
+        .unwrap();
+        let eigen = SymmetricEigen::new(&correlation).unwrap();
+        let values = eigen.values();
+        assert!(values.windows(2).all(|w| w[0] <= w[1]));
+        assert_approx_eq!(values.iter().sum::<f64>(), 3.0, 1e-12);
+
+        // A.v = λ.v, and V is orthonormal.
+        let vectors = eigen.vectors();
+        for (j, lambda) in values.iter().enumerate() {
+            let v = vectors.column(j);
+            let av = correlation.multiply_vector(&v).unwrap();
+            for (a, b) in av.iter().zip(v.iter()) {
+                assert_approx_eq!(a, lambda * b, 1e-12);
+            }
+        }
+        let vtv = vectors.transpose().multiply(vectors).unwrap();
+        for i in 0..3 {
+            for j in 0..3 {
+                assert_approx_eq!(vtv[(i, j)], if i == j { 1.0 } else { 0.0 }, 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_solve_tridiagonal() {
+        let lower = vec![0.0, 1.0, 1.0, 1.0];
+        let diagonal = vec![4.0, 4.0, 4.0, 4.0];
+        let upper = vec![1.0, 1.0, 1.0, 0.0];
+        let x = [1.0, -2.0, 3.0, 0.5];
+        let rhs: Vec<f64> = (0..4)
+            .map(|i| {
+                let left = if i > 0 { lower[i] * x[i - 1] } else { 0.0 };
+                let right = if i < 3 { upper[i] * x[i + 1] } else { 0.0 };
+                left + diagonal[i] * x[i] + right
+            })
+            .collect();
+
+        let solved = solve_tridiagonal(&lower, &diagonal, &upper, &rhs).unwrap();
+        for (a, b) in solved.iter().zip(x.iter()) {
+            assert_approx_eq!(a, b, 1e-12);
+        }
+        assert_eq!(
+            solve_tridiagonal(&[0.0], &[0.0], &[0.0], &[1.0]),
+            Err(Error::Singular)
+        );
+    }
+}
@@ -0,0 +1,417 @@
+//! BFGS and limited memory BFGS.
+//!
+//! Quasi-Newton minimisation: the inverse Hessian is approximated from the change in
+//! the gradient over successive steps, and each step searches along the resulting
+//! direction with a backtracking (Armijo) line search. With a memory, only the last
+//! 'm' steps are kept (L-BFGS), trading accuracy of the approximation for storage
+//! linear in the number of parameters.
+
+use super::super::linalg::{dot, norm, Matrix};
+use super::{
+    initial_parameters, numerical_gradient, Bounds, ConvergenceReasons, Error, Minimise, Minimum,
+    Result,
+};
+
+//  --- Constants
+
+/// Sufficient decrease required by the line search, relative to the directional derivative.
+const ARMIJO: f64 = 1e-4;
+
+const BACKTRACK: f64 = 0.5;
+
+const MAX_BACKTRACKS: usize = 60;
+
+//  --- Structs
+
+#[derive(Clone, Debug)]
+pub struct Bfgs {
+    bounds: Option<Bounds>,
+    memory: Option<usize>,
+    max_iter: usize,
+    tolerance: f64,
+    gradient_tolerance: f64,
+    step_tolerance: f64,
+}
+
+//  --- Implementations
+
+impl Bfgs {
+    pub fn new() -> Self {
+        Self {
+            bounds: None,
+            memory: None,
+            max_iter: 500,
+            tolerance: 1e-14,
+            gradient_tolerance: 1e-8,
+            step_tolerance: 1e-14,
+        }
+    }
+
+    pub fn bounds(self, bounds: Bounds) -> Self {
+        Self {
+            bounds: Some(bounds),
+            ..self
+        }
+    }
+
+    /// Keeps only the last 'memory' steps, approximating the inverse Hessian by L-BFGS.
+    pub fn memory(self, memory: usize) -> Self {
+        Self {
+            memory: Some(memory.max(1)),
+            ..self
+        }
+    }
+
+    pub fn max_iter(self, max_iter: usize) -> Self {
+        Self { max_iter, ..self }
+    }
+
+    /// Relative reduction in the objective below which iteration stops.
+    pub fn tolerance(self, tolerance: f64) -> Self {
+        Self { tolerance, ..self }
+    }
+
+    /// Largest absolute element of the projected gradient at which iteration stops.
+    pub fn gradient_tolerance(self, gradient_tolerance: f64) -> Self {
+        Self {
+            gradient_tolerance,
+            ..self
+        }
+    }
+
+    /// Step, relative to the parameters, below which iteration stops.
+    pub fn step_tolerance(self, step_tolerance: f64) -> Self {
+        Self {
+            step_tolerance,
+            ..self
+        }
+    }
+
+    /// Minimises 'objective', with an analytic 'gradient'.
+    pub fn minimise_with_gradient<F, G>(
+        &self,
+        objective: F,
+        gradient: G,
+        initial: &[f64],
+    ) -> Result<Minimum>
+    where
+        F: Fn(&[f64]) -> f64,
+        G: Fn(&[f64]) -> Vec<f64>,
+    {
+        let mut x = initial_parameters(initial, self.bounds.as_ref())?;
+        let n = x.len();
+        let mut value = objective(&x);
+        if !value.is_finite() {
+            return Err(Error::NonFiniteValue(x));
+        }
+        let mut g = gradient(&x);
+        if g.len() != n {
+            return Err(Error::MismatchedDimensions(n, g.len()));
+        }
+
+        // Pairs of (step, change in gradient) kept for the inverse Hessian.
+        let mut history: Vec<(Vec<f64>, Vec<f64>)> = Vec::new();
+        let mut inverse_hessian = Matrix::identity(n);
+
+        for iteration in 1..=self.max_iter {
+            if self.projected_gradient(&x, &g) <= self.gradient_tolerance {
+                return Ok(minimum(
+                    x,
+                    value,
+                    iteration - 1,
+                    ConvergenceReasons::Gradient,
+                ));
+            }
+
+            // Parameters held at a bound are excluded from the search direction.
+            let active: Vec<bool> = (0..n)
+                .map(|i| match &self.bounds {
+                    Some(bounds) => bounds.is_active(&x, i, -g[i]),
+                    None => false,
+                })
+                .collect();
+            let free = |mut v: Vec<f64>| -> Vec<f64> {
+                v.iter_mut()
+                    .zip(active.iter())
+                    .filter(|(_, active)| **active)
+                    .for_each(|(v, _)| *v = 0.0);
+                v
+            };
+            let free_gradient = free(g.clone());
+
+            let mut direction = free(match self.memory {
+                Some(_) => two_loop(&free_gradient, &history),
+                None => inverse_hessian
+                    .multiply_vector(&free_gradient)?
+                    .iter()
+                    .map(|a| -a)
+                    .collect(),
+            });
+            if dot(&direction, &free_gradient) >= 0.0 {
+                // Not a descent direction, restart from steepest descent.
+                history.clear();
+                inverse_hessian = Matrix::identity(n);
+                direction = free_gradient.iter().map(|a| -a).collect();
+            }
+
+            let (candidate, candidate_value) =
+                match self.line_search(&objective, &x, value, &g, &direction) {
+                    Some(point) => point,
+                    None => return Ok(minimum(x, value, iteration, ConvergenceReasons::Step)),
+                };
+            let candidate_gradient = gradient(&candidate);
+
+            let s: Vec<f64> = candidate.iter().zip(x.iter()).map(|(a, b)| a - b).collect();
+            let y: Vec<f64> = candidate_gradient
+                .iter()
+                .zip(g.iter())
+                .map(|(a, b)| a - b)
+                .collect();
+            let reduction = value - candidate_value;
+            let step = norm(&s);
+            let scale = norm(&x);
+
+            x = candidate;
+            g = candidate_gradient;
+
+            if reduction.abs() <= self.tolerance * (value.abs() + self.tolerance) {
+                return Ok(minimum(
+                    x,
+                    candidate_value,
+                    iteration,
+                    ConvergenceReasons::Function,
+                ));
+            }
+            if step <= self.step_tolerance * (scale + self.step_tolerance) {
+                return Ok(minimum(
+                    x,
+                    candidate_value,
+                    iteration,
+                    ConvergenceReasons::Step,
+                ));
+            }
+            value = candidate_value;
+
+            // Skip the update if curvature is not positive, keeping the approximation
+            // positive definite.
+            let sy = dot(&s, &y);
+            if sy > f64::EPSILON * norm(&s) * norm(&y) {
+                match self.memory {
+                    Some(m) => {
+                        if history.len() == m {
+                            history.remove(0);
+                        }
+                        history.push((s, y));
+                    }
+                    None => update_inverse_hessian(&mut inverse_hessian, &s, &y, sy)?,
+                }
+            }
+        }
+
+        Err(Error::MaxIterations(Minimum {
+            parameters: x,
+            value,
+            iterations: self.max_iter,
+            reason: None,
+        }))
+    }
+
+    /// Backtracks along 'direction', projected within the bounds, until the objective
+    /// decreases sufficiently.
+    fn line_search<F>(
+        &self,
+        objective: &F,
+        x: &[f64],
+        value: f64,
+        gradient: &[f64],
+        direction: &[f64],
+    ) -> Option<(Vec<f64>, f64)>
+    where
+        F: Fn(&[f64]) -> f64,
+    {
+        let mut t = 1.0;
+        for _ in 0..MAX_BACKTRACKS {
+            let mut candidate: Vec<f64> = x
+                .iter()
+                .zip(direction.iter())
+                .map(|(a, d)| a + t * d)
+                .collect();
+            if let Some(bounds) = &self.bounds {
+                bounds.project(&mut candidate);
+            }
+            let step: Vec<f64> = candidate.iter().zip(x.iter()).map(|(a, b)| a - b).collect();
+            let candidate_value = objective(&candidate);
+            if candidate_value.is_finite()
+                && candidate_value <= value + ARMIJO * dot(gradient, &step)
+                && step.iter().any(|s| *s != 0.0)
+            {
+                return Some((candidate, candidate_value));
+            }
+            t *= BACKTRACK;
+        }
+        None
+    }
+
+    /// Largest element of the gradient, ignoring components pushing against a bound.
+    fn projected_gradient(&self, x: &[f64], gradient: &[f64]) -> f64 {
+        gradient
+            .iter()
+            .enumerate()
+            .map(|(i, g)| match &self.bounds {
+                Some(bounds) if bounds.is_active(x, i, -g) => 0.0,
+                _ => g.abs(),
+            })
+            .fold(0.0, f64::max)
+    }
+}
+
+//  --- Trait implementations: Concrete
+
+impl Default for Bfgs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Minimise for Bfgs {
+    /// Minimises 'objective', with a central difference gradient, one sided for
+    /// parameters within a step of a bound.
+    fn minimise<F>(&self, objective: F, initial: &[f64]) -> Result<Minimum>
+    where
+        F: Fn(&[f64]) -> f64,
+    {
+        let gradient = |x: &[f64]| numerical_gradient(&objective, x, self.bounds.as_ref());
+        self.minimise_with_gradient(&objective, gradient, initial)
+    }
+}
+
+//  --- Standalone functions
+
+fn minimum(
+    parameters: Vec<f64>,
+    value: f64,
+    iterations: usize,
+    reason: ConvergenceReasons,
+) -> Minimum {
+    Minimum {
+        parameters,
+        value,
+        iterations,
+        reason: Some(reason),
+    }
+}
+
+/// H <- (I - ρ.s.y')H(I - ρ.y.s') + ρ.s.s', with ρ = 1 / y's.
+fn update_inverse_hessian(h: &mut Matrix, s: &[f64], y: &[f64], sy: f64) -> Result<()> {
+    let rho = 1.0 / sy;
+    let hy = h.multiply_vector(y)?;
+    let yhy = dot(y, &hy);
+    for i in 0..h.rows() {
+        for j in 0..h.cols() {
+            h[(i, j)] +=
+                (1.0 + rho * yhy) * rho * s[i] * s[j] - rho * (hy[i] * s[j] + s[i] * hy[j]);
+        }
+    }
+    Ok(())
+}
+
+/// L-BFGS two loop recursion, returning the search direction -H.g.
+fn two_loop(gradient: &[f64], history: &[(Vec<f64>, Vec<f64>)]) -> Vec<f64> {
+    let mut q = gradient.to_vec();
+    let mut alphas = Vec::with_capacity(history.len());
+    for (s, y) in history.iter().rev() {
+        let alpha = dot(s, &q) / dot(y, s);
+        q.iter_mut()
+            .zip(y.iter())
+            .for_each(|(q, y)| *q -= alpha * y);
+        alphas.push(alpha);
+    }
+
+    let gamma = history
+        .last()
+        .map(|(s, y)| dot(s, y) / dot(y, y))
+        .unwrap_or(1.0);
+    let mut r: Vec<f64> = q.iter().map(|a| gamma * a).collect();
+
+    for ((s, y), alpha) in history.iter().zip(alphas.iter().rev()) {
+        let beta = dot(y, &r) / dot(y, s);
+        r.iter_mut()
+            .zip(s.iter())
+            .for_each(|(r, s)| *r += (alpha - beta) * s);
+    }
+    r.iter().map(|a| -a).collect()
+}
+
+//  --- Unit tests
+#[cfg(test)]
+mod test_bfgs {
+
+    use super::*;
+    use crate::assert_approx_eq;
+
+    fn rosenbrock(p: &[f64]) -> f64 {
+        (1.0 - p[0]).powi(2) + 100.0 * (p[1] - p[0].powi(2)).powi(2)
+    }
+
+    fn rosenbrock_gradient(p: &[f64]) -> Vec<f64> {
+        vec![
+            -2.0 * (1.0 - p[0]) - 400.0 * p[0] * (p[1] - p[0].powi(2)),
+            200.0 * (p[1] - p[0].powi(2)),
+        ]
+    }
+
+    #[test]
+    fn test_bfgs() {
+        let minimum = Bfgs::new()
+            .minimise_with_gradient(rosenbrock, rosenbrock_gradient, &[-1.2, 1.0])
+            .unwrap();
+        assert_approx_eq!(minimum.parameters()[0], 1.0, 1e-6);
+        assert_approx_eq!(minimum.parameters()[1], 1.0, 1e-6);
+        assert!(minimum.value() < 1e-12);
+
+        let minimum = Bfgs::new().minimise(rosenbrock, &[-1.2, 1.0]).unwrap();
+        assert_approx_eq!(minimum.parameters()[0], 1.0, 1e-5);
+        assert_approx_eq!(minimum.parameters()[1], 1.0, 1e-5);
+    }
+
+    #[test]
+    fn test_limited_memory() {
+        // Sum of n shifted quadratics with differing curvature.
+        let objective = |p: &[f64]| {
+            p.iter()
+                .enumerate()
+                .map(|(i, a)| (i + 1) as f64 * (a - i as f64).powi(2))
+                .sum::<f64>()
+        };
+        let minimum = Bfgs::new()
+            .memory(3)
+            .minimise(objective, &[0.0; 10])
+            .unwrap();
+        for (i, p) in minimum.parameters().iter().enumerate() {
+            assert_approx_eq!(p, i as f64, 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_bounds() {
+        let bounds = Bounds::new(&[-2.0, -2.0], &[0.5, 2.0]).unwrap();
+        let minimum = Bfgs::new()
+            .bounds(bounds)
+            .minimise_with_gradient(rosenbrock, rosenbrock_gradient, &[-1.2, 1.0])
+            .unwrap();
+        assert_approx_eq!(minimum.parameters()[0], 0.5, 1e-12);
+        assert_approx_eq!(minimum.parameters()[1], 0.25, 1e-6);
+        assert!(minimum.reason().is_some());
+    }
+
+    #[test]
+    fn test_max_iterations() {
+        match Bfgs::new().max_iter(2).minimise(rosenbrock, &[-1.2, 1.0]) {
+            Err(Error::MaxIterations(minimum)) => {
+                assert_eq!(minimum.iterations(), 2);
+                assert!(minimum.value() < rosenbrock(&[-1.2, 1.0]));
+            }
+            _ => panic!("expected MaxIterations"),
+        }
+    }
+}
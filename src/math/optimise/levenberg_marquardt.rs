@@ -0,0 +1,366 @@
+//! Levenberg-Marquardt least squares.
+//!
+//! Minimises S(p) = Σ ri(p)^2. Each iteration solves the damped normal equations
+//! (J'J + λ.diag(J'J)).δ = -J'r for the step δ, where J is the Jacobian of the
+//! residuals. The damping λ rises when a step fails to reduce S, tending towards
+//! gradient descent, and falls when it succeeds, tending towards Gauss-Newton.
+
+use super::super::linalg::{dot, norm, solve_linear_system, Matrix};
+use super::{
+    derivative_step, initial_parameters, Bounds, ConvergenceReasons, Error, Minimum, Result,
+};
+
+//  --- Constants
+
+const INITIAL_DAMPING: f64 = 1e-3;
+
+/// Damping above which no step reduces the sum of squares.
+const MAX_DAMPING: f64 = 1e16;
+
+//  --- Structs
+
+#[derive(Clone, Debug)]
+pub struct LevenbergMarquardt {
+    bounds: Option<Bounds>,
+    max_iter: usize,
+    tolerance: f64,
+    gradient_tolerance: f64,
+    step_tolerance: f64,
+}
+
+//  --- Implementations
+
+impl LevenbergMarquardt {
+    pub fn new() -> Self {
+        Self {
+            bounds: None,
+            max_iter: 200,
+            tolerance: 1e-12,
+            gradient_tolerance: 1e-14,
+            step_tolerance: 1e-12,
+        }
+    }
+
+    pub fn bounds(self, bounds: Bounds) -> Self {
+        Self {
+            bounds: Some(bounds),
+            ..self
+        }
+    }
+
+    pub fn max_iter(self, max_iter: usize) -> Self {
+        Self { max_iter, ..self }
+    }
+
+    /// Relative reduction in the sum of squares below which iteration stops.
+    pub fn tolerance(self, tolerance: f64) -> Self {
+        Self { tolerance, ..self }
+    }
+
+    /// Largest absolute element of J'r at which iteration stops.
+    pub fn gradient_tolerance(self, gradient_tolerance: f64) -> Self {
+        Self {
+            gradient_tolerance,
+            ..self
+        }
+    }
+
+    /// Step, relative to the parameters, below which iteration stops.
+    pub fn step_tolerance(self, step_tolerance: f64) -> Self {
+        Self {
+            step_tolerance,
+            ..self
+        }
+    }
+
+    /// Minimises the sum of squared 'residuals', with a forward difference Jacobian,
+    /// stepping backwards next to an upper bound.
+    pub fn minimise<F>(&self, residuals: F, initial: &[f64]) -> Result<Minimum>
+    where
+        F: Fn(&[f64]) -> Vec<f64>,
+    {
+        let jacobian = |parameters: &[f64]| {
+            let base = residuals(parameters);
+            Ok((0..parameters.len())
+                .map(|j| {
+                    let step = derivative_step(parameters, j, self.bounds.as_ref());
+                    let mut shifted = parameters.to_vec();
+                    shifted[j] += step;
+                    residuals(&shifted)
+                        .iter()
+                        .zip(base.iter())
+                        .map(|(a, b)| (a - b) / step)
+                        .collect::<Vec<f64>>()
+                })
+                .collect::<Vec<Vec<f64>>>())
+        };
+        self.solve(&residuals, jacobian, initial)
+    }
+
+    /// Minimises the sum of squared 'residuals', with an analytic 'jacobian', returning
+    /// the derivative of each residual (rows) with respect to each parameter (columns).
+    pub fn minimise_with_jacobian<F, J>(
+        &self,
+        residuals: F,
+        jacobian: J,
+        initial: &[f64],
+    ) -> Result<Minimum>
+    where
+        F: Fn(&[f64]) -> Vec<f64>,
+        J: Fn(&[f64]) -> Vec<Vec<f64>>,
+    {
+        let transposed = |parameters: &[f64]| {
+            Ok(Matrix::from_rows(&jacobian(parameters))?
+                .transpose()
+                .to_rows())
+        };
+        self.solve(&residuals, transposed, initial)
+    }
+
+    /// 'jacobian' returns the derivative of the residuals with respect to each
+    /// parameter, one vector per parameter, that is the columns of the Jacobian.
+    fn solve<F, J>(&self, residuals: &F, jacobian: J, initial: &[f64]) -> Result<Minimum>
+    where
+        F: Fn(&[f64]) -> Vec<f64>,
+        J: Fn(&[f64]) -> Result<Vec<Vec<f64>>>,
+    {
+        let mut parameters = initial_parameters(initial, self.bounds.as_ref())?;
+        let k = parameters.len();
+        let mut r = residuals(&parameters);
+        let mut cost = sum_of_squares(&r);
+        if !cost.is_finite() {
+            return Err(Error::NonFiniteValue(parameters));
+        }
+        let mut damping = INITIAL_DAMPING;
+
+        for iteration in 1..=self.max_iter {
+            let columns = jacobian(&parameters)?;
+            if columns.len() != k {
+                return Err(Error::MismatchedDimensions(k, columns.len()));
+            }
+
+            // Parameters held at a bound are excluded from the step.
+            let mut gradient: Vec<f64> = columns.iter().map(|column| -dot(column, &r)).collect();
+            let active: Vec<bool> = (0..k)
+                .map(|i| match &self.bounds {
+                    Some(bounds) => bounds.is_active(&parameters, i, gradient[i]),
+                    None => false,
+                })
+                .collect();
+            for (g, active) in gradient.iter_mut().zip(active.iter()) {
+                if *active {
+                    *g = 0.0;
+                }
+            }
+            if gradient.iter().all(|g| g.abs() <= self.gradient_tolerance) {
+                return Ok(minimum(
+                    parameters,
+                    cost,
+                    iteration - 1,
+                    ConvergenceReasons::Gradient,
+                ));
+            }
+            let jtj: Vec<Vec<f64>> = columns
+                .iter()
+                .zip(active.iter())
+                .map(|(a, a_active)| {
+                    columns
+                        .iter()
+                        .zip(active.iter())
+                        .map(|(b, b_active)| match *a_active || *b_active {
+                            true => 0.0,
+                            false => dot(a, b),
+                        })
+                        .collect()
+                })
+                .collect();
+
+            // Raise the damping until the step reduces the sum of squares.
+            loop {
+                let mut damped = jtj.clone();
+                for (i, row) in damped.iter_mut().enumerate() {
+                    row[i] += damping * jtj[i][i].max(f64::EPSILON);
+                }
+
+                let candidate = solve_linear_system(&damped, &gradient).ok().map(|step| {
+                    let mut p: Vec<f64> = parameters
+                        .iter()
+                        .zip(step.iter())
+                        .map(|(a, b)| a + b)
+                        .collect();
+                    if let Some(bounds) = &self.bounds {
+                        bounds.project(&mut p);
+                    }
+                    p
+                });
+
+                if let Some(candidate) = candidate {
+                    let candidate_r = residuals(&candidate);
+                    let candidate_cost = sum_of_squares(&candidate_r);
+                    if candidate_cost.is_finite() && candidate_cost <= cost {
+                        let step = distance(&candidate, &parameters);
+                        let scale = norm(&parameters);
+                        let reduction = cost - candidate_cost;
+
+                        parameters = candidate;
+                        r = candidate_r;
+                        damping = (damping / 10.0).max(f64::EPSILON);
+
+                        if reduction <= self.tolerance * cost {
+                            return Ok(minimum(
+                                parameters,
+                                candidate_cost,
+                                iteration,
+                                ConvergenceReasons::Function,
+                            ));
+                        }
+                        if step <= self.step_tolerance * (scale + self.step_tolerance) {
+                            return Ok(minimum(
+                                parameters,
+                                candidate_cost,
+                                iteration,
+                                ConvergenceReasons::Step,
+                            ));
+                        }
+                        cost = candidate_cost;
+                        break;
+                    }
+                }
+
+                damping *= 10.0;
+                if damping > MAX_DAMPING {
+                    // No step, however small, reduces the sum of squares.
+                    return Ok(minimum(
+                        parameters,
+                        cost,
+                        iteration,
+                        ConvergenceReasons::Step,
+                    ));
+                }
+            }
+        }
+
+        Err(Error::MaxIterations(Minimum {
+            parameters,
+            value: cost,
+            iterations: self.max_iter,
+            reason: None,
+        }))
+    }
+}
+
+//  --- Trait implementations: Concrete
+
+impl Default for LevenbergMarquardt {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+//  --- Standalone functions
+
+fn minimum(
+    parameters: Vec<f64>,
+    value: f64,
+    iterations: usize,
+    reason: ConvergenceReasons,
+) -> Minimum {
+    Minimum {
+        parameters,
+        value,
+        iterations,
+        reason: Some(reason),
+    }
+}
+
+fn sum_of_squares(r: &[f64]) -> f64 {
+    r.iter().map(|a| a.powi(2)).sum()
+}
+
+fn distance(a: &[f64], b: &[f64]) -> f64 {
+    a.iter()
+        .zip(b.iter())
+        .map(|(a, b)| (a - b).powi(2))
+        .sum::<f64>()
+        .sqrt()
+}
+
+//  --- Unit tests
+#[cfg(test)]
+mod test_levenberg_marquardt {
+
+    use super::*;
+    use crate::assert_approx_eq;
+
+    /// Residuals of y = a.e^(b.x), fit to exact data with a = 2 and b = -0.5.
+    fn exponential(p: &[f64]) -> Vec<f64> {
+        (0..10)
+            .map(|i| {
+                let x = i as f64 * 0.5;
+                2.0 * (-0.5 * x).exp() - p[0] * (p[1] * x).exp()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_finite_difference_jacobian() {
+        let minimum = LevenbergMarquardt::new()
+            .minimise(exponential, &[1.0, 0.0])
+            .unwrap();
+        assert_approx_eq!(minimum.parameters()[0], 2.0, 1e-7);
+        assert_approx_eq!(minimum.parameters()[1], -0.5, 1e-7);
+        assert!(minimum.value() < 1e-14);
+        assert!(minimum.reason().is_some());
+    }
+
+    #[test]
+    fn test_analytic_jacobian() {
+        let jacobian = |p: &[f64]| {
+            (0..10)
+                .map(|i| {
+                    let x = i as f64 * 0.5;
+                    let e = (p[1] * x).exp();
+                    vec![-e, -p[0] * x * e]
+                })
+                .collect()
+        };
+        let minimum = LevenbergMarquardt::new()
+            .minimise_with_jacobian(exponential, jacobian, &[1.0, 0.0])
+            .unwrap();
+        assert_approx_eq!(minimum.parameters()[0], 2.0, 1e-9);
+        assert_approx_eq!(minimum.parameters()[1], -0.5, 1e-9);
+    }
+
+    #[test]
+    fn test_bounds() {
+        let bounds = Bounds::new(&[0.0, -0.3], &[10.0, 0.0]).unwrap();
+        let minimum = LevenbergMarquardt::new()
+            .bounds(bounds)
+            .minimise(exponential, &[1.0, 0.0])
+            .unwrap();
+        assert_approx_eq!(minimum.parameters()[1], -0.3, 1e-12);
+    }
+
+    #[test]
+    fn test_errors() {
+        let optimiser = LevenbergMarquardt::new();
+        assert!(matches!(
+            optimiser.minimise(exponential, &[]),
+            Err(Error::NoParameters)
+        ));
+        assert!(matches!(
+            optimiser
+                .clone()
+                .bounds(Bounds::new(&[0.0], &[1.0]).unwrap())
+                .minimise(exponential, &[1.0, 0.0]),
+            Err(Error::MismatchedDimensions(2, 1))
+        ));
+        match optimiser.max_iter(1).minimise(exponential, &[1.0, 0.0]) {
+            Err(Error::MaxIterations(minimum)) => {
+                assert_eq!(minimum.iterations(), 1);
+                assert_eq!(minimum.reason(), None);
+            }
+            _ => panic!("expected MaxIterations"),
+        }
+    }
+}
@@ -0,0 +1,287 @@
+//! Multivariate optimisation.
+//!
+//! Minimises an objective over several parameters, optionally within bounds:
+//! - Levenberg-Marquardt: least squares, minimising the sum of squared residuals,
+//!   with an analytic or finite difference Jacobian.
+//! - BFGS and L-BFGS: quasi-Newton, for smooth objectives, with an analytic or finite
+//!   difference gradient.
+//! - Nelder-Mead: derivative free simplex search, for noisy or non-smooth objectives.
+//!
+//! Bounds are enforced by projecting each trial point onto the bounds, holding
+//! parameters at a bound fixed while the objective pushes against it. Each method
+//! returns the minimum with the iterations taken and the reason iteration stopped,
+//! or an error holding the best point found if the iteration cap is reached.
+//!
+//! ```rust,ignore
+//! let minimum = LevenbergMarquardt::new()
+//!     .bounds(Bounds::new(&lower, &upper)?)
+//!     .minimise(|p| residuals(p), &initial)?;
+//!
+//! let minimum = Bfgs::new().memory(5).minimise(|p| objective(p), &initial)?;
+//! ```
+
+pub mod bfgs;
+pub mod levenberg_marquardt;
+pub mod nelder_mead;
+
+use super::linalg;
+
+pub use bfgs::Bfgs;
+pub use levenberg_marquardt::LevenbergMarquardt;
+pub use nelder_mead::NelderMead;
+
+//  --- Constants
+
+/// Step, relative to each parameter, used to differentiate numerically.
+const DERIVATIVE_STEP: f64 = 1e-7;
+
+//  --- Errors
+type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+    NoParameters,
+    MismatchedDimensions(usize, usize),
+    InvalidBounds,
+    NonFiniteValue(Vec<f64>),
+    MaxIterations(Minimum),
+    LinearAlgebraError(linalg::Error),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoParameters => write!(f, "At least one parameter is required."),
+            Self::MismatchedDimensions(expected, given) => write!(
+                f,
+                "{} parameters are expected, {} were given.",
+                expected, given
+            ),
+            Self::InvalidBounds => write!(f, "Lower bounds should not exceed upper bounds."),
+            Self::NonFiniteValue(x) => write!(f, "The objective is not finite at {:?}.", x),
+            Self::MaxIterations(minimum) => write!(
+                f,
+                "No convergence after {} iterations, the objective at {:?} is {}.",
+                minimum.iterations, minimum.parameters, minimum.value
+            ),
+            Self::LinearAlgebraError(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<linalg::Error> for Error {
+    fn from(value: linalg::Error) -> Self {
+        Self::LinearAlgebraError(value)
+    }
+}
+
+//  --- Enums
+
+/// Reason iteration stopped.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ConvergenceReasons {
+    /// The relative change in the objective is within the tolerance.
+    Function,
+    /// The gradient is within the gradient tolerance.
+    Gradient,
+    /// The last step, or the simplex, is within the step tolerance.
+    Step,
+}
+
+//  --- Structs
+
+/// Lower and upper bounds on each parameter. Infinite bounds leave a parameter unbounded.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bounds {
+    lower: Vec<f64>,
+    upper: Vec<f64>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Minimum {
+    parameters: Vec<f64>,
+    value: f64,
+    iterations: usize,
+    reason: Option<ConvergenceReasons>,
+}
+
+//  --- Traits
+
+/// Minimiser of a scalar objective.
+pub trait Minimise {
+    fn minimise<F>(&self, objective: F, initial: &[f64]) -> Result<Minimum>
+    where
+        F: Fn(&[f64]) -> f64;
+}
+
+//  --- Implementations
+
+impl Bounds {
+    pub fn new(lower: &[f64], upper: &[f64]) -> Result<Self> {
+        if lower.len() != upper.len() {
+            return Err(Error::MismatchedDimensions(lower.len(), upper.len()));
+        }
+        if lower
+            .iter()
+            .zip(upper.iter())
+            .any(|(l, u)| l.is_nan() || u.is_nan() || l > u)
+        {
+            return Err(Error::InvalidBounds);
+        }
+        Ok(Self {
+            lower: lower.to_vec(),
+            upper: upper.to_vec(),
+        })
+    }
+
+    pub fn lower(&self) -> &[f64] {
+        &self.lower
+    }
+
+    pub fn upper(&self) -> &[f64] {
+        &self.upper
+    }
+
+    /// Clamps each parameter within its bounds.
+    pub fn project(&self, parameters: &mut [f64]) {
+        for ((p, l), u) in parameters
+            .iter_mut()
+            .zip(self.lower.iter())
+            .zip(self.upper.iter())
+        {
+            *p = p.clamp(*l, *u);
+        }
+    }
+
+    /// Whether parameter 'i' is held at a bound, as moving in 'direction' would leave it.
+    fn is_active(&self, parameters: &[f64], i: usize, direction: f64) -> bool {
+        (parameters[i] <= self.lower[i] && direction < 0.0)
+            || (parameters[i] >= self.upper[i] && direction > 0.0)
+    }
+
+    fn validate(&self, parameters: &[f64]) -> Result<()> {
+        if self.lower.len() != parameters.len() {
+            return Err(Error::MismatchedDimensions(
+                parameters.len(),
+                self.lower.len(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Minimum {
+    pub fn parameters(&self) -> &[f64] {
+        &self.parameters
+    }
+
+    /// Objective at the minimum. For least squares, the sum of squared residuals.
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    pub fn iterations(&self) -> usize {
+        self.iterations
+    }
+
+    /// Reason iteration stopped, or None if the iteration cap was reached.
+    pub fn reason(&self) -> Option<ConvergenceReasons> {
+        self.reason
+    }
+}
+
+//  --- Standalone functions
+
+/// Checks the initial parameters, and projects them within 'bounds'.
+fn initial_parameters(initial: &[f64], bounds: Option<&Bounds>) -> Result<Vec<f64>> {
+    if initial.is_empty() {
+        return Err(Error::NoParameters);
+    }
+    let mut parameters = initial.to_vec();
+    if let Some(bounds) = bounds {
+        bounds.validate(initial)?;
+        bounds.project(&mut parameters);
+    }
+    Ok(parameters)
+}
+
+/// Finite difference step for parameter 'i', stepping backwards if a forward
+/// step would leave the bounds.
+fn derivative_step(parameters: &[f64], i: usize, bounds: Option<&Bounds>) -> f64 {
+    let step = DERIVATIVE_STEP * parameters[i].abs().max(1.0);
+    match bounds {
+        Some(bounds) if parameters[i] + step > bounds.upper[i] => -step,
+        _ => step,
+    }
+}
+
+/// Central difference gradient of 'objective', or forward difference for parameters
+/// within a step of a bound.
+fn numerical_gradient<F>(objective: &F, parameters: &[f64], bounds: Option<&Bounds>) -> Vec<f64>
+where
+    F: Fn(&[f64]) -> f64,
+{
+    let value = objective(parameters);
+    (0..parameters.len())
+        .map(|i| {
+            let step = derivative_step(parameters, i, bounds);
+            let shift = |step: f64| {
+                let mut shifted = parameters.to_vec();
+                shifted[i] += step;
+                objective(&shifted)
+            };
+            let central = match bounds {
+                Some(bounds) => {
+                    parameters[i] - step.abs() >= bounds.lower[i]
+                        && parameters[i] + step.abs() <= bounds.upper[i]
+                }
+                None => true,
+            };
+            if central {
+                (shift(step) - shift(-step)) / (2.0 * step)
+            } else {
+                (shift(step) - value) / step
+            }
+        })
+        .collect()
+}
+
+//  --- Unit tests
+#[cfg(test)]
+mod test_optimise {
+
+    use super::*;
+    use crate::assert_approx_eq;
+
+    #[test]
+    fn test_bounds() {
+        let bounds = Bounds::new(&[0.0, f64::NEG_INFINITY], &[1.0, 2.0]).unwrap();
+        let mut parameters = vec![1.5, -10.0];
+        bounds.project(&mut parameters);
+        assert_eq!(parameters, vec![1.0, -10.0]);
+
+        assert!(matches!(
+            Bounds::new(&[1.0], &[0.0]),
+            Err(Error::InvalidBounds)
+        ));
+        assert!(matches!(
+            Bounds::new(&[0.0], &[1.0, 2.0]),
+            Err(Error::MismatchedDimensions(1, 2))
+        ));
+    }
+
+    #[test]
+    fn test_numerical_gradient() {
+        let objective = |p: &[f64]| p[0].powi(2) + 3.0 * p[1];
+        let gradient = numerical_gradient(&objective, &[2.0, 1.0], None);
+        assert_approx_eq!(gradient[0], 4.0, 1e-8);
+        assert_approx_eq!(gradient[1], 3.0, 1e-8);
+
+        // Steps backwards at an upper bound.
+        let bounds = Bounds::new(&[0.0, 0.0], &[2.0, 1.0]).unwrap();
+        let gradient = numerical_gradient(&objective, &[2.0, 1.0], Some(&bounds));
+        assert_approx_eq!(gradient[0], 4.0, 1e-5);
+    }
+}
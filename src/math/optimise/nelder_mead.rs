@@ -0,0 +1,304 @@
+//! Nelder-Mead simplex search.
+//!
+//! Derivative free: a simplex of n + 1 points is moved through the parameter space by
+//! reflecting, expanding and contracting its worst point through the centroid of the
+//! others, and shrinking towards its best point when none of these improve. Suited to
+//! objectives that are noisy or not differentiable, at the cost of slow convergence.
+
+use super::{initial_parameters, Bounds, ConvergenceReasons, Error, Minimise, Minimum, Result};
+
+//  --- Constants
+
+const REFLECTION: f64 = 1.0;
+const EXPANSION: f64 = 2.0;
+const CONTRACTION: f64 = 0.5;
+const SHRINK: f64 = 0.5;
+
+//  --- Structs
+
+#[derive(Clone, Debug)]
+pub struct NelderMead {
+    bounds: Option<Bounds>,
+    initial_step: f64,
+    max_iter: usize,
+    tolerance: f64,
+    step_tolerance: f64,
+}
+
+//  --- Implementations
+
+impl NelderMead {
+    pub fn new() -> Self {
+        Self {
+            bounds: None,
+            initial_step: 0.05,
+            max_iter: 2000,
+            tolerance: 1e-12,
+            step_tolerance: 1e-10,
+        }
+    }
+
+    pub fn bounds(self, bounds: Bounds) -> Self {
+        Self {
+            bounds: Some(bounds),
+            ..self
+        }
+    }
+
+    /// Size of the initial simplex, relative to each parameter, or absolute for
+    /// parameters of zero.
+    pub fn initial_step(self, initial_step: f64) -> Self {
+        Self {
+            initial_step,
+            ..self
+        }
+    }
+
+    pub fn max_iter(self, max_iter: usize) -> Self {
+        Self { max_iter, ..self }
+    }
+
+    /// Spread of the objective over the simplex below which iteration stops.
+    pub fn tolerance(self, tolerance: f64) -> Self {
+        Self { tolerance, ..self }
+    }
+
+    /// Size of the simplex below which iteration stops.
+    pub fn step_tolerance(self, step_tolerance: f64) -> Self {
+        Self {
+            step_tolerance,
+            ..self
+        }
+    }
+
+    fn project(&self, mut x: Vec<f64>) -> Vec<f64> {
+        if let Some(bounds) = &self.bounds {
+            bounds.project(&mut x);
+        }
+        x
+    }
+
+    fn initial_simplex(&self, x: &[f64]) -> Vec<Vec<f64>> {
+        let mut simplex = vec![x.to_vec()];
+        for i in 0..x.len() {
+            let step = if x[i] == 0.0 {
+                self.initial_step
+            } else {
+                self.initial_step * x[i]
+            };
+            let mut vertex = x.to_vec();
+            vertex[i] += step;
+            // Step the other way if the bounds collapse the vertex onto 'x'.
+            if self.project(vertex.clone())[i] == x[i] {
+                vertex[i] = x[i] - step;
+            }
+            simplex.push(self.project(vertex));
+        }
+        simplex
+    }
+}
+
+//  --- Trait implementations: Concrete
+
+impl Default for NelderMead {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Minimise for NelderMead {
+    fn minimise<F>(&self, objective: F, initial: &[f64]) -> Result<Minimum>
+    where
+        F: Fn(&[f64]) -> f64,
+    {
+        let x = initial_parameters(initial, self.bounds.as_ref())?;
+        let n = x.len();
+        // Non-finite values are treated as worse than any finite value.
+        let evaluate = |x: &[f64]| match objective(x) {
+            value if value.is_nan() => f64::INFINITY,
+            value => value,
+        };
+
+        let mut simplex: Vec<(Vec<f64>, f64)> = self
+            .initial_simplex(&x)
+            .into_iter()
+            .map(|vertex| {
+                let value = evaluate(&vertex);
+                (vertex, value)
+            })
+            .collect();
+        if !simplex[0].1.is_finite() {
+            return Err(Error::NonFiniteValue(x));
+        }
+
+        for iteration in 1..=self.max_iter {
+            simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+            let (best, worst) = (simplex[0].1, simplex[n].1);
+
+            if (worst - best).abs() <= self.tolerance * (best.abs() + self.tolerance) {
+                let (x, value) = simplex.swap_remove(0);
+                return Ok(minimum(
+                    x,
+                    value,
+                    iteration - 1,
+                    ConvergenceReasons::Function,
+                ));
+            }
+            let size = simplex[1..]
+                .iter()
+                .flat_map(|(v, _)| {
+                    v.iter()
+                        .zip(simplex[0].0.iter())
+                        .map(|(a, b)| (a - b).abs())
+                })
+                .fold(0.0, f64::max);
+            if size <= self.step_tolerance {
+                let (x, value) = simplex.swap_remove(0);
+                return Ok(minimum(x, value, iteration - 1, ConvergenceReasons::Step));
+            }
+
+            let centroid: Vec<f64> = (0..n)
+                .map(|j| simplex[..n].iter().map(|(v, _)| v[j]).sum::<f64>() / n as f64)
+                .collect();
+            let towards = |coefficient: f64| -> Vec<f64> {
+                self.project(
+                    centroid
+                        .iter()
+                        .zip(simplex[n].0.iter())
+                        .map(|(c, w)| c + coefficient * (c - w))
+                        .collect(),
+                )
+            };
+
+            let reflected = towards(REFLECTION);
+            let reflected_value = evaluate(&reflected);
+
+            let replacement = if reflected_value < best {
+                let expanded = towards(EXPANSION);
+                let expanded_value = evaluate(&expanded);
+                if expanded_value < reflected_value {
+                    Some((expanded, expanded_value))
+                } else {
+                    Some((reflected, reflected_value))
+                }
+            } else if reflected_value < simplex[n - 1].1 {
+                Some((reflected, reflected_value))
+            } else {
+                // Contract outside if the reflection improved on the worst point,
+                // otherwise inside.
+                let (contracted, limit) = if reflected_value < worst {
+                    (towards(CONTRACTION * REFLECTION), reflected_value)
+                } else {
+                    (towards(-CONTRACTION), worst)
+                };
+                let contracted_value = evaluate(&contracted);
+                (contracted_value < limit).then_some((contracted, contracted_value))
+            };
+
+            match replacement {
+                Some(vertex) => simplex[n] = vertex,
+                None => {
+                    let best = simplex[0].0.clone();
+                    for (vertex, value) in simplex[1..].iter_mut() {
+                        *vertex = self.project(
+                            vertex
+                                .iter()
+                                .zip(best.iter())
+                                .map(|(v, b)| b + SHRINK * (v - b))
+                                .collect(),
+                        );
+                        *value = evaluate(vertex);
+                    }
+                }
+            }
+        }
+
+        simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+        let (parameters, value) = simplex.swap_remove(0);
+        Err(Error::MaxIterations(Minimum {
+            parameters,
+            value,
+            iterations: self.max_iter,
+            reason: None,
+        }))
+    }
+}
+
+//  --- Standalone functions
+
+fn minimum(
+    parameters: Vec<f64>,
+    value: f64,
+    iterations: usize,
+    reason: ConvergenceReasons,
+) -> Minimum {
+    Minimum {
+        parameters,
+        value,
+        iterations,
+        reason: Some(reason),
+    }
+}
+
+//  --- Unit tests
+#[cfg(test)]
+mod test_nelder_mead {
+
+    use super::*;
+    use crate::assert_approx_eq;
+
+    fn rosenbrock(p: &[f64]) -> f64 {
+        (1.0 - p[0]).powi(2) + 100.0 * (p[1] - p[0].powi(2)).powi(2)
+    }
+
+    #[test]
+    fn test_nelder_mead() {
+        let minimum = NelderMead::new()
+            .initial_step(0.5)
+            .tolerance(1e-16)
+            .minimise(rosenbrock, &[-1.2, 1.0])
+            .unwrap();
+        assert_approx_eq!(minimum.parameters()[0], 1.0, 1e-5);
+        assert_approx_eq!(minimum.parameters()[1], 1.0, 1e-5);
+        assert!(minimum.reason().is_some());
+    }
+
+    #[test]
+    fn test_non_smooth() {
+        let objective = |p: &[f64]| (p[0] - 1.0).abs() + (p[1] + 2.0).abs();
+        let minimum = NelderMead::new()
+            .initial_step(1.0)
+            .minimise(objective, &[0.0, 0.0])
+            .unwrap();
+        assert_approx_eq!(minimum.parameters()[0], 1.0, 1e-8);
+        assert_approx_eq!(minimum.parameters()[1], -2.0, 1e-8);
+    }
+
+    #[test]
+    fn test_bounds() {
+        let bounds = Bounds::new(&[-2.0, -2.0], &[0.5, 2.0]).unwrap();
+        let minimum = NelderMead::new()
+            .bounds(bounds)
+            .tolerance(1e-16)
+            .minimise(rosenbrock, &[-1.2, 1.0])
+            .unwrap();
+        assert!(minimum.parameters()[0] <= 0.5);
+        assert_approx_eq!(minimum.parameters()[0], 0.5, 1e-6);
+        assert_approx_eq!(minimum.parameters()[1], 0.25, 1e-5);
+    }
+
+    #[test]
+    fn test_errors() {
+        let objective = |p: &[f64]| p[0].ln();
+        assert!(matches!(
+            NelderMead::new().minimise(objective, &[-1.0]),
+            Err(Error::NonFiniteValue(_))
+        ));
+        assert!(matches!(
+            NelderMead::new()
+                .max_iter(3)
+                .minimise(rosenbrock, &[-1.2, 1.0]),
+            Err(Error::MaxIterations(_))
+        ));
+    }
+}
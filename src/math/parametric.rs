@@ -0,0 +1,614 @@
+//! Parametric yield curves.
+//!
+//! Nelson-Siegel models the continuously compounded zero rate at time t as
+//! r(t) = β0 + β1.L(t, τ1) + β2.C(t, τ1), where L(t, τ) = (1 - e^(-t/τ)) / (t/τ)
+//! and C(t, τ) = L(t, τ) - e^(-t/τ). Svensson adds a second hump, β3.C(t, τ2).
+//! The instantaneous forward is f(t) = β0 + β1.e^(-t/τ1) + β2.(t/τ1).e^(-t/τ1) + β3.(t/τ2).e^(-t/τ2).
+//!
+//! Parameters are ordered as [β0, β1, β2, τ1] and [β0, β1, β2, β3, τ1, τ2].
+//!
+//! Fitting minimises the weighted sum of squared residuals with Levenberg-Marquardt,
+//! starting from the best of a grid of decay parameters, for which the betas are
+//! solved by linear least squares.
+//!
+//! ```rust,ignore
+//! let fit = CurveFitter::new(ParametricModels::NelsonSiegelSvensson)
+//!     .weights(&weights)
+//!     .fit(&maturities, &zero_rates)?;
+//!
+//! let rate = fit.curve().rate(&7.5);
+//! println!("{} after {} iterations", fit.rmse(), fit.iterations());
+//! ```
+
+use super::linalg::{Matrix, Qr};
+use super::optimise::{self, Bounds, LevenbergMarquardt};
+
+//  --- Constants
+
+/// Decay parameters, in years, from which the fit is started.
+const DECAY_GRID: [f64; 9] = [0.25, 0.5, 1.0, 1.5, 2.0, 3.0, 5.0, 7.5, 10.0];
+
+//  --- Errors
+type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+    MismatchedLengths(usize, usize),
+    InsufficientPoints(usize, usize),
+    InvalidParameters(usize, usize),
+    InvalidWeights,
+    InvalidBounds,
+    OptimisationError(optimise::Error),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MismatchedLengths(x, y) => {
+                write!(f, "x has {} points, while y has {} points.", x, y)
+            }
+            Self::InsufficientPoints(required, n) => {
+                write!(
+                    f,
+                    "At least {} points are required, {} were given.",
+                    required, n
+                )
+            }
+            Self::InvalidParameters(required, n) => {
+                write!(f, "{} parameters are required, {} were given.", required, n)
+            }
+            Self::InvalidWeights => write!(f, "Weights should be non-negative and finite."),
+            Self::InvalidBounds => write!(
+                f,
+                "Lower bounds should not exceed upper bounds, and decay parameters should be positive."
+            ),
+            Self::OptimisationError(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<optimise::Error> for Error {
+    fn from(value: optimise::Error) -> Self {
+        Self::OptimisationError(value)
+    }
+}
+
+//  --- Enums
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ParametricModels {
+    #[default]
+    NelsonSiegel,
+    NelsonSiegelSvensson,
+}
+
+//  --- Structs
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParametricCurve {
+    model: ParametricModels,
+    parameters: Vec<f64>,
+}
+
+/// Fitted curve, with diagnostics.
+#[derive(Clone, Debug)]
+pub struct Fit {
+    curve: ParametricCurve,
+    residuals: Vec<f64>,
+    rmse: f64,
+    iterations: usize,
+    converged: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct CurveFitter {
+    model: ParametricModels,
+    weights: Option<Vec<f64>>,
+    lower: Vec<f64>,
+    upper: Vec<f64>,
+    initial: Option<Vec<f64>>,
+    max_iter: usize,
+    tolerance: f64,
+}
+
+//  --- Implementations
+
+impl ParametricModels {
+    pub fn parameter_count(&self) -> usize {
+        match self {
+            Self::NelsonSiegel => 4,
+            Self::NelsonSiegelSvensson => 6,
+        }
+    }
+
+    /// Index of the first decay parameter. All preceding parameters are betas.
+    fn decay_index(&self) -> usize {
+        self.parameter_count() - self.decay_count()
+    }
+
+    fn decay_count(&self) -> usize {
+        match self {
+            Self::NelsonSiegel => 1,
+            Self::NelsonSiegelSvensson => 2,
+        }
+    }
+
+    /// Betas are unbounded, and decay parameters lie between a week and 50 years.
+    fn default_bounds(&self) -> (Vec<f64>, Vec<f64>) {
+        let betas = self.decay_index();
+        let mut lower = vec![f64::NEG_INFINITY; betas];
+        let mut upper = vec![f64::INFINITY; betas];
+        lower.extend(vec![7.0 / 365.0; self.decay_count()]);
+        upper.extend(vec![50.0; self.decay_count()]);
+        (lower, upper)
+    }
+
+    /// Coefficients of each beta in the zero rate at time 't', given the decay parameters.
+    fn loadings(&self, t: &f64, decays: &[f64]) -> Vec<f64> {
+        let mut loadings = vec![1.0];
+        for (i, tau) in decays.iter().enumerate() {
+            let (level, curvature) = factors(t, tau);
+            if i == 0 {
+                loadings.push(level);
+            }
+            loadings.push(curvature);
+        }
+        loadings
+    }
+}
+
+impl ParametricCurve {
+    pub fn new(model: ParametricModels, parameters: &[f64]) -> Result<Self> {
+        if parameters.len() != model.parameter_count() {
+            return Err(Error::InvalidParameters(
+                model.parameter_count(),
+                parameters.len(),
+            ));
+        }
+        if parameters[model.decay_index()..]
+            .iter()
+            .any(|tau| *tau <= 0.0)
+        {
+            return Err(Error::InvalidBounds);
+        }
+        Ok(Self {
+            model,
+            parameters: parameters.to_vec(),
+        })
+    }
+
+    pub fn model(&self) -> ParametricModels {
+        self.model
+    }
+
+    pub fn parameters(&self) -> &[f64] {
+        &self.parameters
+    }
+
+    /// Continuously compounded zero rate at time 't'.
+    pub fn rate(&self, t: &f64) -> f64 {
+        let (betas, decays) = self.parameters.split_at(self.model.decay_index());
+        self.model
+            .loadings(t, decays)
+            .iter()
+            .zip(betas.iter())
+            .map(|(l, b)| l * b)
+            .sum()
+    }
+
+    /// Instantaneous forward rate at time 't'.
+    pub fn forward(&self, t: &f64) -> f64 {
+        let p = &self.parameters;
+        match self.model {
+            ParametricModels::NelsonSiegel => {
+                let e = (-t / p[3]).exp();
+                p[0] + p[1] * e + p[2] * (t / p[3]) * e
+            }
+            ParametricModels::NelsonSiegelSvensson => {
+                let (e1, e2) = ((-t / p[4]).exp(), (-t / p[5]).exp());
+                p[0] + p[1] * e1 + p[2] * (t / p[4]) * e1 + p[3] * (t / p[5]) * e2
+            }
+        }
+    }
+
+    pub fn discount(&self, t: &f64) -> f64 {
+        (-self.rate(t) * t).exp()
+    }
+
+    /// Slope of the zero rate at time 't'. As t.r(t) integrates the forward,
+    /// r'(t) = (f(t) - r(t)) / t.
+    pub fn rate_derivative(&self, t: &f64) -> f64 {
+        if *t != 0.0 {
+            return (self.forward(t) - self.rate(t)) / t;
+        }
+        let p = &self.parameters;
+        match self.model {
+            ParametricModels::NelsonSiegel => (p[2] - p[1]) / (2.0 * p[3]),
+            ParametricModels::NelsonSiegelSvensson => {
+                (p[2] - p[1]) / (2.0 * p[4]) + p[3] / (2.0 * p[5])
+            }
+        }
+    }
+}
+
+impl Fit {
+    pub fn curve(&self) -> &ParametricCurve {
+        &self.curve
+    }
+
+    /// Observed less fitted values, unweighted.
+    pub fn residuals(&self) -> &[f64] {
+        &self.residuals
+    }
+
+    /// Root mean squared residual, unweighted.
+    pub fn rmse(&self) -> f64 {
+        self.rmse
+    }
+
+    pub fn iterations(&self) -> usize {
+        self.iterations
+    }
+
+    /// Whether the optimiser met its tolerance within the maximum iterations.
+    pub fn converged(&self) -> bool {
+        self.converged
+    }
+}
+
+impl CurveFitter {
+    pub fn new(model: ParametricModels) -> Self {
+        let (lower, upper) = model.default_bounds();
+        Self {
+            model,
+            weights: None,
+            lower,
+            upper,
+            initial: None,
+            max_iter: 200,
+            tolerance: 1e-12,
+        }
+    }
+
+    /// Weight of each observation. Observations are equally weighted by default.
+    pub fn weights(self, weights: &[f64]) -> Self {
+        Self {
+            weights: Some(weights.to_vec()),
+            ..self
+        }
+    }
+
+    /// Bounds on each parameter, ordered as the parameters.
+    pub fn bounds(self, lower: &[f64], upper: &[f64]) -> Self {
+        Self {
+            lower: lower.to_vec(),
+            upper: upper.to_vec(),
+            ..self
+        }
+    }
+
+    /// Parameters from which to start, in place of the grid search.
+    pub fn initial(self, initial: &[f64]) -> Self {
+        Self {
+            initial: Some(initial.to_vec()),
+            ..self
+        }
+    }
+
+    pub fn max_iter(self, max_iter: usize) -> Self {
+        Self { max_iter, ..self }
+    }
+
+    /// Relative reduction in the sum of squares below which the fit has converged.
+    pub fn tolerance(self, tolerance: f64) -> Self {
+        Self { tolerance, ..self }
+    }
+
+    /// Fits the model to zero rates, or bond yields, 'y' observed at times 'x'.
+    pub fn fit(&self, x: &[f64], y: &[f64]) -> Result<Fit> {
+        self.validate(x, y)?;
+        let weights = self.weights.clone().unwrap_or(vec![1.0; x.len()]);
+        let scale: Vec<f64> = weights.iter().map(|w| w.sqrt()).collect();
+
+        let residuals = |parameters: &[f64]| -> Vec<f64> {
+            let curve = ParametricCurve {
+                model: self.model,
+                parameters: parameters.to_vec(),
+            };
+            x.iter()
+                .zip(y.iter())
+                .zip(scale.iter())
+                .map(|((t, r), s)| s * (r - curve.rate(t)))
+                .collect()
+        };
+
+        let initial = match &self.initial {
+            Some(initial) => self.clamp(initial.clone()),
+            None => self.grid_search(x, y, &weights),
+        };
+        let optimiser = LevenbergMarquardt::new()
+            .bounds(Bounds::new(&self.lower, &self.upper)?)
+            .max_iter(self.max_iter)
+            .tolerance(self.tolerance);
+        let (minimum, converged) = match optimiser.minimise(residuals, &initial) {
+            Ok(minimum) => (minimum, true),
+            Err(optimise::Error::MaxIterations(minimum)) => (minimum, false),
+            Err(e) => return Err(e.into()),
+        };
+
+        let curve = ParametricCurve {
+            model: self.model,
+            parameters: minimum.parameters().to_vec(),
+        };
+        let residuals: Vec<f64> = x
+            .iter()
+            .zip(y.iter())
+            .map(|(t, r)| r - curve.rate(t))
+            .collect();
+        let rmse = (residuals.iter().map(|r| r.powi(2)).sum::<f64>() / x.len() as f64).sqrt();
+
+        Ok(Fit {
+            curve,
+            residuals,
+            rmse,
+            iterations: minimum.iterations(),
+            converged,
+        })
+    }
+
+    fn validate(&self, x: &[f64], y: &[f64]) -> Result<()> {
+        let k = self.model.parameter_count();
+        if x.len() != y.len() {
+            return Err(Error::MismatchedLengths(x.len(), y.len()));
+        }
+        if x.len() < k {
+            return Err(Error::InsufficientPoints(k, x.len()));
+        }
+        if let Some(weights) = &self.weights {
+            if weights.len() != x.len() {
+                return Err(Error::MismatchedLengths(x.len(), weights.len()));
+            }
+            if weights.iter().any(|w| !w.is_finite() || *w < 0.0) {
+                return Err(Error::InvalidWeights);
+            }
+        }
+        if let Some(initial) = &self.initial {
+            if initial.len() != k {
+                return Err(Error::InvalidParameters(k, initial.len()));
+            }
+        }
+        if self.lower.len() != k || self.upper.len() != k {
+            return Err(Error::InvalidParameters(
+                k,
+                self.lower.len().min(self.upper.len()),
+            ));
+        }
+        let invalid_decay = self.lower[self.model.decay_index()..]
+            .iter()
+            .any(|tau| tau.is_nan() || *tau <= 0.0);
+        let crossed = self.lower.iter().zip(self.upper.iter()).any(|(l, u)| l > u);
+        if invalid_decay || crossed {
+            return Err(Error::InvalidBounds);
+        }
+        Ok(())
+    }
+
+    fn clamp(&self, mut parameters: Vec<f64>) -> Vec<f64> {
+        for ((p, l), u) in parameters
+            .iter_mut()
+            .zip(self.lower.iter())
+            .zip(self.upper.iter())
+        {
+            *p = p.clamp(*l, *u);
+        }
+        parameters
+    }
+
+    /// For each combination of decay parameters in the grid, solves the betas by
+    /// weighted linear least squares, and returns the combination with the least error.
+    fn grid_search(&self, x: &[f64], y: &[f64], weights: &[f64]) -> Vec<f64> {
+        let decay_index = self.model.decay_index();
+        let bounded = |tau: &f64, i: usize| {
+            tau.clamp(self.lower[decay_index + i], self.upper[decay_index + i])
+        };
+        let candidates: Vec<Vec<f64>> = match self.model {
+            ParametricModels::NelsonSiegel => {
+                DECAY_GRID.iter().map(|a| vec![bounded(a, 0)]).collect()
+            }
+            ParametricModels::NelsonSiegelSvensson => DECAY_GRID
+                .iter()
+                .flat_map(|a| {
+                    DECAY_GRID
+                        .iter()
+                        .filter(move |b| *b > a)
+                        .map(move |b| vec![*a, *b])
+                })
+                .map(|decays| vec![bounded(&decays[0], 0), bounded(&decays[1], 1)])
+                .collect(),
+        };
+
+        let mut best: Option<(f64, Vec<f64>)> = None;
+        for decays in candidates {
+            let loadings: Vec<Vec<f64>> =
+                x.iter().map(|t| self.model.loadings(t, &decays)).collect();
+            let Some(betas) = weighted_least_squares(&loadings, y, weights) else {
+                continue;
+            };
+            let mut parameters = betas;
+            parameters.extend(decays);
+            let parameters = self.clamp(parameters);
+
+            let curve = ParametricCurve {
+                model: self.model,
+                parameters,
+            };
+            let error: f64 = x
+                .iter()
+                .zip(y.iter())
+                .zip(weights.iter())
+                .map(|((t, r), w)| w * (r - curve.rate(t)).powi(2))
+                .sum();
+            if best.as_ref().is_none_or(|(e, _)| error < *e) {
+                best = Some((error, curve.parameters));
+            }
+        }
+
+        match best {
+            Some((_, parameters)) => parameters,
+            None => {
+                // Level at the long end, and slope to the short end.
+                let (first, last) = (y[0], y[y.len() - 1]);
+                let mut parameters = vec![0.0; self.model.parameter_count()];
+                parameters[0] = last;
+                parameters[1] = first - last;
+                for tau in parameters[decay_index..].iter_mut() {
+                    *tau = 1.0;
+                }
+                self.clamp(parameters)
+            }
+        }
+    }
+}
+
+//  --- Trait implementations: Concrete
+
+impl Default for CurveFitter {
+    fn default() -> Self {
+        Self::new(ParametricModels::default())
+    }
+}
+
+//  --- Standalone functions
+
+/// Level, L(t, τ), and curvature, C(t, τ), factors. At t = 0, these are 1 and 0.
+fn factors(t: &f64, tau: &f64) -> (f64, f64) {
+    let u = t / tau;
+    if u.abs() < 1e-10 {
+        return (1.0, 0.0);
+    }
+    let e = (-u).exp();
+    let level = (1.0 - e) / u;
+    (level, level - e)
+}
+
+/// Solves min ||W^(1/2).(A.b - y)|| for 'b' by QR decomposition.
+fn weighted_least_squares(a: &[Vec<f64>], y: &[f64], weights: &[f64]) -> Option<Vec<f64>> {
+    let scale: Vec<f64> = weights.iter().map(|w| w.sqrt()).collect();
+    let rows: Vec<Vec<f64>> = a
+        .iter()
+        .zip(scale.iter())
+        .map(|(row, s)| row.iter().map(|a| s * a).collect())
+        .collect();
+    let rhs: Vec<f64> = y.iter().zip(scale.iter()).map(|(y, s)| s * y).collect();
+    Qr::new(&Matrix::from_rows(&rows).ok()?)
+        .ok()?
+        .solve(&rhs)
+        .ok()
+}
+
+//  --- Unit tests
+#[cfg(test)]
+mod test_parametric {
+
+    use super::*;
+    use crate::assert_approx_eq;
+
+    const MATURITIES: [f64; 12] = [
+        0.25, 0.5, 1.0, 2.0, 3.0, 4.0, 5.0, 7.0, 10.0, 15.0, 20.0, 30.0,
+    ];
+
+    #[test]
+    fn test_rate_and_forward() {
+        let curve = ParametricCurve::new(ParametricModels::NelsonSiegel, &[0.05, -0.02, 0.01, 2.0])
+            .unwrap();
+        assert_approx_eq!(curve.rate(&0.0), 0.03);
+        assert_approx_eq!(curve.forward(&0.0), 0.03);
+
+        // t.r(t) integrates the forward.
+        let (t, eps) = (4.0, 1e-5);
+        let numerical =
+            ((t + eps) * curve.rate(&(t + eps)) - (t - eps) * curve.rate(&(t - eps))) / (2.0 * eps);
+        assert_approx_eq!(curve.forward(&t), numerical, 1e-8);
+
+        let numerical = (curve.rate(&(t + eps)) - curve.rate(&(t - eps))) / (2.0 * eps);
+        assert_approx_eq!(curve.rate_derivative(&t), numerical, 1e-8);
+        let numerical = (curve.rate(&eps) - curve.rate(&0.0)) / eps;
+        assert_approx_eq!(curve.rate_derivative(&0.0), numerical, 1e-6);
+    }
+
+    #[test]
+    fn test_recovers_nelson_siegel() {
+        let parameters = [0.065, -0.025, 0.03, 1.8];
+        let curve = ParametricCurve::new(ParametricModels::NelsonSiegel, &parameters).unwrap();
+        let y: Vec<f64> = MATURITIES.iter().map(|t| curve.rate(t)).collect();
+
+        let fit = CurveFitter::new(ParametricModels::NelsonSiegel)
+            .fit(&MATURITIES, &y)
+            .unwrap();
+        assert!(fit.converged());
+        assert!(fit.rmse() < 1e-9);
+        for (a, b) in fit.curve().parameters().iter().zip(parameters.iter()) {
+            assert_approx_eq!(a, b, 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_recovers_svensson() {
+        let parameters = [0.07, -0.03, 0.02, -0.015, 1.2, 6.0];
+        let curve =
+            ParametricCurve::new(ParametricModels::NelsonSiegelSvensson, &parameters).unwrap();
+        let y: Vec<f64> = MATURITIES.iter().map(|t| curve.rate(t)).collect();
+
+        let fit = CurveFitter::new(ParametricModels::NelsonSiegelSvensson)
+            .fit(&MATURITIES, &y)
+            .unwrap();
+        assert!(fit.rmse() < 1e-7);
+        assert_eq!(fit.residuals().len(), MATURITIES.len());
+        assert!(fit.iterations() > 0);
+    }
+
+    #[test]
+    fn test_weights_and_bounds() {
+        // A kinked curve, which the model can not fit exactly.
+        let y: Vec<f64> = MATURITIES
+            .iter()
+            .map(|t| if *t < 5.0 { 0.05 + 0.004 * t } else { 0.07 })
+            .collect();
+
+        let unweighted = CurveFitter::new(ParametricModels::NelsonSiegel)
+            .fit(&MATURITIES, &y)
+            .unwrap();
+        let mut weights = vec![1.0; MATURITIES.len()];
+        weights[0] = 1000.0;
+        let weighted = CurveFitter::new(ParametricModels::NelsonSiegel)
+            .weights(&weights)
+            .fit(&MATURITIES, &y)
+            .unwrap();
+        assert!(weighted.residuals()[0].abs() < unweighted.residuals()[0].abs());
+
+        let bounded = CurveFitter::new(ParametricModels::NelsonSiegel)
+            .bounds(&[0.0, -1.0, -1.0, 3.0], &[0.1, 1.0, 1.0, 5.0])
+            .fit(&MATURITIES, &y)
+            .unwrap();
+        let tau = bounded.curve().parameters()[3];
+        assert!((3.0..=5.0).contains(&tau));
+    }
+
+    #[test]
+    fn test_errors() {
+        let fitter = CurveFitter::new(ParametricModels::NelsonSiegelSvensson);
+        assert!(matches!(
+            fitter.fit(&[1.0, 2.0], &[0.05, 0.06]),
+            Err(Error::InsufficientPoints(6, 2))
+        ));
+        assert!(matches!(
+            fitter.clone().weights(&[1.0]).fit(&MATURITIES, &MATURITIES),
+            Err(Error::MismatchedLengths(12, 1))
+        ));
+        assert!(matches!(
+            ParametricCurve::new(ParametricModels::NelsonSiegel, &[0.05, 0.0, 0.0, -1.0]),
+            Err(Error::InvalidBounds)
+        ));
+    }
+}
@@ -1,73 +1,548 @@
-/*
-let path = "src/resources/curves";
-    let dir = format!("{}/zar_disc_csa.txt", path);
-    let contents = std::fs::read_to_string(dir).unwrap();
-    let curve: BTreeMap<u32, f64> = serde_json::from_str(&contents).unwrap();
-    let curve: CurveParameters<f64> = curve.into();
+//! Polynomial interpolation and regression.
+//!
+//! Through n + 1 points with distinct x, there is exactly one polynomial of degree n.
+//! It can be constructed as:
+//! - Lagrange: p(x) = Σj yj.Lj(x), where Lj(x) = Πk≠j (x - xk) / (xj - xk).
+//! - Newton: p(x) = Σj [y0, .., yj].Πk<j (x - xk), from divided differences, which
+//!   allow points to be added without refitting.
+//! - Barycentric: p(x) = Σj (wj / (x - xj)).yj / Σj wj / (x - xj), with wj = 1 / Πk≠j (xj - xk).
+//!   Evaluation is O(n), and stable even for many points.
+//!
+//! All three yield the same polynomial, but differ in cost and rounding. Monomial
+//! coefficients are ill-conditioned for high degrees, so prefer evaluating the
+//! barycentric form directly.
+//!
+//! Regression fits a polynomial of lower degree to all points by least squares, solving
+//! min ||V.c - y|| for the coefficients 'c', where V is the Vandermonde matrix, by QR.
+//!
+//! ```rust,ignore
+//! let interpolant = Barycentric::new(&x, &y)?;
+//! let (value, slope) = (interpolant.value(&xp), interpolant.derivative(&xp));
+//!
+//! let fit = Regression::fit(&x, &y, 3)?;
+//! let coefficients = fit.polynomial().coefficients();
+//! ```
 
-    let xp: Vec<f64> = (1..100).map(|x| x as f64 / 365.0).collect();
-    let (x, y) = curve.unpack_with_map_x(|a| a / 365.0);
+use super::linalg::{self, Matrix, Qr};
 
-    let coef = solve_for_cubic_coefficients(&x, &y, 10);
+//  --- Errors
+type Result<T> = std::result::Result<T, Error>;
 
-    let interpolated_points: Vec<f64> = xp
-        .into_iter()
-        .map(|xi| polynominal_function(&coef, &xi))
-        .collect();
+#[derive(Debug)]
+pub enum Error {
+    MismatchedLengths(usize, usize),
+    InsufficientPoints(usize, usize),
+    DuplicatePoints(f64),
+    LinalgError(linalg::Error),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MismatchedLengths(x, y) => {
+                write!(f, "x has {} points, while y has {} points.", x, y)
+            }
+            Self::InsufficientPoints(required, n) => {
+                write!(
+                    f,
+                    "At least {} points are required, {} were given.",
+                    required, n
+                )
+            }
+            Self::DuplicatePoints(x) => write!(f, "x should be distinct, {} is repeated.", x),
+            Self::LinalgError(err) => write!(f, "{}", err),
+        }
+    }
+}
 
-    println!("{:?}", interpolated_points);
-*/
+impl std::error::Error for Error {}
 
-pub fn polynominal_function(coefficients: &[f64], x: &f64) -> f64 {
-    coefficients
-        .iter()
-        .enumerate()
-        .map(|(i, c)| c * x.powi(i as i32))
-        .sum::<f64>()
+impl From<linalg::Error> for Error {
+    fn from(value: linalg::Error) -> Self {
+        Self::LinalgError(value)
+    }
 }
 
-pub fn solve_for_cubic_coefficients(x: &[f64], y: &[f64], degree: usize) -> Vec<f64> {
-    assert_eq!(x.len(), y.len());
+//  --- Structs
 
-    let mut coefficients = vec![0.0; degree + 1];
+/// Polynomial in monomial form, with coefficients in increasing order of power.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Polynomial {
+    coefficients: Vec<f64>,
+}
 
-    let learning_rate = 1e-8;
-    let epochs = 10_000;
-    let n = x.len() as f64;
+/// Interpolating polynomial in Newton form.
+#[derive(Clone, Debug)]
+pub struct NewtonPolynomial {
+    x: Vec<f64>,
+    divided_differences: Vec<f64>,
+    // Divided differences ending at the last point, [yn], [yn-1, yn], .., [y0, .., yn].
+    trailing: Vec<f64>,
+}
 
-    for _ in 0..epochs {
-        let mut gradients = vec![0.0; degree + 1];
+/// Interpolating polynomial in barycentric form.
+#[derive(Clone, Debug)]
+pub struct Barycentric {
+    x: Vec<f64>,
+    y: Vec<f64>,
+    weights: Vec<f64>,
+}
 
-        let mut total_error = 0.0;
+/// Least squares polynomial, with diagnostics.
+#[derive(Clone, Debug)]
+pub struct Regression {
+    polynomial: Polynomial,
+    residuals: Vec<f64>,
+    rmse: f64,
+    r_squared: f64,
+}
 
-        for (xi, yi) in x.iter().zip(y.iter()) {
-            let predict = polynominal_function(&coefficients, xi);
-            let error = yi - predict;
-            let error_derivative = -2.0 * error;
-            total_error += (yi - predict).powi(2);
+//  --- Implementations
 
-            let weight = 1.0;
+impl Polynomial {
+    pub fn new(coefficients: &[f64]) -> Self {
+        Self {
+            coefficients: coefficients.to_vec(),
+        }
+    }
 
-            for (i, c) in gradients.iter_mut().enumerate() {
-                *c += weight * error_derivative * xi.powi(i as i32);
+    /// Interpolating polynomial through ('x', 'y'), summing the Lagrange basis polynomials.
+    pub fn lagrange(x: &[f64], y: &[f64]) -> Result<Self> {
+        validate_points(x, y, 1)?;
+        let mut coefficients = vec![0.0; x.len()];
+        for (j, (xj, yj)) in x.iter().zip(y.iter()).enumerate() {
+            // Lj(x), expanded one factor at a time.
+            let mut basis = vec![1.0];
+            let mut denominator = 1.0;
+            for (_, xk) in x.iter().enumerate().filter(|(k, _)| *k != j) {
+                basis = multiply_linear(&basis, *xk);
+                denominator *= xj - xk;
+            }
+            for (c, b) in coefficients.iter_mut().zip(basis.iter()) {
+                *c += yj * b / denominator;
             }
         }
+        Ok(Self { coefficients })
+    }
+
+    pub fn coefficients(&self) -> &[f64] {
+        &self.coefficients
+    }
+
+    pub fn degree(&self) -> usize {
+        self.coefficients.len().saturating_sub(1)
+    }
+
+    /// Value at 'xp', by Horner's method.
+    pub fn value(&self, xp: &f64) -> f64 {
+        self.coefficients
+            .iter()
+            .rev()
+            .fold(0.0, |acc, c| acc * xp + c)
+    }
+
+    pub fn derivative(&self, xp: &f64) -> f64 {
+        self.differentiate().value(xp)
+    }
+
+    /// Derivative, as a polynomial of one degree lower.
+    pub fn differentiate(&self) -> Self {
+        Self {
+            coefficients: self
+                .coefficients
+                .iter()
+                .enumerate()
+                .skip(1)
+                .map(|(i, c)| i as f64 * c)
+                .collect(),
+        }
+    }
+}
+
+impl NewtonPolynomial {
+    pub fn new(x: &[f64], y: &[f64]) -> Result<Self> {
+        validate_points(x, y, 1)?;
+        let mut polynomial = Self {
+            x: Vec::with_capacity(x.len()),
+            divided_differences: Vec::with_capacity(x.len()),
+            trailing: Vec::with_capacity(x.len()),
+        };
+        for (xi, yi) in x.iter().zip(y.iter()) {
+            polynomial.add_point(*xi, *yi)?;
+        }
+        Ok(polynomial)
+    }
 
-        let grad_clip_threshold = 1.0;
-        for c in gradients.iter_mut() {
-            *c = c.min(grad_clip_threshold).max(-grad_clip_threshold) * learning_rate / n;
+    /// Raises the degree by one, such that the polynomial also passes through ('x', 'y').
+    pub fn add_point(&mut self, x: f64, y: f64) -> Result<()> {
+        if self.x.contains(&x) {
+            return Err(Error::DuplicatePoints(x));
+        }
+        // [yn-k+1, .., y] = ([yn-k+2, .., y] - [yn-k+1, .., yn]) / (x - xn-k+1).
+        let mut trailing = Vec::with_capacity(self.trailing.len() + 1);
+        trailing.push(y);
+        for (k, previous) in self.trailing.iter().enumerate() {
+            let xk = self.x[self.x.len() - 1 - k];
+            trailing.push((trailing[k] - previous) / (x - xk));
         }
+        self.x.push(x);
+        self.divided_differences.push(trailing[trailing.len() - 1]);
+        self.trailing = trailing;
+        Ok(())
+    }
 
-        for i in 0..coefficients.len() {
-            coefficients[i] -= gradients[i];
+    /// Divided differences [y0], [y0, y1], .., [y0, .., yn], the Newton coefficients.
+    pub fn divided_differences(&self) -> &[f64] {
+        &self.divided_differences
+    }
+
+    pub fn value(&self, xp: &f64) -> f64 {
+        self.x
+            .iter()
+            .zip(self.divided_differences.iter())
+            .rev()
+            .fold(0.0, |acc, (xi, d)| acc * (xp - xi) + d)
+    }
+
+    pub fn derivative(&self, xp: &f64) -> f64 {
+        // Horner's method on p and p' together.
+        let (_, slope) = self
+            .x
+            .iter()
+            .zip(self.divided_differences.iter())
+            .rev()
+            .fold((0.0, 0.0), |(value, slope), (xi, d)| {
+                (value * (xp - xi) + d, slope * (xp - xi) + value)
+            });
+        slope
+    }
+
+    /// The same polynomial, in monomial form.
+    pub fn to_polynomial(&self) -> Polynomial {
+        let coefficients = self
+            .x
+            .iter()
+            .zip(self.divided_differences.iter())
+            .rev()
+            .fold(vec![0.0], |acc, (xi, d)| {
+                let mut next = multiply_linear(&acc, *xi);
+                next[0] += d;
+                next
+            });
+        Polynomial {
+            coefficients: coefficients[..self.x.len()].to_vec(),
         }
+    }
+}
 
-        println!("{:.6}", total_error);
+impl Barycentric {
+    pub fn new(x: &[f64], y: &[f64]) -> Result<Self> {
+        validate_points(x, y, 1)?;
+        let weights = x
+            .iter()
+            .enumerate()
+            .map(|(j, xj)| {
+                1.0 / x
+                    .iter()
+                    .enumerate()
+                    .filter(|(k, _)| *k != j)
+                    .map(|(_, xk)| xj - xk)
+                    .product::<f64>()
+            })
+            .collect();
+        Ok(Self {
+            x: x.to_vec(),
+            y: y.to_vec(),
+            weights,
+        })
+    }
+
+    pub fn weights(&self) -> &[f64] {
+        &self.weights
+    }
 
-        if total_error.abs() < 1e-8 {
-            return coefficients.to_vec();
+    pub fn value(&self, xp: &f64) -> f64 {
+        if let Some(i) = self.node(xp) {
+            return self.y[i];
         }
+        let (numerator, denominator) = self
+            .terms(xp)
+            .fold((0.0, 0.0), |(n, d), (t, y)| (n + t * y, d + t));
+        numerator / denominator
+    }
+
+    /// p'(x) = Σj (wj / (x - xj)).(p(x) - yj) / (x - xj) / Σj wj / (x - xj), and at a node
+    /// p'(xi) = Σj≠i (wj / wi).(yj - yi) / (xi - xj).
+    pub fn derivative(&self, xp: &f64) -> f64 {
+        if let Some(i) = self.node(xp) {
+            return self
+                .x
+                .iter()
+                .zip(self.y.iter())
+                .zip(self.weights.iter())
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(_, ((xj, yj), wj))| wj / self.weights[i] * (yj - self.y[i]) / (xp - xj))
+                .sum();
+        }
+        let value = self.value(xp);
+        let (numerator, denominator) = self
+            .terms(xp)
+            .zip(self.x.iter())
+            .fold((0.0, 0.0), |(n, d), ((t, y), xj)| {
+                (n + t * (value - y) / (xp - xj), d + t)
+            });
+        numerator / denominator
+    }
+
+    fn node(&self, xp: &f64) -> Option<usize> {
+        self.x.iter().position(|xi| xi == xp)
+    }
+
+    /// wj / (x - xj) and yj, for each point.
+    fn terms<'a>(&'a self, xp: &'a f64) -> impl Iterator<Item = (f64, f64)> + 'a {
+        self.x
+            .iter()
+            .zip(self.weights.iter())
+            .zip(self.y.iter())
+            .map(move |((xj, wj), yj)| (wj / (xp - xj), *yj))
     }
+}
 
-    coefficients.to_vec()
+impl Regression {
+    /// Fits a polynomial of 'degree' to ('x', 'y') by least squares. 'x' may repeat,
+    /// as with several observations at the same point.
+    pub fn fit(x: &[f64], y: &[f64], degree: usize) -> Result<Self> {
+        validate_lengths(x, y, degree + 1)?;
+        let vandermonde: Vec<Vec<f64>> = x
+            .iter()
+            .map(|xi| (0..=degree).map(|i| xi.powi(i as i32)).collect())
+            .collect();
+        let coefficients = Qr::new(&Matrix::from_rows(&vandermonde)?)?.solve(y)?;
+        let polynomial = Polynomial { coefficients };
+
+        let residuals: Vec<f64> = x
+            .iter()
+            .zip(y.iter())
+            .map(|(xi, yi)| yi - polynomial.value(xi))
+            .collect();
+        let sse: f64 = residuals.iter().map(|r| r.powi(2)).sum();
+        let mean = y.iter().sum::<f64>() / y.len() as f64;
+        let sst: f64 = y.iter().map(|yi| (yi - mean).powi(2)).sum();
+
+        Ok(Self {
+            polynomial,
+            rmse: (sse / x.len() as f64).sqrt(),
+            r_squared: if sst > 0.0 { 1.0 - sse / sst } else { 1.0 },
+            residuals,
+        })
+    }
+
+    pub fn polynomial(&self) -> &Polynomial {
+        &self.polynomial
+    }
+
+    /// Observed less fitted values.
+    pub fn residuals(&self) -> &[f64] {
+        &self.residuals
+    }
+
+    pub fn rmse(&self) -> f64 {
+        self.rmse
+    }
+
+    pub fn r_squared(&self) -> f64 {
+        self.r_squared
+    }
+
+    pub fn value(&self, xp: &f64) -> f64 {
+        self.polynomial.value(xp)
+    }
+
+    pub fn derivative(&self, xp: &f64) -> f64 {
+        self.polynomial.derivative(xp)
+    }
+}
+
+//  --- Standalone functions
+
+pub fn polynominal_function(coefficients: &[f64], x: &f64) -> f64 {
+    Polynomial::new(coefficients).value(x)
+}
+
+/// Fits a polynomial of 'degree' to the points ('x', 'y') by least squares, returning
+/// the coefficients in increasing order of power.
+#[deprecated(note = "use 'Regression::fit', which also reports the quality of the fit")]
+pub fn solve_for_cubic_coefficients(x: &[f64], y: &[f64], degree: usize) -> Result<Vec<f64>> {
+    Ok(Regression::fit(x, y, degree)?
+        .polynomial()
+        .coefficients()
+        .to_vec())
+}
+
+fn validate_lengths(x: &[f64], y: &[f64], required: usize) -> Result<()> {
+    if x.len() != y.len() {
+        return Err(Error::MismatchedLengths(x.len(), y.len()));
+    }
+    if x.len() < required.max(1) {
+        return Err(Error::InsufficientPoints(required.max(1), x.len()));
+    }
+    Ok(())
+}
+
+/// Interpolating forms further require distinct 'x'.
+fn validate_points(x: &[f64], y: &[f64], required: usize) -> Result<()> {
+    validate_lengths(x, y, required)?;
+    if let Some((_, xi)) = x.iter().enumerate().find(|(i, xi)| x[..*i].contains(xi)) {
+        return Err(Error::DuplicatePoints(*xi));
+    }
+    Ok(())
+}
+
+/// Coefficients of p(x).(x - root).
+fn multiply_linear(p: &[f64], root: f64) -> Vec<f64> {
+    let mut product = vec![0.0; p.len() + 1];
+    for (i, c) in p.iter().enumerate() {
+        product[i + 1] += c;
+        product[i] -= root * c;
+    }
+    product
+}
+
+//  --- Unit tests
+#[cfg(test)]
+mod test_poly {
+
+    use super::*;
+    use crate::assert_approx_eq;
+
+    const COEFFICIENTS: [f64; 4] = [1.0, -2.0, 0.5, 0.1];
+
+    fn cubic() -> (Vec<f64>, Vec<f64>) {
+        let x = vec![-1.0, 0.5, 2.0, 4.0];
+        let y = x
+            .iter()
+            .map(|a| polynominal_function(&COEFFICIENTS, a))
+            .collect();
+        (x, y)
+    }
+
+    #[test]
+    fn test_polynomial() {
+        let p = Polynomial::new(&COEFFICIENTS);
+        assert_eq!(p.degree(), 3);
+        assert_approx_eq!(p.value(&2.0), 1.0 - 4.0 + 2.0 + 0.8, 1e-15);
+        let derivative = p.differentiate();
+        for (a, b) in derivative.coefficients().iter().zip([-2.0, 1.0, 0.3]) {
+            assert_approx_eq!(a, b, 1e-15);
+        }
+        assert_approx_eq!(p.derivative(&2.0), -2.0 + 2.0 + 1.2, 1e-15);
+    }
+
+    #[test]
+    fn test_interpolation_forms_agree() {
+        let (x, y) = cubic();
+        let lagrange = Polynomial::lagrange(&x, &y).unwrap();
+        let newton = NewtonPolynomial::new(&x, &y).unwrap();
+        let barycentric = Barycentric::new(&x, &y).unwrap();
+
+        for (a, b) in lagrange.coefficients().iter().zip(COEFFICIENTS.iter()) {
+            assert_approx_eq!(a, b, 1e-12);
+        }
+        for (a, b) in newton
+            .to_polynomial()
+            .coefficients()
+            .iter()
+            .zip(COEFFICIENTS.iter())
+        {
+            assert_approx_eq!(a, b, 1e-12);
+        }
+
+        let exact = Polynomial::new(&COEFFICIENTS);
+        for xp in [-1.0, -0.3, 0.5, 1.7, 3.2, 5.0] {
+            assert_approx_eq!(newton.value(&xp), exact.value(&xp), 1e-12);
+            assert_approx_eq!(barycentric.value(&xp), exact.value(&xp), 1e-12);
+            assert_approx_eq!(newton.derivative(&xp), exact.derivative(&xp), 1e-12);
+            assert_approx_eq!(barycentric.derivative(&xp), exact.derivative(&xp), 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_newton_add_point() {
+        let (x, y) = cubic();
+        let mut newton = NewtonPolynomial::new(&x[..3], &y[..3]).unwrap();
+        newton.add_point(x[3], y[3]).unwrap();
+        let divided_differences = NewtonPolynomial::new(&x, &y).unwrap();
+        assert_eq!(
+            newton.divided_differences(),
+            divided_differences.divided_differences()
+        );
+        // Degree 3 data, so the divided difference of order 3 is the leading coefficient.
+        assert_approx_eq!(newton.divided_differences()[3], 0.1, 1e-12);
+        assert!(matches!(
+            newton.add_point(x[0], 0.0),
+            Err(Error::DuplicatePoints(_))
+        ));
+    }
+
+    #[test]
+    fn test_barycentric_runge() {
+        // Chebyshev points avoid the oscillation of equally spaced points.
+        let n = 40;
+        let x: Vec<f64> = (0..=n)
+            .map(|j| (std::f64::consts::PI * j as f64 / n as f64).cos())
+            .collect();
+        let runge = |a: &f64| 1.0 / (1.0 + 25.0 * a.powi(2));
+        let y: Vec<f64> = x.iter().map(runge).collect();
+        let barycentric = Barycentric::new(&x, &y).unwrap();
+        for xp in [-0.95, -0.5, 0.0, 0.33, 0.9] {
+            assert_approx_eq!(barycentric.value(&xp), runge(&xp), 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_regression() {
+        let x: Vec<f64> = (0..20).map(|i| i as f64 / 4.0).collect();
+        let y: Vec<f64> = x
+            .iter()
+            .map(|a| polynominal_function(&COEFFICIENTS, a))
+            .collect();
+
+        let fit = Regression::fit(&x, &y, 3).unwrap();
+        for (c, expected) in fit.polynomial().coefficients().iter().zip(COEFFICIENTS) {
+            assert_approx_eq!(c, expected, 1e-10);
+        }
+        assert_approx_eq!(fit.r_squared(), 1.0, 1e-12);
+        #[allow(deprecated)]
+        let coefficients = solve_for_cubic_coefficients(&x, &y, 3).unwrap();
+        assert_eq!(coefficients, fit.polynomial().coefficients());
+
+        // A line through noisy points.
+        let fit = Regression::fit(&[0.0, 1.0, 2.0, 3.0], &[1.1, 2.9, 5.1, 6.9], 1).unwrap();
+        assert_approx_eq!(fit.polynomial().coefficients()[1], 1.96, 1e-12);
+        assert_approx_eq!(fit.derivative(&10.0), 1.96, 1e-12);
+        assert_approx_eq!(fit.residuals().iter().sum::<f64>(), 0.0, 1e-12);
+        assert!(fit.rmse() > 0.0 && fit.r_squared() < 1.0);
+
+        // Repeated observations at the same points.
+        let fit = Regression::fit(&[0.0, 0.0, 1.0, 1.0], &[0.9, 1.1, 2.9, 3.1], 1).unwrap();
+        assert_approx_eq!(fit.polynomial().coefficients()[0], 1.0, 1e-12);
+        assert_approx_eq!(fit.polynomial().coefficients()[1], 2.0, 1e-12);
+    }
+
+    #[test]
+    fn test_invalid_points() {
+        assert!(matches!(
+            Barycentric::new(&[0.0, 1.0], &[1.0]),
+            Err(Error::MismatchedLengths(2, 1))
+        ));
+        assert!(matches!(
+            Polynomial::lagrange(&[0.0, 1.0, 0.0], &[1.0, 2.0, 3.0]),
+            Err(Error::DuplicatePoints(_))
+        ));
+        assert!(matches!(
+            Regression::fit(&[0.0, 1.0], &[1.0, 2.0], 2),
+            Err(Error::InsufficientPoints(3, 2))
+        ));
+    }
 }
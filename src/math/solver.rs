@@ -1,121 +1,576 @@
-// To be used for:
-//  1. Type conversion
-//  2. Determining polynomial coefficients.
-//  3. Hazard rate model.
-//  4. Solving for the hype derivative fixed rate.
+//! Root finding.
+//!
+//! Solves f(x) = target for x. Open methods (Newton, Halley and secant) start from
+//! initial guesses, and converge quickly near the root, but may diverge. Bracketing
+//! methods (bisection and Brent) require f(x) - target to change sign between two
+//! bounds, and always converge.
+//!
+//! Iteration stops once the residual, |f(x) - target|, is within the tolerance, or
+//! once steps, or the bracket, shrink below the step tolerance relative to x. Open
+//! methods whose steps stall with the residual above the tolerance return an error.
+//!
+//! ```rust,ignore
+//! let solution = RootFinder::new()
+//!     .target(1.0)
+//!     .tolerance(1e-14)
+//!     .newton(|df| repricing(df), initial)?;
+//!
+//! println!("{} after {} iterations", solution.root(), solution.iterations());
+//! ```
+//!
+//! To be used for:
+//!  1. Type conversion
+//!  2. Bootstrapping.
+//!  3. Hazard rate model.
+//!  4. Solving for the hype derivative fixed rate.
 
-pub trait NewtonRaphson<A>
-where
-    A: Fn(f64) -> f64,
-{
-    const MAX_ITER: usize = 1_000_000;
-    const TOLERANCE: f64 = 1e-16;
-    const STEP: f64 = 1e-8;
+//  --- Constants
 
-    fn solve(init: &f64, closure: A) -> f64 {
-        let mut start = *init;
-        let mut result_check = closure(start);
-        let mut iterations = 0;
+/// Step, relative to x, used to differentiate numerically.
+const DERIVATIVE_STEP: f64 = 1e-6;
 
-        while (1.0 - result_check).abs() > Self::TOLERANCE && iterations < Self::MAX_ITER {
-            let derivative = ((closure(start + Self::STEP)) - result_check) / Self::STEP;
+//  --- Errors
+type Result<T> = std::result::Result<T, Error>;
 
-            start += (1.0 - result_check) / derivative;
+#[derive(Debug)]
+pub enum Error {
+    InvalidBracket(f64, f64),
+    ZeroDerivative(f64),
+    NonFiniteValue(f64),
+    MaxIterations(Solution),
+    Stalled(Solution),
+}
 
-            result_check = closure(start);
-            iterations += 1;
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidBracket(lower, upper) => write!(
+                f,
+                "The function does not change sign between {} and {}.",
+                lower, upper
+            ),
+            Self::ZeroDerivative(x) => write!(f, "The derivative is zero at {}.", x),
+            Self::NonFiniteValue(x) => write!(f, "The function is not finite at {}.", x),
+            Self::MaxIterations(solution) => write!(
+                f,
+                "No convergence after {} iterations, the residual at {} is {}.",
+                solution.iterations, solution.root, solution.residual
+            ),
+            Self::Stalled(solution) => write!(
+                f,
+                "Steps stalled after {} iterations, the residual at {} is {}.",
+                solution.iterations, solution.root, solution.residual
+            ),
         }
-        println!("{:?}", iterations);
-        start
     }
 }
 
-impl<A> NewtonRaphson<A> for f64 where A: Fn(f64) -> f64 {}
+impl std::error::Error for Error {}
 
-//  --- Old code.
+//  --- Enums
 
-#[derive(Default)]
-pub enum SolvingMethod {
-    #[default]
-    GRGNonLinear,
-    SimplexLP,
-    Evolutionary,
+/// Reason iteration stopped.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ConvergenceReasons {
+    /// The residual is within the tolerance.
+    Residual,
+    /// The last step is within the step tolerance, while the residual is not. Open
+    /// methods report this through `Error::Stalled`.
+    Step,
+    /// The bracket is within the step tolerance.
+    Bracket,
 }
 
-pub trait SolveEquation<A, B, C, D>
-where
-    A: Fn(&B) -> C,
-{
-    /// Fn whose argument will be solved for.
-    fn forward(var: &B, closure: &A) -> C {
-        closure(var)
+//  --- Structs
+
+#[derive(Clone, Debug)]
+pub struct RootFinder {
+    target: f64,
+    tolerance: f64,
+    step_tolerance: f64,
+    max_iter: usize,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Solution {
+    root: f64,
+    iterations: usize,
+    residual: f64,
+    reason: Option<ConvergenceReasons>,
+}
+
+//  --- Implementations
+
+impl RootFinder {
+    pub fn new() -> Self {
+        Self {
+            target: 0.0,
+            tolerance: 1e-12,
+            step_tolerance: 1e-15,
+            max_iter: 100,
+        }
     }
 
-    //fn backward(err: C, closure: D) -> C;
-    fn backward(var: &B, closure: &D) -> C;
+    /// Value of f(x) solved for.
+    pub fn target(self, target: f64) -> Self {
+        Self { target, ..self }
+    }
 
-    //fn error
+    /// Largest acceptable residual, |f(x) - target|.
+    pub fn tolerance(self, tolerance: f64) -> Self {
+        Self { tolerance, ..self }
+    }
 
-    /// Solves for the 'var' that would result in the 'forward' fn to produce a desired result.
-    fn solve(f_closure: &A, b_closure: &D, target: &C) -> C;
-}
+    /// Smallest step, or bracket, relative to x, before iteration stops.
+    pub fn step_tolerance(self, step_tolerance: f64) -> Self {
+        Self {
+            step_tolerance,
+            ..self
+        }
+    }
 
-pub struct Simple;
+    pub fn max_iter(self, max_iter: usize) -> Self {
+        Self { max_iter, ..self }
+    }
 
-pub struct Discrete;
+    /// Newton's method, with a central difference derivative.
+    pub fn newton<F>(&self, f: F, initial: f64) -> Result<Solution>
+    where
+        F: Fn(f64) -> f64,
+    {
+        self.newton_with_derivative(&f, |x| numerical_derivative(&f, x), initial)
+    }
 
-// TODO how to refactor the below?
+    /// Newton's method, with an analytic derivative 'df'.
+    pub fn newton_with_derivative<F, D>(&self, f: F, df: D, initial: f64) -> Result<Solution>
+    where
+        F: Fn(f64) -> f64,
+        D: Fn(f64) -> f64,
+    {
+        self.iterate(&f, initial, |x, residual| {
+            let derivative = df(x);
+            if derivative == 0.0 || !derivative.is_finite() {
+                return Err(Error::ZeroDerivative(x));
+            }
+            Ok(residual / derivative)
+        })
+    }
 
-impl SolveEquation<Box<dyn Fn(&f64) -> f64>, f64, f64, Box<dyn Fn() -> f64>> for Simple {
-    fn forward(var: &f64, closure: &Box<dyn Fn(&f64) -> f64>) -> f64 {
-        closure(var)
+    /// Halley's method, with analytic first and second derivatives.
+    pub fn halley<F, D, D2>(&self, f: F, df: D, d2f: D2, initial: f64) -> Result<Solution>
+    where
+        F: Fn(f64) -> f64,
+        D: Fn(f64) -> f64,
+        D2: Fn(f64) -> f64,
+    {
+        self.iterate(&f, initial, |x, residual| {
+            let (first, second) = (df(x), d2f(x));
+            let denominator = 2.0 * first.powi(2) - residual * second;
+            if denominator == 0.0 || !denominator.is_finite() {
+                return Err(Error::ZeroDerivative(x));
+            }
+            Ok(2.0 * residual * first / denominator)
+        })
     }
 
-    fn backward(var: &f64, closure: &Box<dyn Fn() -> f64>) -> f64 {
-        closure()
+    /// Secant method, starting from two initial guesses.
+    pub fn secant<F>(&self, f: F, x0: f64, x1: f64) -> Result<Solution>
+    where
+        F: Fn(f64) -> f64,
+    {
+        let (mut previous, mut current) = (x0, x1);
+        let mut previous_residual = self.residual(&f, previous)?;
+        let mut residual = self.residual(&f, current)?;
+
+        for iteration in 0..self.max_iter {
+            if residual.abs() <= self.tolerance {
+                return Ok(self.solution(
+                    current,
+                    iteration,
+                    residual,
+                    ConvergenceReasons::Residual,
+                ));
+            }
+            let slope = (residual - previous_residual) / (current - previous);
+            if slope == 0.0 || !slope.is_finite() {
+                return Err(Error::ZeroDerivative(current));
+            }
+
+            let step = residual / slope;
+            (previous, previous_residual) = (current, residual);
+            current -= step;
+            residual = self.residual(&f, current)?;
+
+            if self.is_small(step, current) {
+                return self.stalled(current, iteration + 1, residual);
+            }
+        }
+        Err(self.max_iterations(current, residual))
     }
 
-    fn solve(
-        f_closure: &Box<dyn Fn(&f64) -> f64>,
-        b_closure: &Box<dyn Fn() -> f64>,
-        target: &f64,
-    ) -> f64 {
-        let mut init = 0.0;
-        loop {
-            let forward_value: f64 = Self::forward(&init, f_closure);
-            let err = target - forward_value;
-            if err.abs() < 1e-14 {
-                return init;
+    /// Bisection between 'lower' and 'upper'.
+    pub fn bisection<F>(&self, f: F, lower: f64, upper: f64) -> Result<Solution>
+    where
+        F: Fn(f64) -> f64,
+    {
+        let (mut a, mut b) = (lower, upper);
+        let (mut fa, fb) = (self.residual(&f, a)?, self.residual(&f, b)?);
+        if let Some(solution) = self.bracket_end(a, fa, b, fb)? {
+            return Ok(solution);
+        }
+
+        for iteration in 1..=self.max_iter {
+            let mid = 0.5 * (a + b);
+            let fm = self.residual(&f, mid)?;
+            if fm.abs() <= self.tolerance {
+                return Ok(self.solution(mid, iteration, fm, ConvergenceReasons::Residual));
+            }
+            if self.is_small(0.5 * (b - a), mid) {
+                return Ok(self.solution(mid, iteration, fm, ConvergenceReasons::Bracket));
+            }
+            if fa.signum() == fm.signum() {
+                (a, fa) = (mid, fm);
+            } else {
+                b = mid;
+            }
+        }
+        let mid = 0.5 * (a + b);
+        Err(self.max_iterations(mid, self.residual(&f, mid)?))
+    }
+
+    /// Brent's method between 'lower' and 'upper', combining bisection, secant and
+    /// inverse quadratic interpolation steps.
+    pub fn brent<F>(&self, f: F, lower: f64, upper: f64) -> Result<Solution>
+    where
+        F: Fn(f64) -> f64,
+    {
+        let (mut a, mut b) = (lower, upper);
+        let (mut fa, mut fb) = (self.residual(&f, a)?, self.residual(&f, b)?);
+        if let Some(solution) = self.bracket_end(a, fa, b, fb)? {
+            return Ok(solution);
+        }
+
+        // 'b' is the best estimate, and the root lies between 'b' and 'c'.
+        let (mut c, mut fc) = (b, fb);
+        let (mut d, mut e) = (b - a, b - a);
+
+        for iteration in 1..=self.max_iter {
+            if fb.signum() == fc.signum() {
+                (c, fc) = (a, fa);
+                d = b - a;
+                e = d;
+            }
+            if fc.abs() < fb.abs() {
+                (a, fa) = (b, fb);
+                (b, fb) = (c, fc);
+                (c, fc) = (a, fa);
+            }
+
+            let bound = self.step_tolerance * b.abs().max(1.0);
+            let half = 0.5 * (c - b);
+            if fb.abs() <= self.tolerance {
+                return Ok(self.solution(b, iteration - 1, fb, ConvergenceReasons::Residual));
+            }
+            if half.abs() <= bound {
+                return Ok(self.solution(b, iteration - 1, fb, ConvergenceReasons::Bracket));
+            }
+
+            if e.abs() >= bound && fa.abs() > fb.abs() {
+                // Secant, or inverse quadratic interpolation.
+                let s = fb / fa;
+                let (mut p, mut q) = if a == c {
+                    (2.0 * half * s, 1.0 - s)
+                } else {
+                    let (q, r) = (fa / fc, fb / fc);
+                    (
+                        s * (2.0 * half * q * (q - r) - (b - a) * (r - 1.0)),
+                        (q - 1.0) * (r - 1.0) * (s - 1.0),
+                    )
+                };
+                if p > 0.0 {
+                    q = -q;
+                }
+                p = p.abs();
+
+                if 2.0 * p < (3.0 * half * q - (bound * q).abs()).min((e * q).abs()) {
+                    e = d;
+                    d = p / q;
+                } else {
+                    d = half;
+                    e = d;
+                }
+            } else {
+                d = half;
+                e = d;
+            }
+
+            (a, fa) = (b, fb);
+            b += if d.abs() > bound {
+                d
             } else {
-                init += err * Self::backward(&init, b_closure) * 0.01;
+                bound.copysign(half)
+            };
+            fb = self.residual(&f, b)?;
+        }
+        Err(self.max_iterations(b, fb))
+    }
+
+    /// Iterates x -= step(x, f(x) - target), from 'initial'.
+    fn iterate<F, S>(&self, f: &F, initial: f64, step: S) -> Result<Solution>
+    where
+        F: Fn(f64) -> f64,
+        S: Fn(f64, f64) -> Result<f64>,
+    {
+        let mut x = initial;
+        let mut residual = self.residual(f, x)?;
+
+        for iteration in 0..self.max_iter {
+            if residual.abs() <= self.tolerance {
+                return Ok(self.solution(x, iteration, residual, ConvergenceReasons::Residual));
+            }
+            let delta = step(x, residual)?;
+            x -= delta;
+            residual = self.residual(f, x)?;
+
+            if self.is_small(delta, x) {
+                return self.stalled(x, iteration + 1, residual);
             }
         }
+        Err(self.max_iterations(x, residual))
+    }
+
+    fn residual<F>(&self, f: &F, x: f64) -> Result<f64>
+    where
+        F: Fn(f64) -> f64,
+    {
+        let residual = f(x) - self.target;
+        if !residual.is_finite() {
+            return Err(Error::NonFiniteValue(x));
+        }
+        Ok(residual)
+    }
+
+    /// Solution at either end of a bracket, if any, after checking that the bracket is valid.
+    fn bracket_end(&self, a: f64, fa: f64, b: f64, fb: f64) -> Result<Option<Solution>> {
+        if fa.abs() <= self.tolerance {
+            return Ok(Some(self.solution(a, 0, fa, ConvergenceReasons::Residual)));
+        }
+        if fb.abs() <= self.tolerance {
+            return Ok(Some(self.solution(b, 0, fb, ConvergenceReasons::Residual)));
+        }
+        if fa.signum() == fb.signum() {
+            return Err(Error::InvalidBracket(a, b));
+        }
+        Ok(None)
+    }
+
+    fn is_small(&self, step: f64, x: f64) -> bool {
+        step.abs() <= self.step_tolerance * x.abs().max(1.0)
+    }
+
+    fn solution(
+        &self,
+        root: f64,
+        iterations: usize,
+        residual: f64,
+        reason: ConvergenceReasons,
+    ) -> Solution {
+        Solution {
+            root,
+            iterations,
+            residual,
+            reason: Some(reason),
+        }
+    }
+
+    /// Solution once steps stall, which is an error unless the residual is within the tolerance.
+    fn stalled(&self, root: f64, iterations: usize, residual: f64) -> Result<Solution> {
+        if residual.abs() <= self.tolerance {
+            return Ok(self.solution(root, iterations, residual, ConvergenceReasons::Residual));
+        }
+        Err(Error::Stalled(self.solution(
+            root,
+            iterations,
+            residual,
+            ConvergenceReasons::Step,
+        )))
+    }
+
+    fn max_iterations(&self, root: f64, residual: f64) -> Error {
+        Error::MaxIterations(Solution {
+            root,
+            iterations: self.max_iter,
+            residual,
+            reason: None,
+        })
     }
 }
 
-impl SolveEquation<Box<dyn Fn(&f64) -> f64>, f64, f64, Box<dyn Fn(&f64) -> f64>> for Discrete {
-    fn forward(var: &f64, closure: &Box<dyn Fn(&f64) -> f64>) -> f64 {
-        closure(var)
+impl Solution {
+    pub fn root(&self) -> f64 {
+        self.root
     }
 
-    fn backward(var: &f64, closure: &Box<dyn Fn(&f64) -> f64>) -> f64 {
-        closure(var)
+    pub fn iterations(&self) -> usize {
+        self.iterations
     }
 
-    fn solve(
-        f_closure: &Box<dyn Fn(&f64) -> f64>,
-        b_closure: &Box<dyn Fn(&f64) -> f64>,
-        target: &f64,
-    ) -> f64 {
-        let mut init = 0.0;
-        loop {
-            let forward_value: f64 = Self::forward(&init, f_closure);
-            let err = target - forward_value;
-            if err.abs() < 1e-10 {
-                return init;
-            } else {
-                init += err * Self::backward(&init, b_closure) * 0.01;
+    /// f(root) - target.
+    pub fn residual(&self) -> f64 {
+        self.residual
+    }
+
+    /// Reason iteration stopped, or None if the iteration cap was reached.
+    pub fn reason(&self) -> Option<ConvergenceReasons> {
+        self.reason
+    }
+}
+
+//  --- Trait implementations: Concrete
+
+impl Default for RootFinder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+//  --- Standalone functions
+
+fn numerical_derivative<F>(f: &F, x: f64) -> f64
+where
+    F: Fn(f64) -> f64,
+{
+    let h = DERIVATIVE_STEP * x.abs().max(1.0);
+    (f(x + h) - f(x - h)) / (2.0 * h)
+}
+
+//  --- Unit tests
+#[cfg(test)]
+mod test_solver {
+
+    use super::*;
+    use crate::assert_approx_eq;
+
+    // Wallis' cubic, x^3 - 2x - 5 = 0.
+    const ROOT: f64 = 2.0945514815423265;
+
+    fn cubic(x: f64) -> f64 {
+        x.powi(3) - 2.0 * x - 5.0
+    }
+
+    fn cubic_derivative(x: f64) -> f64 {
+        3.0 * x.powi(2) - 2.0
+    }
+
+    #[test]
+    fn test_open_methods() {
+        let finder = RootFinder::new();
+        let solutions = [
+            finder.newton(cubic, 2.0).unwrap(),
+            finder
+                .newton_with_derivative(cubic, cubic_derivative, 2.0)
+                .unwrap(),
+            finder
+                .halley(cubic, cubic_derivative, |x| 6.0 * x, 2.0)
+                .unwrap(),
+            finder.secant(cubic, 2.0, 3.0).unwrap(),
+        ];
+        for solution in solutions {
+            assert_approx_eq!(solution.root(), ROOT, 1e-12);
+            assert!(solution.residual().abs() <= 1e-12);
+            assert_eq!(solution.reason(), Some(ConvergenceReasons::Residual));
+            assert!(solution.iterations() > 0);
+        }
+
+        let newton = finder
+            .newton_with_derivative(cubic, cubic_derivative, 2.0)
+            .unwrap();
+        let halley = finder
+            .halley(cubic, cubic_derivative, |x| 6.0 * x, 2.0)
+            .unwrap();
+        assert!(halley.iterations() <= newton.iterations());
+    }
+
+    #[test]
+    fn test_bracketing_methods() {
+        let finder = RootFinder::new();
+        let bisection = finder.bisection(cubic, 2.0, 3.0).unwrap();
+        let brent = finder.brent(cubic, 2.0, 3.0).unwrap();
+
+        assert_approx_eq!(bisection.root(), ROOT, 1e-12);
+        assert_approx_eq!(brent.root(), ROOT, 1e-12);
+        assert!(brent.iterations() < bisection.iterations());
+
+        // The bounds may be given in either order.
+        assert_approx_eq!(finder.brent(cubic, 3.0, 2.0).unwrap().root(), ROOT, 1e-12);
+    }
+
+    #[test]
+    fn test_target() {
+        let finder = RootFinder::new().target(2.0).tolerance(1e-14);
+        let square = |x: f64| x * x;
+        assert_approx_eq!(
+            finder.newton(square, 1.0).unwrap().root(),
+            2.0_f64.sqrt(),
+            1e-14
+        );
+        assert_approx_eq!(
+            finder.brent(square, 0.0, 2.0).unwrap().root(),
+            2.0_f64.sqrt(),
+            1e-14
+        );
+    }
+
+    #[test]
+    fn test_step_convergence() {
+        // Residuals of this size are unattainable in double precision, so steps stall.
+        match RootFinder::new().tolerance(0.0).newton(cubic, 2.0) {
+            Err(Error::Stalled(solution)) => {
+                assert_eq!(solution.reason(), Some(ConvergenceReasons::Step));
+                assert_approx_eq!(solution.root(), ROOT, 1e-14);
+            }
+            _ => panic!("expected Stalled"),
+        }
+        assert!(matches!(
+            RootFinder::new().tolerance(0.0).secant(cubic, 2.0, 3.0),
+            Err(Error::Stalled(_))
+        ));
+
+        let solution = RootFinder::new()
+            .tolerance(0.0)
+            .bisection(cubic, 2.0, 3.0)
+            .unwrap();
+        assert_eq!(solution.reason(), Some(ConvergenceReasons::Bracket));
+    }
+
+    #[test]
+    fn test_errors() {
+        let finder = RootFinder::new();
+        assert!(matches!(
+            finder.brent(cubic, 3.0, 4.0),
+            Err(Error::InvalidBracket(3.0, 4.0))
+        ));
+        assert!(matches!(
+            finder.bisection(cubic, 3.0, 4.0),
+            Err(Error::InvalidBracket(3.0, 4.0))
+        ));
+        assert!(matches!(
+            finder.newton_with_derivative(|x| x * x + 1.0, |x| 2.0 * x, 0.0),
+            Err(Error::ZeroDerivative(_))
+        ));
+        assert!(matches!(
+            finder.newton(|x: f64| x.ln(), -1.0),
+            Err(Error::NonFiniteValue(_))
+        ));
+
+        // No real root, so Newton wanders.
+        match finder.max_iter(5).newton(|x| x * x + 1.0, 3.0) {
+            Err(Error::MaxIterations(solution)) => {
+                assert_eq!(solution.iterations(), 5);
+                assert_eq!(solution.reason(), None);
+                assert!(solution.residual() >= 1.0);
             }
+            _ => panic!("expected MaxIterations"),
         }
     }
 }
@@ -1,19 +1,307 @@
-// Research Gaussian elimination.
-// https://www.bing.com/videos/riverview/relatedvideo?q=gaussian+elimination&mid=4ED02AAAB6B975E7ADE54ED02AAAB6B975E7ADE5&FORM=VIRE
-
-// 3rd degree.
-// Si(x) = ai + bi(x - xi) + ci(x - xi)^2 + di(x - x1)^3
-// S'i(x) = bi + 2.ci(x - xi) + 3.di(x - xi)^2
-// S''i(x) = 2.ci + 6.di(x - xi)
-
-// 2nd degree.
-// Si(x) = ai + bi(x - x1) + ci(x - xi)^2
-// S'i(x) = bi + 2.ci(x - xi)
-// S''i(x) = 2.ci
-
-// Conditions:
-// 1. Si(xi) = yi
-// 2. Si(x1+1) = yi+1
-// 3. S'i(xi+1) = S'i+1(xi+1)
-// 4. S''i(xi+1) = S''x1+1(x1+1)
-// 5. S''0(x0) = 0 AND S''n-1(xn) = 0
+//! Cubic splines.
+//!
+//! On each interval [xi, xi+1], the spline is the cubic
+//! Si(x) = ai + bi(x - xi) + ci(x - xi)^2 + di(x - xi)^3
+//! S'i(x) = bi + 2.ci(x - xi) + 3.di(x - xi)^2
+//! S''i(x) = 2.ci + 6.di(x - xi)
+//!
+//! Conditions:
+//! 1. Si(xi) = yi
+//! 2. Si(xi+1) = yi+1
+//! 3. S'i(xi+1) = S'i+1(xi+1)
+//! 4. S''i(xi+1) = S''i+1(xi+1)
+//! 5. One condition at each end, set by the boundary.
+//!
+//! Writing Mi = S''(xi), hi = xi+1 - xi, and δi = (yi+1 - yi) / hi, conditions 1 to 4 reduce to
+//! the tridiagonal system hi-1.Mi-1 + 2(hi-1 + hi).Mi + hi.Mi+1 = 6(δi - δi-1).
+//! The coefficients are solved once, after which evaluation is O(log n).
+
+use super::linalg::solve_tridiagonal;
+
+//  --- Errors
+type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+    MismatchedLengths(usize, usize),
+    InsufficientPoints(usize),
+    UnorderedPoints,
+    SingularSystem,
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MismatchedLengths(x, y) => {
+                write!(f, "x has {} points, while y has {} points.", x, y)
+            }
+            Self::InsufficientPoints(n) => {
+                write!(f, "At least 2 points are required, {} were given.", n)
+            }
+            Self::UnorderedPoints => write!(f, "x should be strictly increasing."),
+            Self::SingularSystem => write!(f, "The spline equations are singular."),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+//  --- Enums
+
+/// Condition imposed at each end of the spline.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum SplineBoundaries {
+    /// Zero second derivative at both ends.
+    #[default]
+    Natural,
+    /// First derivative fixed at the first and last point.
+    Clamped(f64, f64),
+    /// Third derivative continuous at the second and second to last point.
+    NotAKnot,
+}
+
+//  --- Structs
+
+#[derive(Clone, Debug)]
+pub struct CubicSpline {
+    x: Vec<f64>,
+    a: Vec<f64>,
+    b: Vec<f64>,
+    c: Vec<f64>,
+    d: Vec<f64>,
+}
+
+//  --- Implementations
+
+impl CubicSpline {
+    pub fn new(x: &[f64], y: &[f64], boundary: SplineBoundaries) -> Result<Self> {
+        if x.len() != y.len() {
+            return Err(Error::MismatchedLengths(x.len(), y.len()));
+        }
+        if x.len() < 2 {
+            return Err(Error::InsufficientPoints(x.len()));
+        }
+        if x.windows(2).any(|a| a[1] <= a[0]) {
+            return Err(Error::UnorderedPoints);
+        }
+
+        let h: Vec<f64> = x.windows(2).map(|a| a[1] - a[0]).collect();
+        let delta: Vec<f64> = y
+            .windows(2)
+            .zip(h.iter())
+            .map(|(a, h)| (a[1] - a[0]) / h)
+            .collect();
+
+        let m = second_derivatives(&h, &delta, &boundary)?;
+
+        let n = h.len();
+        Ok(Self {
+            x: x.to_vec(),
+            a: y[..n].to_vec(),
+            b: (0..n)
+                .map(|i| delta[i] - h[i] * (2.0 * m[i] + m[i + 1]) / 6.0)
+                .collect(),
+            c: (0..n).map(|i| m[i] / 2.0).collect(),
+            d: (0..n).map(|i| (m[i + 1] - m[i]) / (6.0 * h[i])).collect(),
+        })
+    }
+
+    /// Value at 'xp'. Points outside the knots are extrapolated with the end cubics.
+    pub fn value(&self, xp: &f64) -> f64 {
+        let (i, dx) = self.segment(xp);
+        self.a[i] + dx * (self.b[i] + dx * (self.c[i] + dx * self.d[i]))
+    }
+
+    pub fn derivative(&self, xp: &f64) -> f64 {
+        let (i, dx) = self.segment(xp);
+        self.b[i] + dx * (2.0 * self.c[i] + 3.0 * dx * self.d[i])
+    }
+
+    pub fn second_derivative(&self, xp: &f64) -> f64 {
+        let (i, dx) = self.segment(xp);
+        2.0 * self.c[i] + 6.0 * dx * self.d[i]
+    }
+
+    /// Index of the interval containing 'xp', and the distance from its left knot.
+    fn segment(&self, xp: &f64) -> (usize, f64) {
+        let i = self
+            .x
+            .partition_point(|a| a <= xp)
+            .saturating_sub(1)
+            .min(self.a.len() - 1);
+        (i, xp - self.x[i])
+    }
+}
+
+//  --- Standalone functions
+
+/// Second derivatives at each knot.
+fn second_derivatives(h: &[f64], delta: &[f64], boundary: &SplineBoundaries) -> Result<Vec<f64>> {
+    let n = h.len();
+    let rhs = |i: usize| 6.0 * (delta[i] - delta[i - 1]);
+
+    match boundary {
+        SplineBoundaries::Natural => {
+            let mut lower = vec![0.0; n + 1];
+            let mut diagonal = vec![1.0; n + 1];
+            let mut upper = vec![0.0; n + 1];
+            let mut r = vec![0.0; n + 1];
+            for i in 1..n {
+                lower[i] = h[i - 1];
+                diagonal[i] = 2.0 * (h[i - 1] + h[i]);
+                upper[i] = h[i];
+                r[i] = rhs(i);
+            }
+            solve_tridiagonal(&lower, &diagonal, &upper, &r).map_err(|_| Error::SingularSystem)
+        }
+        SplineBoundaries::Clamped(start, end) => {
+            let mut lower = vec![0.0; n + 1];
+            let mut diagonal = vec![0.0; n + 1];
+            let mut upper = vec![0.0; n + 1];
+            let mut r = vec![0.0; n + 1];
+
+            diagonal[0] = 2.0 * h[0];
+            upper[0] = h[0];
+            r[0] = 6.0 * (delta[0] - start);
+            for i in 1..n {
+                lower[i] = h[i - 1];
+                diagonal[i] = 2.0 * (h[i - 1] + h[i]);
+                upper[i] = h[i];
+                r[i] = rhs(i);
+            }
+            lower[n] = h[n - 1];
+            diagonal[n] = 2.0 * h[n - 1];
+            r[n] = 6.0 * (end - delta[n - 1]);
+            solve_tridiagonal(&lower, &diagonal, &upper, &r).map_err(|_| Error::SingularSystem)
+        }
+        // With fewer than 4 points, the spline reduces to the polynomial through all points.
+        SplineBoundaries::NotAKnot if n == 1 => Ok(vec![0.0; 2]),
+        SplineBoundaries::NotAKnot if n == 2 => {
+            Ok(vec![2.0 * (delta[1] - delta[0]) / (h[0] + h[1]); 3])
+        }
+        SplineBoundaries::NotAKnot => {
+            // Eliminate M0 and Mn, solving for the interior second derivatives.
+            let size = n - 1;
+            let mut lower = vec![0.0; size];
+            let mut diagonal = vec![0.0; size];
+            let mut upper = vec![0.0; size];
+            let r: Vec<f64> = (1..n).map(rhs).collect();
+
+            for i in 1..n {
+                lower[i - 1] = h[i - 1];
+                diagonal[i - 1] = 2.0 * (h[i - 1] + h[i]);
+                upper[i - 1] = h[i];
+            }
+            diagonal[0] += h[0] * (h[0] + h[1]) / h[1];
+            upper[0] -= h[0].powi(2) / h[1];
+            diagonal[size - 1] += h[n - 1] * (h[n - 2] + h[n - 1]) / h[n - 2];
+            lower[size - 1] -= h[n - 1].powi(2) / h[n - 2];
+
+            let interior = solve_tridiagonal(&lower, &diagonal, &upper, &r)
+                .map_err(|_| Error::SingularSystem)?;
+
+            let first = ((h[0] + h[1]) * interior[0] - h[0] * interior[1]) / h[1];
+            let last = ((h[n - 2] + h[n - 1]) * interior[size - 1] - h[n - 1] * interior[size - 2])
+                / h[n - 2];
+
+            let mut m = Vec::with_capacity(n + 1);
+            m.push(first);
+            m.extend(interior);
+            m.push(last);
+            Ok(m)
+        }
+    }
+}
+
+//  --- Unit tests
+#[cfg(test)]
+mod test_splines {
+
+    use super::*;
+    use crate::assert_approx_eq;
+
+    fn cubic(x: &f64) -> f64 {
+        x.powi(3) - 2.0 * x.powi(2) + 0.5 * x + 1.0
+    }
+
+    fn cubic_derivative(x: &f64) -> f64 {
+        3.0 * x.powi(2) - 4.0 * x + 0.5
+    }
+
+    #[test]
+    fn test_natural_spline() {
+        let x = vec![0.0, 1.0, 2.0, 3.0];
+        let y = vec![0.0, 1.0, 8.0, 27.0];
+        let spline = CubicSpline::new(&x, &y, SplineBoundaries::Natural).unwrap();
+
+        for (a, b) in x.iter().zip(y.iter()) {
+            assert_approx_eq!(spline.value(a), b, 1e-12);
+        }
+        assert_approx_eq!(spline.second_derivative(&0.0), 0.0, 1e-12);
+        assert_approx_eq!(spline.second_derivative(&3.0), 0.0, 1e-12);
+        // Obtained by solving the natural spline equations by hand.
+        assert_approx_eq!(spline.value(&1.5), 3.15, 1e-12);
+    }
+
+    #[test]
+    fn test_continuity_at_knots() {
+        let x = vec![0.25, 0.5, 1.0, 2.0, 3.0, 5.0, 10.0];
+        let y = vec![0.071, 0.073, 0.076, 0.079, 0.082, 0.087, 0.095];
+        let spline = CubicSpline::new(&x, &y, SplineBoundaries::NotAKnot).unwrap();
+
+        let eps = 1e-9;
+        for knot in &x[1..x.len() - 1] {
+            let (left, right) = (knot - eps, knot + eps);
+            assert_approx_eq!(spline.value(&left), spline.value(&right), 1e-8);
+            assert_approx_eq!(spline.derivative(&left), spline.derivative(&right), 1e-7);
+            assert_approx_eq!(
+                spline.second_derivative(&left),
+                spline.second_derivative(&right),
+                1e-6
+            );
+        }
+    }
+
+    #[test]
+    fn test_reproduces_cubic() {
+        let x = vec![0.0, 0.4, 1.1, 1.5, 2.3, 3.0];
+        let y: Vec<f64> = x.iter().map(cubic).collect();
+
+        let not_a_knot = CubicSpline::new(&x, &y, SplineBoundaries::NotAKnot).unwrap();
+        let clamped = CubicSpline::new(
+            &x,
+            &y,
+            SplineBoundaries::Clamped(cubic_derivative(&0.0), cubic_derivative(&3.0)),
+        )
+        .unwrap();
+
+        for xp in [0.2, 0.9, 1.3, 2.0, 2.9, 3.5] {
+            assert_approx_eq!(not_a_knot.value(&xp), cubic(&xp), 1e-10);
+            assert_approx_eq!(clamped.value(&xp), cubic(&xp), 1e-10);
+            assert_approx_eq!(clamped.derivative(&xp), cubic_derivative(&xp), 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_few_points() {
+        let spline =
+            CubicSpline::new(&[0.0, 1.0], &[1.0, 3.0], SplineBoundaries::NotAKnot).unwrap();
+        assert_approx_eq!(spline.value(&0.5), 2.0, 1e-12);
+
+        let x = vec![0.0, 1.0, 3.0];
+        let y: Vec<f64> = x.iter().map(|a: &f64| a.powi(2)).collect();
+        let spline = CubicSpline::new(&x, &y, SplineBoundaries::NotAKnot).unwrap();
+        assert_approx_eq!(spline.value(&2.0), 4.0, 1e-12);
+
+        let spline = CubicSpline::new(&[0.0, 1.0], &[1.0, 3.0], SplineBoundaries::Natural).unwrap();
+        assert_approx_eq!(spline.value(&0.25), 1.5, 1e-12);
+    }
+
+    #[test]
+    fn test_invalid_points() {
+        let boundary = SplineBoundaries::Natural;
+        assert!(CubicSpline::new(&[0.0, 1.0], &[1.0], boundary).is_err());
+        assert!(CubicSpline::new(&[0.0], &[1.0], boundary).is_err());
+        assert!(CubicSpline::new(&[0.0, 1.0, 1.0], &[1.0, 2.0, 3.0], boundary).is_err());
+    }
+}
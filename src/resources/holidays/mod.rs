@@ -39,7 +39,7 @@ use reqwest::StatusCode;
 use serde::{Deserialize, Serialize};
 use serde_json::to_string_pretty;
 use std::collections::{HashMap, HashSet};
-use std::fs::{self, read_to_string};
+use std::fs;
 use std::io::Write;
 use std::{fmt::Debug, fs::File};
 
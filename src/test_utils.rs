@@ -0,0 +1,8 @@
+//! Helpers shared by the unit tests.
+
+use chrono::NaiveDate;
+
+/// Date from its year, month and day.
+pub(crate) fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
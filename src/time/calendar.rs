@@ -0,0 +1,285 @@
+//! Holiday calendars.
+//!
+//! A calendar combines a set of public holidays with the days of the week
+//! observed as the weekend. Calendars can be joined, such that a date is a
+//! holiday in the union if it is a holiday in either calendar, and a holiday
+//! in the intersection only if it is a holiday in both.
+//!
+//! ```rust,ignore
+//! use dpm::time::calendar::Calendar;
+//!
+//! let calendar = Calendar::load("ZA+US").unwrap();
+//! ```
+
+use std::collections::BTreeSet;
+
+use chrono::{Datelike, NaiveDate, Weekday};
+
+use crate::conventions::business_day::{BusinessCalendar, BusinessDay, BusinessDayConventions};
+use crate::resources::holidays::{load_holidays, Error};
+
+//  --- Constants
+const SEPARATOR: char = '+';
+
+//  --- Structs
+#[derive(Clone, Debug, PartialEq)]
+pub struct Calendar {
+    holidays: BTreeSet<NaiveDate>,
+    weekend: [bool; 7],
+}
+
+//  --- Implementations
+
+impl Calendar {
+    /// Calendar with a Saturday and Sunday weekend.
+    pub fn new(holidays: &[NaiveDate]) -> Self {
+        Self {
+            holidays: holidays.iter().copied().collect(),
+            weekend: weekend_days(&[Weekday::Sat, Weekday::Sun]),
+        }
+    }
+
+    /// Replace the days of the week observed as the weekend.
+    pub fn weekend(self, days: &[Weekday]) -> Self {
+        Self {
+            weekend: weekend_days(days),
+            ..self
+        }
+    }
+
+    /// Load the cached holidays of one or more countries, such as "ZA+US".
+    pub fn load(country_codes: &str) -> Result<Self, Error> {
+        country_codes
+            .split(SEPARATOR)
+            .map(|code| load_holidays(code.trim()).map(|h| Self::new(&h)))
+            .reduce(|a, b| Ok(a?.union(&b?)))
+            .unwrap_or_else(|| Ok(Self::default()))
+    }
+
+    /// A date is a holiday or weekend if it is one in either calendar.
+    pub fn union(&self, other: &Self) -> Self {
+        let mut weekend = self.weekend;
+        weekend
+            .iter_mut()
+            .zip(other.weekend.iter())
+            .for_each(|(a, b)| *a |= b);
+
+        Self {
+            holidays: self.holidays.union(&other.holidays).copied().collect(),
+            weekend,
+        }
+    }
+
+    /// A date is a holiday or weekend only if it is one in both calendars.
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut weekend = self.weekend;
+        weekend
+            .iter_mut()
+            .zip(other.weekend.iter())
+            .for_each(|(a, b)| *a &= b);
+
+        Self {
+            holidays: self
+                .holidays
+                .intersection(&other.holidays)
+                .copied()
+                .collect(),
+            weekend,
+        }
+    }
+
+    pub fn holidays(&self) -> Vec<NaiveDate> {
+        self.holidays.iter().copied().collect()
+    }
+
+    pub fn add_holidays(&mut self, holidays: &[NaiveDate]) -> &mut Self {
+        self.holidays.extend(holidays.iter().copied());
+        self
+    }
+
+    pub fn is_holiday(&self, date: &NaiveDate) -> bool {
+        self.holidays.contains(date)
+    }
+
+    pub fn is_weekend(&self, date: &NaiveDate) -> bool {
+        self.weekend[date.weekday().num_days_from_monday() as usize]
+    }
+
+    pub fn is_business_day(&self, date: &NaiveDate) -> bool {
+        !self.is_weekend(date) && !self.is_holiday(date)
+    }
+
+    /// Moves 'days' business days from 'date', backwards when negative.
+    /// 'date' itself need not be a business day.
+    pub fn add_business_days(&self, date: &NaiveDate, days: i64) -> Option<NaiveDate> {
+        let mut date = *date;
+        for _ in 0..days.unsigned_abs() {
+            date = if days > 0 {
+                date.succ_opt()?
+            } else {
+                date.pred_opt()?
+            };
+            while !self.is_business_day(&date) {
+                date = if days > 0 {
+                    date.succ_opt()?
+                } else {
+                    date.pred_opt()?
+                };
+            }
+        }
+        Some(date)
+    }
+
+    /// Number of business days in ['start', 'end'), negative when 'end' precedes 'start'.
+    pub fn business_days_between(&self, start: &NaiveDate, end: &NaiveDate) -> i64 {
+        if start > end {
+            return -self.business_days_between(end, start);
+        }
+        start
+            .iter_days()
+            .take_while(|d| d < end)
+            .filter(|d| self.is_business_day(d))
+            .count() as i64
+    }
+
+    /// Adjust 'date' using the business day convention.
+    pub fn adjust(&self, date: &NaiveDate, convention: &BusinessDayConventions) -> NaiveDate {
+        convention.business_day(date, self)
+    }
+}
+
+//  --- Trait implementations: Concrete
+
+impl Default for Calendar {
+    fn default() -> Self {
+        Self::new(&[])
+    }
+}
+
+impl From<Vec<NaiveDate>> for Calendar {
+    fn from(value: Vec<NaiveDate>) -> Self {
+        Self::new(&value)
+    }
+}
+
+impl BusinessCalendar<NaiveDate> for Calendar {
+    fn is_business_day(&self, value: &NaiveDate) -> bool {
+        Calendar::is_business_day(self, value)
+    }
+}
+
+//  --- Standalone functions
+
+fn weekend_days(days: &[Weekday]) -> [bool; 7] {
+    let mut weekend = [false; 7];
+    for day in days {
+        weekend[day.num_days_from_monday() as usize] = true;
+    }
+    weekend
+}
+
+//  --- Unit tests
+#[cfg(test)]
+mod test_calendar {
+
+    use super::*;
+    use crate::conventions::day_count::{DayCountConventions, DayCountWithHolidays};
+    use crate::test_utils::date;
+
+    #[test]
+    fn test_weekend() {
+        let calendar = Calendar::default();
+        assert!(calendar.is_weekend(&date(2024, 6, 15)));
+        assert!(!calendar.is_weekend(&date(2024, 6, 14)));
+
+        let calendar = Calendar::default().weekend(&[Weekday::Fri, Weekday::Sat]);
+        assert!(calendar.is_weekend(&date(2024, 6, 14)));
+        assert!(calendar.is_business_day(&date(2024, 6, 16)));
+    }
+
+    #[test]
+    fn test_union_and_intersection() {
+        let za = Calendar::new(&[date(2024, 6, 17), date(2024, 12, 25)]);
+        let us = Calendar::new(&[date(2024, 7, 4), date(2024, 12, 25)])
+            .weekend(&[Weekday::Fri, Weekday::Sat]);
+
+        let joint = za.union(&us);
+        assert!(joint.is_holiday(&date(2024, 6, 17)));
+        assert!(joint.is_holiday(&date(2024, 7, 4)));
+        assert!(joint.is_weekend(&date(2024, 6, 14)));
+        assert!(joint.is_weekend(&date(2024, 6, 16)));
+
+        let common = za.intersection(&us);
+        assert_eq!(common.holidays(), vec![date(2024, 12, 25)]);
+        assert!(common.is_weekend(&date(2024, 6, 15)));
+        assert!(!common.is_weekend(&date(2024, 6, 16)));
+    }
+
+    #[test]
+    fn test_business_day_arithmetic() {
+        let calendar = Calendar::new(&[date(2024, 6, 17)]);
+
+        // Friday plus one business day skips the weekend and the holiday.
+        let friday = date(2024, 6, 14);
+        assert_eq!(
+            calendar.add_business_days(&friday, 1),
+            Some(date(2024, 6, 18))
+        );
+        assert_eq!(
+            calendar.add_business_days(&date(2024, 6, 18), -1),
+            Some(friday)
+        );
+        assert_eq!(calendar.add_business_days(&friday, 0), Some(friday));
+
+        assert_eq!(
+            calendar.business_days_between(&friday, &date(2024, 6, 21)),
+            4
+        );
+        assert_eq!(
+            calendar.business_days_between(&date(2024, 6, 21), &friday),
+            -4
+        );
+    }
+
+    #[test]
+    fn test_adjust() {
+        let calendar = Calendar::new(&[date(2024, 6, 17)]);
+        let saturday = date(2024, 6, 15);
+
+        let adjusted = calendar.adjust(&saturday, &BusinessDayConventions::Following);
+        assert_eq!(adjusted, date(2024, 6, 18));
+        let adjusted = calendar.adjust(&saturday, &BusinessDayConventions::Preceding);
+        assert_eq!(adjusted, date(2024, 6, 14));
+
+        // Month end rolls back under modified following.
+        let adjusted = calendar.adjust(
+            &date(2024, 8, 31),
+            &BusinessDayConventions::ModifiedFollowing,
+        );
+        assert_eq!(adjusted, date(2024, 8, 30));
+
+        let dates = vec![saturday, date(2024, 6, 19)];
+        let adjusted: Vec<NaiveDate> =
+            BusinessDayConventions::Following.business_day(&dates, &calendar);
+        assert_eq!(adjusted, vec![date(2024, 6, 18), date(2024, 6, 19)]);
+    }
+
+    #[test]
+    fn test_working_days_252() {
+        let calendar = Calendar::new(&[date(2024, 6, 17)]).weekend(&[Weekday::Fri, Weekday::Sat]);
+        let days = DayCountConventions::WorkingDays252.day_count_with_holidays(
+            &date(2024, 6, 14),
+            &date(2024, 6, 21),
+            &calendar,
+        );
+        assert_eq!(days, 4.0);
+    }
+
+    #[test]
+    fn test_load() {
+        let calendar = Calendar::load("ZA+US").unwrap();
+        assert!(calendar.is_holiday(&date(2024, 6, 17)));
+        assert!(calendar.is_holiday(&date(2024, 7, 4)));
+        assert!(Calendar::load("XX").is_err());
+    }
+}
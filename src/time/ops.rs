@@ -10,8 +10,9 @@ pub trait EndOfMonth: Sized {
 
 impl EndOfMonth for NaiveDate {
     fn eom(&self) -> Option<Self> {
-        self.checked_sub_days(Days::new(self.day() as u64))
+        self.with_day(1)
             .and_then(|d| d.checked_add_months(Months::new(1)))
+            .and_then(|d| d.checked_sub_days(Days::new(1)))
     }
 }
 
@@ -24,3 +25,26 @@ where
         self.iter().map(|d| d.eom()).collect()
     }
 }
+
+//  --- Unit tests
+#[cfg(test)]
+mod test_time_ops {
+
+    use super::*;
+
+    #[test]
+    fn test_eom() {
+        let population = vec![
+            ((2024, 5, 30), (2024, 5, 31)),
+            ((2024, 2, 1), (2024, 2, 29)),
+            ((2023, 2, 28), (2023, 2, 28)),
+            ((2023, 12, 15), (2023, 12, 31)),
+        ];
+
+        for ((y, m, d), (ey, em, ed)) in population {
+            let date = NaiveDate::from_ymd_opt(y, m, d).unwrap();
+            let expected = NaiveDate::from_ymd_opt(ey, em, ed);
+            assert_eq!(date.eom(), expected);
+        }
+    }
+}
@@ -1,5 +1,23 @@
 use chrono::NaiveDate;
 
+//  --- Errors
+type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+    InvalidTenor(IntervalPeriod),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidTenor(tenor) => write!(f, "Tenor {:?} is not positive.", tenor),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
 //  --- Enums
 
 #[derive(Clone, Copy, Debug)]
@@ -10,6 +28,33 @@ pub enum IntervalPeriod {
     Years(u32),
 }
 
+//  --- Implementations
+
+impl IntervalPeriod {
+    /// Number of periods in a year. A zero tenor is rejected.
+    pub fn periods_per_year(&self) -> Result<f64> {
+        match self {
+            Self::Days(0) | Self::Weeks(0) | Self::Months(0) | Self::Years(0) => {
+                Err(Error::InvalidTenor(*self))
+            }
+            Self::Days(num) => Ok(365.0 / *num as f64),
+            Self::Weeks(num) => Ok(52.0 / *num as f64),
+            Self::Months(num) => Ok(12.0 / *num as f64),
+            Self::Years(num) => Ok(1.0 / *num as f64),
+        }
+    }
+
+    /// The period repeated 'n' times.
+    pub fn times(&self, n: u32) -> Self {
+        match self {
+            Self::Days(num) => Self::Days(num * n),
+            Self::Weeks(num) => Self::Weeks(num * n),
+            Self::Months(num) => Self::Months(num * n),
+            Self::Years(num) => Self::Years(num * n),
+        }
+    }
+}
+
 //  --- Trait implementations: Concrete
 
 impl std::ops::Add<IntervalPeriod> for NaiveDate {
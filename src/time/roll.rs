@@ -0,0 +1,211 @@
+//! Roll conventions.
+//!
+//! A roll convention fixes the day within a month on which schedule dates fall,
+//! and which months are eligible. IMM and CDS dates only fall in March, June,
+//! September and December.
+
+use chrono::{Datelike, Months, NaiveDate, Weekday};
+
+use super::ops::EndOfMonth;
+
+//  --- Constants
+const QUARTERLY_MONTHS: [u32; 4] = [3, 6, 9, 12];
+
+/// Number of months searched for the next or previous roll date.
+const SEARCH_MONTHS: u32 = 12;
+
+//  --- Enums
+// Default is not implemented, as the roll convention is always contractual.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RollConventions {
+    /// Fixed day of the month, clipped to the last day of shorter months.
+    DayOfMonth(u32),
+    /// Last day of the month.
+    EndOfMonth,
+    /// Third Wednesday of any month.
+    ThirdWednesday,
+    /// Third Wednesday of March, June, September and December.
+    IMM,
+    /// 20th of March, June, September and December.
+    CDS,
+}
+
+//  --- Structs
+struct DayOfMonth(u32);
+struct MonthEnd;
+struct ThirdWednesday;
+struct Imm;
+struct Cds;
+
+//  --- Traits
+pub trait RollRule {
+    /// Roll date within the given month, if the month is eligible.
+    fn roll_in_month(&self, year: i32, month: u32) -> Option<NaiveDate>;
+
+    /// Whether 'date' is a roll date.
+    fn is_roll_date(&self, date: &NaiveDate) -> bool {
+        self.roll_in_month(date.year(), date.month()) == Some(*date)
+    }
+
+    /// First roll date strictly after 'date'.
+    fn next_roll_date(&self, date: &NaiveDate) -> Option<NaiveDate> {
+        let month_start = date.with_day(1)?;
+        (0..=SEARCH_MONTHS)
+            .filter_map(|n| month_start.checked_add_months(Months::new(n)))
+            .filter_map(|d| self.roll_in_month(d.year(), d.month()))
+            .find(|d| d > date)
+    }
+
+    /// Last roll date strictly before 'date'.
+    fn previous_roll_date(&self, date: &NaiveDate) -> Option<NaiveDate> {
+        let month_start = date.with_day(1)?;
+        (0..=SEARCH_MONTHS)
+            .filter_map(|n| month_start.checked_sub_months(Months::new(n)))
+            .filter_map(|d| self.roll_in_month(d.year(), d.month()))
+            .find(|d| d < date)
+    }
+
+    /// Roll dates in ['start', 'end'].
+    fn roll_dates(&self, start: &NaiveDate, end: &NaiveDate) -> Vec<NaiveDate> {
+        let mut dates = Vec::new();
+        let mut current = start.pred_opt().and_then(|d| self.next_roll_date(&d));
+        while let Some(date) = current.filter(|d| d <= end) {
+            dates.push(date);
+            current = self.next_roll_date(&date);
+        }
+        dates
+    }
+}
+
+//  --- Trait implementations: Concrete
+impl RollRule for RollConventions {
+    fn roll_in_month(&self, year: i32, month: u32) -> Option<NaiveDate> {
+        match self {
+            Self::DayOfMonth(day) => DayOfMonth(*day).roll_in_month(year, month),
+            Self::EndOfMonth => MonthEnd.roll_in_month(year, month),
+            Self::ThirdWednesday => ThirdWednesday.roll_in_month(year, month),
+            Self::IMM => Imm.roll_in_month(year, month),
+            Self::CDS => Cds.roll_in_month(year, month),
+        }
+    }
+}
+
+impl RollRule for DayOfMonth {
+    fn roll_in_month(&self, year: i32, month: u32) -> Option<NaiveDate> {
+        let last_day = NaiveDate::from_ymd_opt(year, month, 1)?.eom()?.day();
+        NaiveDate::from_ymd_opt(year, month, self.0.clamp(1, last_day))
+    }
+}
+
+impl RollRule for MonthEnd {
+    fn roll_in_month(&self, year: i32, month: u32) -> Option<NaiveDate> {
+        NaiveDate::from_ymd_opt(year, month, 1)?.eom()
+    }
+}
+
+impl RollRule for ThirdWednesday {
+    fn roll_in_month(&self, year: i32, month: u32) -> Option<NaiveDate> {
+        NaiveDate::from_weekday_of_month_opt(year, month, Weekday::Wed, 3)
+    }
+}
+
+impl RollRule for Imm {
+    fn roll_in_month(&self, year: i32, month: u32) -> Option<NaiveDate> {
+        if QUARTERLY_MONTHS.contains(&month) {
+            ThirdWednesday.roll_in_month(year, month)
+        } else {
+            None
+        }
+    }
+}
+
+impl RollRule for Cds {
+    fn roll_in_month(&self, year: i32, month: u32) -> Option<NaiveDate> {
+        if QUARTERLY_MONTHS.contains(&month) {
+            NaiveDate::from_ymd_opt(year, month, 20)
+        } else {
+            None
+        }
+    }
+}
+
+//  --- Unit tests
+#[cfg(test)]
+mod test_roll {
+
+    use super::*;
+    use crate::test_utils::date;
+
+    #[test]
+    fn test_imm_dates() {
+        let expected = vec![
+            date(2024, 3, 20),
+            date(2024, 6, 19),
+            date(2024, 9, 18),
+            date(2024, 12, 18),
+        ];
+        let dates = RollConventions::IMM.roll_dates(&date(2024, 1, 1), &date(2024, 12, 31));
+        assert_eq!(dates, expected);
+
+        assert!(RollConventions::IMM.is_roll_date(&date(2024, 6, 19)));
+        assert!(!RollConventions::IMM.is_roll_date(&date(2024, 7, 17)));
+        assert!(RollConventions::ThirdWednesday.is_roll_date(&date(2024, 7, 17)));
+    }
+
+    #[test]
+    fn test_next_and_previous_imm_date() {
+        let convention = RollConventions::IMM;
+        assert_eq!(
+            convention.next_roll_date(&date(2024, 3, 20)),
+            Some(date(2024, 6, 19))
+        );
+        assert_eq!(
+            convention.next_roll_date(&date(2024, 3, 19)),
+            Some(date(2024, 3, 20))
+        );
+        assert_eq!(
+            convention.previous_roll_date(&date(2024, 3, 20)),
+            Some(date(2023, 12, 20))
+        );
+        assert_eq!(
+            convention.previous_roll_date(&date(2024, 2, 1)),
+            Some(date(2023, 12, 20))
+        );
+    }
+
+    #[test]
+    fn test_next_and_previous_cds_date() {
+        let convention = RollConventions::CDS;
+        assert_eq!(
+            convention.next_roll_date(&date(2024, 12, 20)),
+            Some(date(2025, 3, 20))
+        );
+        assert_eq!(
+            convention.next_roll_date(&date(2024, 11, 5)),
+            Some(date(2024, 12, 20))
+        );
+        assert_eq!(
+            convention.previous_roll_date(&date(2024, 3, 20)),
+            Some(date(2023, 12, 20))
+        );
+        assert!(convention.is_roll_date(&date(2024, 9, 20)));
+        assert!(!convention.is_roll_date(&date(2024, 10, 20)));
+    }
+
+    #[test]
+    fn test_end_of_month_and_day_of_month() {
+        let convention = RollConventions::EndOfMonth;
+        assert_eq!(
+            convention.next_roll_date(&date(2024, 1, 31)),
+            Some(date(2024, 2, 29))
+        );
+        assert!(convention.is_roll_date(&date(2023, 2, 28)));
+
+        let convention = RollConventions::DayOfMonth(31);
+        assert_eq!(convention.roll_in_month(2024, 4), Some(date(2024, 4, 30)));
+        assert_eq!(
+            convention.previous_roll_date(&date(2024, 3, 31)),
+            Some(date(2024, 2, 29))
+        );
+    }
+}
@@ -0,0 +1,577 @@
+//! Schedule generation.
+//!
+//! A schedule is rolled from either the termination date (front stubs) or the
+//! effective date (back stubs) in steps of the tenor. The effective and termination
+//! dates are not rolled, and are not adjusted unless requested. Each period holds the
+//! unadjusted and adjusted accrual dates, and the payment date.
+
+use chrono::{Datelike, NaiveDate};
+
+use super::ops::EndOfMonth;
+use super::periods::IntervalPeriod;
+use super::roll::{RollConventions, RollRule};
+use crate::conventions::business_day::{
+    BusinessCalendar, BusinessDay, BusinessDayConventions, BusinessDayOperations,
+};
+use crate::conventions::day_count::DayCount;
+use crate::interest::term_structure::{Term, TermStructure};
+
+//  --- Errors
+type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+    InvalidDates(NaiveDate, NaiveDate),
+    InvalidRollDay(u32),
+    InvalidTenor(IntervalPeriod),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidDates(effective, termination) => write!(
+                f,
+                "Effective date {} should precede termination date {}.",
+                effective, termination
+            ),
+            Self::InvalidRollDay(day) => write!(f, "Roll day {} is not in [1, 31].", day),
+            Self::InvalidTenor(tenor) => write!(f, "Tenor {:?} is not positive.", tenor),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+//  --- Enums
+
+/// Position and length of the irregular period.
+#[derive(Clone, Copy, Debug, Default)]
+pub enum StubConventions {
+    #[default]
+    ShortFront,
+    LongFront,
+    ShortBack,
+    LongBack,
+}
+
+//  --- Structs
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SchedulePeriod {
+    pub unadjusted_start: NaiveDate,
+    pub unadjusted_end: NaiveDate,
+    pub adjusted_start: NaiveDate,
+    pub adjusted_end: NaiveDate,
+    pub payment_date: NaiveDate,
+}
+
+#[derive(Clone, Debug)]
+pub struct Schedule(Vec<SchedulePeriod>);
+
+#[derive(Clone, Debug)]
+pub struct ScheduleBuilder {
+    effective: NaiveDate,
+    termination: NaiveDate,
+    tenor: IntervalPeriod,
+    stub: StubConventions,
+    roll: Option<RollConventions>,
+    end_of_month: bool,
+    business_day_convention: BusinessDayConventions,
+    adjust_effective_and_termination: bool,
+    payment_lag: u32,
+}
+
+//  --- Implementations
+
+impl ScheduleBuilder {
+    pub fn new(effective: NaiveDate, termination: NaiveDate, tenor: IntervalPeriod) -> Self {
+        Self {
+            effective,
+            termination,
+            tenor,
+            stub: StubConventions::default(),
+            roll: None,
+            end_of_month: false,
+            business_day_convention: BusinessDayConventions::default(),
+            adjust_effective_and_termination: false,
+            payment_lag: 0,
+        }
+    }
+
+    pub fn tenor(self, tenor: IntervalPeriod) -> Self {
+        Self { tenor, ..self }
+    }
+
+    pub fn stub(self, stub: StubConventions) -> Self {
+        Self { stub, ..self }
+    }
+
+    /// Convention regular dates roll on. Defaults to the day of the
+    /// date the schedule is rolled from.
+    pub fn roll_convention(self, roll: RollConventions) -> Self {
+        Self {
+            roll: Some(roll),
+            ..self
+        }
+    }
+
+    /// Day of the month regular dates roll on.
+    pub fn roll_day(self, roll_day: u32) -> Self {
+        self.roll_convention(RollConventions::DayOfMonth(roll_day))
+    }
+
+    /// Roll regular dates to the end of the month, when the date the
+    /// schedule is rolled from is the end of a month.
+    pub fn end_of_month(self, end_of_month: bool) -> Self {
+        Self {
+            end_of_month,
+            ..self
+        }
+    }
+
+    pub fn business_day_convention(self, business_day_convention: BusinessDayConventions) -> Self {
+        Self {
+            business_day_convention,
+            ..self
+        }
+    }
+
+    /// Apply the business day convention to the effective and termination dates.
+    pub fn adjust_effective_and_termination(self, adjust: bool) -> Self {
+        Self {
+            adjust_effective_and_termination: adjust,
+            ..self
+        }
+    }
+
+    /// Number of business days between the adjusted accrual end date and the payment date.
+    pub fn payment_lag(self, payment_lag: u32) -> Self {
+        Self {
+            payment_lag,
+            ..self
+        }
+    }
+
+    /// Build the schedule, adjusting dates against 'public_holidays', which is either
+    /// a list of holidays or a `Calendar`.
+    pub fn build<H>(&self, public_holidays: &H) -> Result<Schedule>
+    where
+        H: BusinessCalendar<NaiveDate>,
+    {
+        if self.effective >= self.termination {
+            return Err(Error::InvalidDates(self.effective, self.termination));
+        }
+        if let Some(RollConventions::DayOfMonth(day)) = self.roll {
+            if !(1..=31).contains(&day) {
+                return Err(Error::InvalidRollDay(day));
+            }
+        }
+        if self.effective + self.tenor <= self.effective {
+            return Err(Error::InvalidTenor(self.tenor));
+        }
+
+        let dates = self.unadjusted_dates();
+        let last = dates.len() - 1;
+
+        let adjusted: Vec<NaiveDate> = dates
+            .iter()
+            .enumerate()
+            .map(|(i, date)| {
+                if (i == 0 || i == last) && !self.adjust_effective_and_termination {
+                    *date
+                } else {
+                    self.business_day_convention
+                        .business_day(date, public_holidays)
+                }
+            })
+            .collect();
+
+        let periods = (0..last)
+            .map(|i| {
+                let payment_date = self
+                    .business_day_convention
+                    .business_day(&adjusted[i + 1], public_holidays);
+
+                SchedulePeriod {
+                    unadjusted_start: dates[i],
+                    unadjusted_end: dates[i + 1],
+                    adjusted_start: adjusted[i],
+                    adjusted_end: adjusted[i + 1],
+                    payment_date: add_business_days(
+                        &payment_date,
+                        self.payment_lag,
+                        public_holidays,
+                    ),
+                }
+            })
+            .collect();
+
+        Ok(Schedule(periods))
+    }
+
+    /// Unadjusted period boundaries, including the effective and termination dates.
+    fn unadjusted_dates(&self) -> Vec<NaiveDate> {
+        let backward = matches!(
+            self.stub,
+            StubConventions::ShortFront | StubConventions::LongFront
+        );
+        let (anchor, boundary) = if backward {
+            (self.termination, self.effective)
+        } else {
+            (self.effective, self.termination)
+        };
+
+        let mut dates = vec![anchor];
+        let mut n = 1;
+        loop {
+            let date = self.regular_date(&anchor, n, backward);
+            let is_inside = if backward {
+                date > boundary
+            } else {
+                date < boundary
+            };
+            if !is_inside {
+                break;
+            }
+            dates.push(date);
+            n += 1;
+        }
+
+        // Merge the stub into the adjacent regular period.
+        let has_stub = self.regular_date(&anchor, n, backward) != boundary;
+        let is_long = matches!(
+            self.stub,
+            StubConventions::LongFront | StubConventions::LongBack
+        );
+        if has_stub && is_long && dates.len() > 1 {
+            dates.pop();
+        }
+
+        dates.push(boundary);
+        if backward {
+            dates.reverse();
+        }
+        dates
+    }
+
+    /// Regular date 'n' tenors from 'anchor'.
+    fn regular_date(&self, anchor: &NaiveDate, n: u32, backward: bool) -> NaiveDate {
+        let date = if backward {
+            *anchor - self.tenor.times(n)
+        } else {
+            *anchor + self.tenor.times(n)
+        };
+
+        if !matches!(
+            self.tenor,
+            IntervalPeriod::Months(_) | IntervalPeriod::Years(_)
+        ) {
+            return date;
+        }
+
+        let anchor_is_eom = anchor.eom().is_some_and(|d| d == *anchor);
+        if self.end_of_month && anchor_is_eom {
+            return date.eom().unwrap_or(date);
+        }
+
+        // Months without a roll date, such as non IMM months, are left as is.
+        self.roll
+            .and_then(|roll| roll.roll_in_month(date.year(), date.month()))
+            .unwrap_or(date)
+    }
+}
+
+impl Schedule {
+    pub fn periods(&self) -> &[SchedulePeriod] {
+        &self.0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Unadjusted period boundaries, including the effective and termination dates.
+    pub fn unadjusted_dates(&self) -> Vec<NaiveDate> {
+        let mut dates: Vec<NaiveDate> = self.0.iter().map(|p| p.unadjusted_start).collect();
+        dates.extend(self.0.last().map(|p| p.unadjusted_end));
+        dates
+    }
+
+    /// Adjusted period boundaries, including the effective and termination dates.
+    pub fn adjusted_dates(&self) -> Vec<NaiveDate> {
+        let mut dates: Vec<NaiveDate> = self.adjusted_start_dates();
+        dates.extend(self.0.last().map(|p| p.adjusted_end));
+        dates
+    }
+
+    pub fn adjusted_start_dates(&self) -> Vec<NaiveDate> {
+        self.0.iter().map(|p| p.adjusted_start).collect()
+    }
+
+    pub fn adjusted_end_dates(&self) -> Vec<NaiveDate> {
+        self.0.iter().map(|p| p.adjusted_end).collect()
+    }
+
+    pub fn payment_dates(&self) -> Vec<NaiveDate> {
+        self.0.iter().map(|p| p.payment_date).collect()
+    }
+
+    /// Adjusted accrual start and end dates.
+    pub fn accrual_term(&self) -> Term<NaiveDate> {
+        Term::new(&self.adjusted_start_dates(), &self.adjusted_end_dates())
+    }
+
+    /// Accrual year fractions of each period.
+    pub fn year_fractions<A>(&self, day_count: &A) -> Vec<f64>
+    where
+        A: DayCount<NaiveDate, NaiveDate, f64>,
+    {
+        self.0
+            .iter()
+            .map(|p| day_count.year_fraction(&p.adjusted_start, &p.adjusted_end))
+            .collect()
+    }
+}
+
+//  --- Standalone functions
+
+fn add_business_days<H>(date: &NaiveDate, days: u32, public_holidays: &H) -> NaiveDate
+where
+    H: BusinessCalendar<NaiveDate>,
+{
+    let mut date = *date;
+    for _ in 0..days {
+        date = date.add_day();
+        while !public_holidays.is_business_day(&date) {
+            date = date.add_day();
+        }
+    }
+    date
+}
+
+//  --- Unit tests
+#[cfg(test)]
+mod test_schedule {
+
+    use super::*;
+    use crate::test_utils::date;
+
+    #[test]
+    fn test_regular_schedule() {
+        let schedule = ScheduleBuilder::new(
+            date(2024, 1, 15),
+            date(2025, 1, 15),
+            IntervalPeriod::Months(3),
+        )
+        .business_day_convention(BusinessDayConventions::Actual)
+        .build(&vec![])
+        .unwrap();
+
+        let expected = vec![
+            date(2024, 1, 15),
+            date(2024, 4, 15),
+            date(2024, 7, 15),
+            date(2024, 10, 15),
+            date(2025, 1, 15),
+        ];
+        assert_eq!(schedule.unadjusted_dates(), expected);
+        assert_eq!(schedule.adjusted_dates(), expected);
+        assert_eq!(schedule.len(), 4);
+    }
+
+    #[test]
+    fn test_stubs() {
+        let (effective, termination) = (date(2024, 2, 1), date(2025, 1, 15));
+        let tenor = IntervalPeriod::Months(3);
+        let population = vec![
+            (
+                StubConventions::ShortFront,
+                vec![
+                    date(2024, 2, 1),
+                    date(2024, 4, 15),
+                    date(2024, 7, 15),
+                    date(2024, 10, 15),
+                    date(2025, 1, 15),
+                ],
+            ),
+            (
+                StubConventions::LongFront,
+                vec![
+                    date(2024, 2, 1),
+                    date(2024, 7, 15),
+                    date(2024, 10, 15),
+                    date(2025, 1, 15),
+                ],
+            ),
+            (
+                StubConventions::ShortBack,
+                vec![
+                    date(2024, 2, 1),
+                    date(2024, 5, 1),
+                    date(2024, 8, 1),
+                    date(2024, 11, 1),
+                    date(2025, 1, 15),
+                ],
+            ),
+            (
+                StubConventions::LongBack,
+                vec![
+                    date(2024, 2, 1),
+                    date(2024, 5, 1),
+                    date(2024, 8, 1),
+                    date(2025, 1, 15),
+                ],
+            ),
+        ];
+
+        for (stub, expected) in population {
+            let schedule = ScheduleBuilder::new(effective, termination, tenor)
+                .stub(stub)
+                .build(&vec![])
+                .unwrap();
+            assert_eq!(schedule.unadjusted_dates(), expected, "{:?}", stub);
+        }
+    }
+
+    #[test]
+    fn test_end_of_month() {
+        // Rolled from the last day of February, the two conventions differ.
+        let builder = ScheduleBuilder::new(
+            date(2023, 2, 28),
+            date(2023, 6, 30),
+            IntervalPeriod::Months(1),
+        )
+        .stub(StubConventions::ShortBack)
+        .business_day_convention(BusinessDayConventions::Actual);
+
+        let schedule = builder.clone().end_of_month(true).build(&vec![]).unwrap();
+        let expected = vec![
+            date(2023, 2, 28),
+            date(2023, 3, 31),
+            date(2023, 4, 30),
+            date(2023, 5, 31),
+            date(2023, 6, 30),
+        ];
+        assert_eq!(schedule.unadjusted_dates(), expected);
+
+        let schedule = builder.build(&vec![]).unwrap();
+        let expected = vec![
+            date(2023, 2, 28),
+            date(2023, 3, 28),
+            date(2023, 4, 28),
+            date(2023, 5, 28),
+            date(2023, 6, 28),
+            date(2023, 6, 30),
+        ];
+        assert_eq!(schedule.unadjusted_dates(), expected);
+    }
+
+    #[test]
+    fn test_roll_day() {
+        let schedule = ScheduleBuilder::new(
+            date(2024, 1, 10),
+            date(2024, 7, 20),
+            IntervalPeriod::Months(2),
+        )
+        .stub(StubConventions::ShortFront)
+        .roll_day(30)
+        .build(&vec![])
+        .unwrap();
+
+        let expected = vec![
+            date(2024, 1, 10),
+            date(2024, 1, 30),
+            date(2024, 3, 30),
+            date(2024, 5, 30),
+            date(2024, 7, 20),
+        ];
+        assert_eq!(schedule.unadjusted_dates(), expected);
+    }
+
+    #[test]
+    fn test_imm_roll() {
+        let schedule = ScheduleBuilder::new(
+            date(2024, 3, 20),
+            date(2025, 3, 19),
+            IntervalPeriod::Months(3),
+        )
+        .stub(StubConventions::ShortBack)
+        .roll_convention(RollConventions::IMM)
+        .business_day_convention(BusinessDayConventions::Actual)
+        .build(&vec![])
+        .unwrap();
+
+        let expected = vec![
+            date(2024, 3, 20),
+            date(2024, 6, 19),
+            date(2024, 9, 18),
+            date(2024, 12, 18),
+            date(2025, 3, 19),
+        ];
+        assert_eq!(schedule.unadjusted_dates(), expected);
+    }
+
+    #[test]
+    fn test_adjustment_and_payment_lag() {
+        // 2024-06-15 is a Saturday and 2024-06-17 a public holiday.
+        let public_holidays = vec![date(2024, 6, 17)];
+        let schedule = ScheduleBuilder::new(
+            date(2024, 3, 15),
+            date(2024, 9, 15),
+            IntervalPeriod::Months(3),
+        )
+        .business_day_convention(BusinessDayConventions::Following)
+        .payment_lag(2)
+        .build(&public_holidays)
+        .unwrap();
+
+        let periods = schedule.periods();
+        assert_eq!(periods[0].unadjusted_end, date(2024, 6, 15));
+        assert_eq!(periods[0].adjusted_end, date(2024, 6, 18));
+        assert_eq!(periods[0].payment_date, date(2024, 6, 20));
+
+        // Termination date is a Sunday, and is not adjusted.
+        assert_eq!(periods[1].adjusted_end, date(2024, 9, 15));
+        assert_eq!(periods[1].payment_date, date(2024, 9, 18));
+    }
+
+    #[test]
+    fn test_adjust_effective_and_termination() {
+        let schedule = ScheduleBuilder::new(
+            date(2024, 3, 15),
+            date(2024, 9, 15),
+            IntervalPeriod::Months(3),
+        )
+        .business_day_convention(BusinessDayConventions::Following)
+        .adjust_effective_and_termination(true)
+        .build(&vec![])
+        .unwrap();
+
+        assert_eq!(schedule.adjusted_dates().last(), Some(&date(2024, 9, 16)));
+        assert_eq!(schedule.unadjusted_dates().last(), Some(&date(2024, 9, 15)));
+    }
+
+    #[test]
+    fn test_errors() {
+        let tenor = IntervalPeriod::Months(3);
+        let result = ScheduleBuilder::new(date(2025, 1, 1), date(2024, 1, 1), tenor).build(&vec![]);
+        assert!(matches!(result, Err(Error::InvalidDates(_, _))));
+
+        let result = ScheduleBuilder::new(date(2024, 1, 1), date(2025, 1, 1), tenor)
+            .roll_day(32)
+            .build(&vec![]);
+        assert!(matches!(result, Err(Error::InvalidRollDay(32))));
+
+        let result = ScheduleBuilder::new(
+            date(2024, 1, 1),
+            date(2025, 1, 1),
+            IntervalPeriod::Months(0),
+        )
+        .build(&vec![]);
+        assert!(matches!(result, Err(Error::InvalidTenor(_))));
+    }
+}